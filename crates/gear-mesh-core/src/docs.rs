@@ -12,9 +12,15 @@ pub struct DocComment {
     /// 詳細な説明
     pub description: Option<String>,
     /// @example セクション
-    pub examples: Vec<String>,
+    pub examples: Vec<DocExample>,
     /// その他のセクション
     pub sections: Vec<DocSection>,
+    /// `#[doc(hidden)]` が付いているか (`@internal` として出力)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+    /// `#[deprecated]` の内容 (`@deprecated` として出力)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Deprecation>,
 }
 
 impl DocComment {
@@ -25,6 +31,8 @@ impl DocComment {
             description: None,
             examples: Vec::new(),
             sections: Vec::new(),
+            hidden: false,
+            deprecated: None,
         }
     }
 
@@ -32,9 +40,7 @@ impl DocComment {
     pub fn summary(text: impl Into<String>) -> Self {
         Self {
             summary: text.into(),
-            description: None,
-            examples: Vec::new(),
-            sections: Vec::new(),
+            ..Self::empty()
         }
     }
 
@@ -46,36 +52,35 @@ impl DocComment {
         let mut examples = Vec::new();
         let mut sections = Vec::new();
         let mut current_section: Option<(String, String)> = None;
-        let mut in_code_block = false;
-        let mut code_block_content = String::new();
+        let mut code_block: Option<String> = None;
+        let mut code_block_content = Vec::new();
         let mut parsing_summary = true;
 
         for line in lines {
             let trimmed = line.trim();
 
             // コードブロックの処理
-            if trimmed.starts_with("```") {
-                if in_code_block {
+            if let Some(info) = trimmed.strip_prefix("```") {
+                if let Some(language) = code_block.take() {
                     // コードブロック終了
-                    in_code_block = false;
                     if let Some((ref section_name, _)) = current_section
                         && (section_name == "Examples" || section_name == "Example")
                     {
-                        examples.push(code_block_content.clone());
+                        examples.push(DocExample {
+                            language,
+                            code: dedent(&code_block_content),
+                        });
                     }
                     code_block_content.clear();
                 } else {
                     // コードブロック開始
-                    in_code_block = true;
+                    code_block = Some(fence_language(info));
                 }
                 continue;
             }
 
-            if in_code_block {
-                if !code_block_content.is_empty() {
-                    code_block_content.push('\n');
-                }
-                code_block_content.push_str(line);
+            if code_block.is_some() {
+                code_block_content.push(line);
                 continue;
             }
 
@@ -141,59 +146,317 @@ impl DocComment {
             },
             examples,
             sections,
+            hidden: false,
+            deprecated: None,
+        }
+    }
+
+    /// intra-docリンクを `{@link X}` に置き換える
+    ///
+    /// `resolve` はリンク先の型名 (パスの最後の要素) から出力される型名を返します。
+    /// 解決できないリンクは表示名だけを残します。
+    pub fn with_links(&self, resolve: impl Fn(&str) -> Option<String>) -> Self {
+        let link = |text: &str| replace_links(text, &resolve);
+        Self {
+            summary: link(&self.summary),
+            description: self.description.as_deref().map(link),
+            examples: self.examples.clone(),
+            sections: self
+                .sections
+                .iter()
+                .map(|section| DocSection {
+                    name: section.name.clone(),
+                    content: link(&section.content),
+                })
+                .collect(),
+            hidden: self.hidden,
+            deprecated: self.deprecated.clone(),
         }
     }
 
     /// JSDoc形式に変換
     pub fn to_jsdoc(&self) -> String {
-        let mut lines = Vec::new();
-        lines.push("/**".to_string());
+        let mut lines = self.jsdoc_body();
+        let tags = self.jsdoc_tags();
+        if !lines.is_empty() && !tags.is_empty() {
+            lines.push(String::new());
+        }
+        lines.extend(tags);
+        jsdoc_block("", &lines)
+    }
 
-        // サマリー
+    /// 説明部分の行 (サマリー・説明・タグに対応しないセクション)
+    ///
+    /// 空文字列は空行を表します。
+    pub fn jsdoc_body(&self) -> Vec<String> {
+        let mut lines = Vec::new();
         if !self.summary.is_empty() {
-            lines.push(format!(" * {}", self.summary));
+            lines.push(self.summary.clone());
         }
-
-        // 説明
         if let Some(ref desc) = self.description {
-            lines.push(" *".to_string());
-            for line in desc.lines() {
-                lines.push(format!(" * {}", line));
-            }
+            push_paragraph(&mut lines, desc.lines().map(str::to_string));
         }
-
-        // サンプル
-        for example in &self.examples {
-            lines.push(" *".to_string());
-            lines.push(" * @example".to_string());
-            lines.push(" * ```typescript".to_string());
-            for line in example.lines() {
-                lines.push(format!(" * {}", line));
-            }
-            lines.push(" * ```".to_string());
+        for section in self
+            .sections
+            .iter()
+            .filter(|s| section_tag(&s.name).is_none())
+        {
+            let heading = format!("# {}", section.name);
+            push_paragraph(
+                &mut lines,
+                std::iter::once(heading).chain(section.content.lines().map(str::to_string)),
+            );
         }
+        lines
+    }
 
-        // その他のセクション
+    /// タグ部分の行 (`@throws` などに対応するセクション・`@example`・`@internal`)
+    pub fn jsdoc_tags(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(deprecation) = &self.deprecated {
+            lines.push(deprecation.to_tag());
+        }
         for section in &self.sections {
-            lines.push(" *".to_string());
-            lines.push(format!(" * @{}", section.name.to_lowercase()));
-            for line in section.content.lines() {
-                lines.push(format!(" * {}", line));
+            let Some((tag, label)) = section_tag(&section.name) else {
+                continue;
+            };
+            lines.push(tag.to_string());
+            for (i, line) in section.content.lines().enumerate() {
+                match label {
+                    Some(label) if i == 0 => lines.push(format!("{label}: {line}")),
+                    _ => lines.push(line.to_string()),
+                }
             }
         }
 
-        lines.push(" */".to_string());
-        lines.join("\n")
+        // Rustのサンプルはそのまま使えないので出力しない
+        for example in self.examples.iter().filter(|e| !e.is_rust()) {
+            lines.push("@example".to_string());
+            lines.push(format!("```{}", example.language));
+            lines.extend(example.code.lines().map(str::to_string));
+            lines.push("```".to_string());
+        }
+
+        if self.hidden {
+            lines.push("@internal".to_string());
+        }
+        lines
     }
 
     /// 単一行JSDocコメント
     pub fn to_inline_jsdoc(&self) -> String {
-        if self.summary.is_empty() {
+        let parts = [
+            Some(self.summary.clone()).filter(|summary| !summary.is_empty()),
+            self.deprecated.as_ref().map(Deprecation::to_tag),
+            self.hidden.then(|| "@internal".to_string()),
+        ];
+        let parts = parts.into_iter().flatten().collect::<Vec<_>>();
+        if parts.is_empty() {
             String::new()
         } else {
-            format!("/** {} */", self.summary)
+            format!("/** {} */", parts.join(" "))
+        }
+    }
+
+    /// `#[deprecated]` が付いているか
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecated.is_some()
+    }
+}
+
+/// `#[deprecated(since = "...", note = "...")]`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deprecation {
+    /// 非推奨になったバージョン
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    /// 移行先などの説明
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl Deprecation {
+    /// `@deprecated Since 1.2.0. Use `email` instead.`
+    pub fn to_tag(&self) -> String {
+        match self.describe() {
+            Some(text) => format!("@deprecated {text}"),
+            None => "@deprecated".to_string(),
+        }
+    }
+
+    /// バージョンと説明をまとめた文 (どちらもなければ `None`)
+    pub fn describe(&self) -> Option<String> {
+        let since = self.since.as_ref().map(|since| format!("Since {since}."));
+        let parts = [since, self.note.clone()];
+        let parts = parts.into_iter().flatten().collect::<Vec<_>>();
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+}
+
+/// `/** ... */` のブロックを組み立てる (空文字列の行は ` *` になる)
+pub fn jsdoc_block(indent: &str, lines: &[String]) -> String {
+    let mut output = vec![format!("{indent}/**")];
+    for line in lines {
+        if line.is_empty() {
+            output.push(format!("{indent} *"));
+        } else {
+            output.push(format!("{indent} * {line}"));
         }
     }
+    output.push(format!("{indent} */"));
+    output.join("\n")
+}
+
+/// 空行を挟んで段落を追加
+fn push_paragraph(lines: &mut Vec<String>, paragraph: impl Iterator<Item = String>) {
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines.extend(paragraph);
+}
+
+/// rustdocの定番セクションに対応するJSDocタグと、内容の先頭に付けるラベル
+fn section_tag(name: &str) -> Option<(&'static str, Option<&'static str>)> {
+    match name.to_lowercase().as_str() {
+        "errors" => Some(("@throws", None)),
+        "panics" => Some(("@throws", Some("Panics"))),
+        "safety" => Some(("@remarks", Some("Safety"))),
+        "returns" => Some(("@returns", None)),
+        _ => None,
+    }
+}
+
+/// ドキュメント内のサンプルコード
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocExample {
+    /// コードフェンスの言語 (指定がなければ `rust`)
+    pub language: String,
+    /// コード
+    pub code: String,
+}
+
+impl DocExample {
+    pub fn new(language: impl Into<String>, code: impl Into<String>) -> Self {
+        Self {
+            language: language.into(),
+            code: code.into(),
+        }
+    }
+
+    /// Rustのサンプルか
+    pub fn is_rust(&self) -> bool {
+        self.language == "rust"
+    }
+}
+
+/// コードフェンスの情報文字列から言語を取り出す (`ignore` などのrustdoc属性は除く)
+fn fence_language(info: &str) -> String {
+    const RUSTDOC_ATTRIBUTES: &[&str] = &[
+        "rust",
+        "ignore",
+        "no_run",
+        "should_panic",
+        "compile_fail",
+        "test_harness",
+        "standalone_crate",
+    ];
+    info.split(|c: char| c == ',' || c.is_whitespace())
+        .map(str::trim)
+        .find(|token| {
+            !token.is_empty()
+                && !RUSTDOC_ATTRIBUTES.contains(token)
+                && !token.starts_with("edition")
+        })
+        .unwrap_or("rust")
+        .to_string()
+}
+
+/// 共通のインデントを取り除く (`///` の後の空白など)
+fn dedent(lines: &[&str]) -> String {
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 文章中の `[`Type`]` や `[text](crate::Type)` を置き換える
+fn replace_links(text: &str, resolve: &dyn Fn(&str) -> Option<String>) -> String {
+    let mut output = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        let (before, link) = rest.split_at(start);
+        output.push_str(before);
+        // `items[0]` のような添字はリンクではない
+        let indexing = output
+            .chars()
+            .last()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_');
+        match parse_link(link).filter(|_| !indexing) {
+            Some((len, target, label)) => {
+                let text = label.unwrap_or(&link[1..len - 1]);
+                match resolve(target) {
+                    Some(name) if label.is_some() => {
+                        output.push_str(&format!("{{@link {name} | {text}}}"))
+                    }
+                    Some(name) => output.push_str(&format!("{{@link {name}}}")),
+                    None => output.push_str(text),
+                }
+                rest = &link[len..];
+            }
+            None => {
+                output.push('[');
+                rest = &link[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// `[` で始まる文字列からintra-docリンクを読み取る
+///
+/// (読んだ長さ, リンク先の型名, 表示名) を返します。
+fn parse_link(text: &str) -> Option<(usize, &str, Option<&str>)> {
+    let close = text.find(']')?;
+    let inner = &text[1..close];
+    let after = &text[close + 1..];
+    if let Some(target) = after.strip_prefix('(') {
+        let end = target.find(')')?;
+        return Some((close + end + 3, link_target(&target[..end])?, Some(inner)));
+    }
+    if after.starts_with('[') {
+        return None;
+    }
+    Some((close + 1, link_target(inner)?, None))
+}
+
+/// intra-docリンクのリンク先 (`crate::api::Order` や `struct@User`) から型名を取り出す
+fn link_target(target: &str) -> Option<&str> {
+    let target = target.trim();
+    let target = target
+        .strip_prefix('`')
+        .and_then(|t| t.strip_suffix('`'))
+        .unwrap_or(target);
+    let target = target.split_once('@').map_or(target, |(_, path)| path);
+    let target = target.trim_end_matches("()").trim_end_matches('!');
+    let is_ident = |segment: &str| {
+        segment
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+    };
+    target
+        .split("::")
+        .all(is_ident)
+        .then(|| target.rsplit("::").next())?
 }
 
 /// ドキュメントセクション
@@ -236,8 +499,10 @@ let user = User::new("Alice");
         let doc = DocComment {
             summary: "A user object".to_string(),
             description: None,
-            examples: vec!["const user = new User();".to_string()],
+            examples: vec![DocExample::new("typescript", "const user = new User();")],
             sections: vec![],
+            hidden: false,
+            deprecated: None,
         };
         let jsdoc = doc.to_jsdoc();
         assert!(jsdoc.contains("A user object"));
@@ -64,11 +64,13 @@ fn test_to_jsdoc_with_sections() {
     let doc = DocComment {
         summary: "Calculate sum".to_string(),
         description: Some("This function adds two numbers".to_string()),
-        examples: vec!["const result = add(1, 2);".to_string()],
+        examples: vec![DocExample::new("ts", "const result = add(1, 2);")],
         sections: vec![DocSection {
             name: "Returns".to_string(),
             content: "The sum of the two numbers".to_string(),
         }],
+        hidden: false,
+        deprecated: None,
     };
 
     let jsdoc = doc.to_jsdoc();
@@ -91,3 +93,121 @@ fn test_inline_jsdoc_with_content() {
     let inline = doc.to_inline_jsdoc();
     assert_eq!(inline, "/** A user ID */");
 }
+
+#[test]
+fn test_parse_keeps_fence_language() {
+    let doc = r#"Summary
+
+# Examples
+
+```
+let user = User::new();
+```
+
+```ts
+const user: User = { id: 1 };
+```
+
+```rust,no_run
+let order = Order::load();
+```
+"#;
+    let parsed = DocComment::parse(doc);
+    let languages: Vec<_> = parsed
+        .examples
+        .iter()
+        .map(|e| e.language.as_str())
+        .collect();
+    assert_eq!(languages, ["rust", "ts", "rust"]);
+    assert_eq!(parsed.examples[1].code, "const user: User = { id: 1 };");
+
+    // Rustのサンプルは出力しない
+    let jsdoc = parsed.to_jsdoc();
+    assert!(jsdoc.contains(" * ```ts\n * const user: User = { id: 1 };\n * ```"));
+    assert!(!jsdoc.contains("User::new"));
+    assert!(!jsdoc.contains("Order::load"));
+}
+
+#[test]
+fn test_standard_sections_map_to_jsdoc_tags() {
+    let doc = r#"Transfer money
+
+# Errors
+
+Fails when the balance is too low.
+
+# Panics
+
+Panics if the amount is zero.
+
+# Safety
+
+The account must be locked.
+
+# Arguments
+
+* `amount` - Amount to move
+"#;
+    let jsdoc = DocComment::parse(doc).to_jsdoc();
+    assert!(jsdoc.contains(" * @throws\n * Fails when the balance is too low."));
+    assert!(jsdoc.contains(" * @throws\n * Panics: Panics if the amount is zero."));
+    assert!(jsdoc.contains(" * @remarks\n * Safety: The account must be locked."));
+    assert!(jsdoc.contains(" * # Arguments\n * * `amount` - Amount to move"));
+    assert!(!jsdoc.contains("@errors"));
+    assert!(!jsdoc.contains("@arguments"));
+}
+
+#[test]
+fn test_intra_doc_links() {
+    let doc = DocComment::parse(
+        "Placed by a [`User`], see [the order](crate::api::Order) and [`Cart::total`].\n\nUses items[0] and [docs](https://example.com).",
+    );
+    let linked = doc.with_links(|name| match name {
+        "User" => Some("User".to_string()),
+        "Order" => Some("PurchaseOrder".to_string()),
+        _ => None,
+    });
+    assert_eq!(
+        linked.summary,
+        "Placed by a {@link User}, see {@link PurchaseOrder | the order} and `Cart::total`."
+    );
+    assert_eq!(
+        linked.description.as_deref(),
+        Some("Uses items[0] and [docs](https://example.com).")
+    );
+}
+
+#[test]
+fn test_hidden_becomes_internal() {
+    let doc = DocComment {
+        hidden: true,
+        ..DocComment::summary("Bookkeeping")
+    };
+    assert_eq!(doc.to_inline_jsdoc(), "/** Bookkeeping @internal */");
+    assert!(doc.to_jsdoc().contains(" * @internal"));
+}
+
+#[test]
+fn test_deprecated_becomes_jsdoc_tag() {
+    let doc = DocComment {
+        deprecated: Some(Deprecation {
+            since: Some("1.2.0".to_string()),
+            note: Some("Use `email` instead.".to_string()),
+        }),
+        ..DocComment::summary("Login name")
+    };
+    assert_eq!(
+        doc.to_inline_jsdoc(),
+        "/** Login name @deprecated Since 1.2.0. Use `email` instead. */"
+    );
+    assert!(
+        doc.to_jsdoc()
+            .contains(" * @deprecated Since 1.2.0. Use `email` instead.")
+    );
+
+    let bare = DocComment {
+        deprecated: Some(Deprecation::default()),
+        ..DocComment::empty()
+    };
+    assert_eq!(bare.to_inline_jsdoc(), "/** @deprecated */");
+}
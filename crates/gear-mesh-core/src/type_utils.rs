@@ -47,6 +47,11 @@ pub fn is_bigint_type(type_name: &str) -> bool {
     )
 }
 
+/// Returns true for Rust integer type names.
+pub fn is_integer_type(type_name: &str) -> bool {
+    is_bigint_type(type_name) || matches!(type_name, "i8" | "i16" | "i32" | "u8" | "u16" | "u32")
+}
+
 /// Maps a primitive Rust type name to its TypeScript primitive counterpart.
 pub fn to_typescript_primitive(type_name: &str, use_bigint: bool) -> Option<&'static str> {
     match type_name {
@@ -20,6 +20,49 @@ pub struct GearMeshType {
     pub attributes: TypeAttributes,
 }
 
+impl GearMeshType {
+    /// derive時に記録したRustでの定義位置を設定
+    ///
+    /// `fields` は構造体のフィールド名と行番号の組です。
+    pub fn with_source(mut self, location: SourceLocation, fields: &[(&str, u32)]) -> Self {
+        if let TypeKind::Struct(s) = &mut self.kind {
+            for field in &mut s.fields {
+                field.attributes.line = fields
+                    .iter()
+                    .find(|(name, _)| *name == field.name)
+                    .map(|(_, line)| *line);
+            }
+        }
+        self.attributes.source = Some(location);
+        self
+    }
+}
+
+/// Rustでの定義位置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLocation {
+    /// ファイル (`file!()`)
+    pub file: String,
+    /// 行番号
+    pub line: u32,
+    /// モジュールパス (`module_path!()`)
+    pub module: String,
+    /// Rustでの型名
+    pub item: String,
+}
+
+impl SourceLocation {
+    /// `crates/api/src/user.rs:42`
+    pub fn link(&self) -> String {
+        format!("{}:{}", self.file, self.line)
+    }
+
+    /// `api::user::User`
+    pub fn path(&self) -> String {
+        format!("{}::{}", self.module, self.item)
+    }
+}
+
 /// 型の種類
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TypeKind {
@@ -143,6 +186,50 @@ pub struct FieldInfo {
     pub optional: bool,
     /// serde属性
     pub serde_attrs: SerdeFieldAttrs,
+    /// `#[gear_mesh(...)]` フィールド属性
+    #[serde(default)]
+    pub attributes: FieldAttributes,
+}
+
+/// フィールド属性
+///
+/// 導出された変換が適切でない場合に、フィールド単位で上書きします。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FieldAttributes {
+    /// 整数の表現方法 (型・生成設定より優先)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bigint: Option<BigIntPolicy>,
+    /// TypeScriptの型 (`type = "..."`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ts_type: Option<String>,
+    /// Zodスキーマの式 (`zod = "..."`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zod: Option<String>,
+    /// プロパティを省略可能にする
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    /// `null` を許容する
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub nullable: bool,
+    /// `readonly` プロパティとして生成
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub readonly: bool,
+    /// 生成対象から除外
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub skip: bool,
+    /// プロパティ名 (serdeのリネームより優先)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rename: Option<String>,
+    /// 固定値 (`literal = "v2"`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub literal: Option<crate::ConditionValue>,
+    /// テンプレートリテラル型 (`template = "usr_${string}"`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Rustでの定義の行番号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
 }
 
 /// serdeフィールド属性
@@ -207,6 +294,19 @@ pub enum EnumRepresentation {
 pub struct NewtypeType {
     /// 内部の型
     pub inner: TypeRef,
+    /// 内部のフィールドのバリデーションルール
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub validations: Vec<crate::ValidationRule>,
+    /// `PhantomData` で保持するマーカーのジェネリクス引数名（ブランドのキーになる）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub markers: Vec<String>,
+}
+
+impl NewtypeType {
+    /// マーカーを持つジェネリックなブランドか
+    pub fn is_generic_brand(&self) -> bool {
+        !self.markers.is_empty()
+    }
 }
 
 /// 型参照
@@ -244,7 +344,7 @@ pub struct GenericParam {
 }
 
 /// 型の属性
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypeAttributes {
     /// Branded Typeとして生成するか
     pub branded: bool,
@@ -252,11 +352,97 @@ pub struct TypeAttributes {
     pub validate: bool,
     /// BigInt自動変換を有効にするか
     pub bigint_auto: bool,
+    /// 整数の表現方法 (生成設定より優先)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bigint: Option<BigIntPolicy>,
     /// serde属性
     pub serde: SerdeTypeAttrs,
     /// Output path for automatic TypeScript generation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output_path: Option<String>,
+    /// TypeScriptでの型名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rename: Option<String>,
+    /// `export` するか (`export = false` ではモジュール内でのみ宣言)
+    #[serde(default = "default_export")]
+    pub export: bool,
+    /// `Option<T>` の表現方法 (生成設定より優先)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub option_style: Option<OptionStyle>,
+    /// Rustでの定義位置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourceLocation>,
+}
+
+impl Default for TypeAttributes {
+    fn default() -> Self {
+        Self {
+            branded: false,
+            validate: false,
+            bigint_auto: false,
+            bigint: None,
+            serde: SerdeTypeAttrs::default(),
+            output_path: None,
+            rename: None,
+            export: true,
+            option_style: None,
+            source: None,
+        }
+    }
+}
+
+fn default_export() -> bool {
+    true
+}
+
+impl TypeAttributes {
+    /// 型に指定された整数の表現方法 (`bigint = "auto"` は `bigint` として扱う)
+    pub fn bigint_policy(&self) -> Option<BigIntPolicy> {
+        self.bigint
+            .or(self.bigint_auto.then_some(BigIntPolicy::BigInt))
+    }
+}
+
+/// TypeScript mapping strategy for `Option<T>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum OptionStyle {
+    /// Generate `T | null`.
+    Nullable,
+    /// Generate an optional property key with type `T`, and `T | undefined` in nested contexts.
+    Optional,
+    /// Generate an optional property key with type `T | null`, and `T | null | undefined` in nested contexts.
+    Both,
+}
+
+impl Default for OptionStyle {
+    fn default() -> Self {
+        Self::Nullable
+    }
+}
+
+/// JavaScriptの `number` では精度が落ちる整数の表現方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum BigIntPolicy {
+    /// `bigint` (JSONの数値・文字列から変換)
+    BigInt,
+    /// `number` (安全な整数の範囲に制限)
+    Number,
+    /// 10進数の文字列 (`serde_with::DisplayFromStr` など)
+    String,
+}
+
+impl BigIntPolicy {
+    /// この表現方法が `type_name` の整数に適用されるか
+    ///
+    /// 文字列表現はシリアライズ形式そのものなので、すべての整数型に適用されます。
+    pub fn applies_to(self, type_name: &str) -> bool {
+        match self {
+            BigIntPolicy::String => crate::is_integer_type(type_name),
+            BigIntPolicy::BigInt | BigIntPolicy::Number => crate::is_bigint_type(type_name),
+        }
+    }
 }
 
 /// serde型属性
@@ -270,6 +456,9 @@ pub struct SerdeTypeAttrs {
     pub content: Option<String>,
     /// タグなし
     pub untagged: bool,
+    /// `#[serde(transparent)]` (中身のフィールドだけでシリアライズする)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub transparent: bool,
 }
 
 /// リネームルール
@@ -430,6 +619,7 @@ mod tests {
                 validations: vec![],
                 optional: false,
                 serde_attrs: SerdeFieldAttrs::default(),
+                attributes: Default::default(),
             }],
         };
 
@@ -445,5 +635,20 @@ mod tests {
         assert!(!attrs.branded);
         assert!(!attrs.validate);
         assert!(!attrs.bigint_auto);
+        assert!(attrs.export);
+    }
+
+    #[test]
+    fn test_attributes_deserialize_without_overrides() {
+        let attrs: TypeAttributes = serde_json::from_str(
+            r#"{"branded":false,"validate":false,"bigint_auto":false,"serde":{"rename_all":null,"tag":null,"content":null,"untagged":false}}"#,
+        )
+        .unwrap();
+        assert!(attrs.export);
+        assert!(attrs.option_style.is_none());
+
+        let field: FieldAttributes = serde_json::from_str("{}").unwrap();
+        assert!(!field.skip);
+        assert!(field.ts_type.is_none());
     }
 }
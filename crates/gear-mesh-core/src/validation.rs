@@ -4,22 +4,48 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::TypeRef;
+
 /// バリデーションルール
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ValidationRule {
     /// 範囲チェック
     Range { min: Option<f64>, max: Option<f64> },
-    /// 文字列長さチェック
+    /// 境界値を含まない範囲チェック
+    ExclusiveRange { min: Option<f64>, max: Option<f64> },
+    /// 長さチェック (文字列・配列・セット・マップ)
     Length {
         min: Option<usize>,
         max: Option<usize>,
     },
+    /// 空でないこと
+    NonEmpty,
+    /// 配列の要素が重複しないこと
+    Unique,
+    /// 要素 (マップの場合は値) に適用するルール
+    Each(Vec<ValidationRule>),
     /// 正規表現パターン
     Pattern(String),
+    /// 部分文字列 (配列の場合は要素、マップの場合はキー) を含む
+    Contains(String),
+    /// 前方一致
+    StartsWith(String),
+    /// 後方一致
+    EndsWith(String),
     /// メールアドレス形式
     Email,
     /// URL形式
     Url,
+    /// UUID形式
+    Uuid,
+    /// IPアドレス形式
+    Ip(Option<IpVersion>),
+    /// ASCII文字のみ
+    Ascii,
+    /// 英数字のみ (Unicodeの英字・数字を含む)
+    Alphanumeric,
+    /// 指定値の倍数
+    MultipleOf(f64),
     /// 必須フィールド
     Required,
     /// カスタムバリデーション
@@ -36,9 +62,195 @@ pub enum ValidationRule {
     },
     /// 条件付きバリデーション
     Conditional {
-        condition: String,
+        condition: Condition,
+        rule: Box<ValidationRule>,
+    },
+    /// エラーメッセージを指定したルール
+    WithMessage {
         rule: Box<ValidationRule>,
+        message: ValidationMessage,
+    },
+}
+
+/// ルールに指定したエラーメッセージ
+///
+/// 文言には `{min}` のようにルールの値を埋め込めます。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ValidationMessage {
+    /// 固定の文言
+    Text(String),
+    /// メッセージカタログのキー (`default` は既定ロケールの文言)
+    Key {
+        key: String,
+        default: Option<String>,
+    },
+}
+
+/// 条件付きバリデーションの条件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Condition {
+    /// 任意のJavaScript式 (Rust側では評価されません)
+    Expression(String),
+    /// フィールドが指定値と等しい
+    Equals {
+        field: String,
+        value: ConditionValue,
+    },
+    /// フィールドが指定値と等しくない
+    NotEquals {
+        field: String,
+        value: ConditionValue,
     },
+    /// フィールドに値がある (`null`・未定義・空文字列以外)
+    Present(String),
+    /// フィールドに値がない
+    Absent(String),
+}
+
+/// 条件で比較する値や `literal = ...` で固定する値
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConditionValue {
+    String(String),
+    Number(f64),
+    Bool(bool),
+}
+
+impl Condition {
+    /// 条件が参照するフィールド名
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            Condition::Expression(_) => None,
+            Condition::Equals { field, .. }
+            | Condition::NotEquals { field, .. }
+            | Condition::Present(field)
+            | Condition::Absent(field) => Some(field),
+        }
+    }
+
+    /// TypeScriptの条件式を生成
+    ///
+    /// `accessor` はフィールド名から `data.kind` のような参照式を返します。
+    pub fn to_typescript(&self, accessor: impl Fn(&str) -> String, quote: QuoteStyle) -> String {
+        let empty = js_string("", quote);
+        match self {
+            Condition::Expression(expression) => expression.clone(),
+            Condition::Equals { field, value } => {
+                format!("{} === {}", accessor(field), value.to_typescript(quote))
+            }
+            Condition::NotEquals { field, value } => {
+                format!("{} !== {}", accessor(field), value.to_typescript(quote))
+            }
+            Condition::Present(field) => {
+                let target = accessor(field);
+                format!("{target} !== undefined && {target} !== null && {target} !== {empty}")
+            }
+            Condition::Absent(field) => {
+                let target = accessor(field);
+                format!("({target} === undefined || {target} === null || {target} === {empty})")
+            }
+        }
+    }
+}
+
+impl ConditionValue {
+    /// TypeScriptのリテラル (型としても値としても使える)
+    pub fn to_typescript(&self, quote: QuoteStyle) -> String {
+        match self {
+            ConditionValue::String(value) => js_string(value, quote),
+            ConditionValue::Number(value) => value.to_string(),
+            ConditionValue::Bool(value) => value.to_string(),
+        }
+    }
+}
+
+/// IPアドレスのバージョン
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum IpVersion {
+    V4,
+    V6,
+}
+
+impl IpVersion {
+    fn as_str(self) -> &'static str {
+        match self {
+            IpVersion::V4 => "v4",
+            IpVersion::V6 => "v6",
+        }
+    }
+}
+
+/// ルールを適用する値の種類
+///
+/// 長さや空チェックは、文字列・配列とセット・マップでZodの書き方が異なります。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValueKind {
+    #[default]
+    Scalar,
+    Array,
+    Set,
+    Map,
+    /// 10進数の文字列で表した整数 (数値の範囲は `BigInt` で比較する)
+    IntegerString,
+}
+
+impl ValueKind {
+    /// 型参照から値の種類を判定 (`Option` やスマートポインタは透過)
+    pub fn of(type_ref: &TypeRef) -> Self {
+        match type_ref.name.as_str() {
+            "Vec" | "Array" | "__array__" | "__slice__" => ValueKind::Array,
+            "HashSet" | "BTreeSet" => ValueKind::Set,
+            "HashMap" | "BTreeMap" => ValueKind::Map,
+            "Option" | "Box" | "Arc" | "Rc" | "Cow" => type_ref
+                .generics
+                .last()
+                .map(ValueKind::of)
+                .unwrap_or_default(),
+            _ => ValueKind::Scalar,
+        }
+    }
+}
+
+/// 出力するZodのメジャーバージョン
+///
+/// v4では文字列フォーマットがトップレベルのスキーマ (`z.email()`) になり、
+/// エラーメッセージは `{ error: ... }` で指定します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ZodVersion {
+    #[default]
+    V3,
+    V4,
+}
+
+impl ZodVersion {
+    /// チェックのオプションでメッセージを指定するプロパティ名
+    pub fn message_option(self) -> &'static str {
+        match self {
+            ZodVersion::V3 => "message",
+            ZodVersion::V4 => "error",
+        }
+    }
+}
+
+/// Quote character for string literals in the generated code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuoteStyle {
+    /// Double quotes for literals and single quotes for import paths.
+    #[default]
+    Mixed,
+    /// Double quotes everywhere.
+    Double,
+    /// Single quotes everywhere.
+    Single,
+}
+
+impl QuoteStyle {
+    /// import文のモジュール指定子を囲む引用符
+    pub fn module_quote(self) -> char {
+        match self {
+            QuoteStyle::Double => '"',
+            QuoteStyle::Mixed | QuoteStyle::Single => '\'',
+        }
+    }
 }
 
 /// Cross-field validation modes.
@@ -50,51 +262,252 @@ pub enum CrossFieldRule {
 }
 
 impl ValidationRule {
+    /// ルールが参照する他フィールドの名前
+    pub fn referenced_fields(&self) -> Vec<&str> {
+        match self {
+            ValidationRule::CrossField { fields, path, .. } => fields
+                .iter()
+                .map(String::as_str)
+                .chain(path.as_deref())
+                .collect(),
+            ValidationRule::Conditional { condition, rule } => condition
+                .field()
+                .into_iter()
+                .chain(rule.referenced_fields())
+                .collect(),
+            ValidationRule::WithMessage { rule, .. } => rule.referenced_fields(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// メッセージ指定を取り除いたルール本体
+    pub fn without_message(&self) -> &ValidationRule {
+        match self {
+            ValidationRule::WithMessage { rule, .. } => rule.without_message(),
+            rule => rule,
+        }
+    }
+
+    /// `WithMessage` で指定されたエラーメッセージ
+    pub fn message(&self) -> Option<&ValidationMessage> {
+        match self {
+            ValidationRule::WithMessage { message, .. } => Some(message),
+            _ => None,
+        }
+    }
+
+    /// メッセージに埋め込めるルールの値 (`{min}` など)
+    pub fn message_params(&self) -> Vec<(&'static str, String)> {
+        match self {
+            ValidationRule::Range { min, max } | ValidationRule::ExclusiveRange { min, max } => {
+                [("min", *min), ("max", *max)]
+                    .into_iter()
+                    .filter_map(|(name, value)| value.map(|value| (name, value.to_string())))
+                    .collect()
+            }
+            ValidationRule::Length { min, max } => [("min", *min), ("max", *max)]
+                .into_iter()
+                .filter_map(|(name, value)| value.map(|value| (name, value.to_string())))
+                .collect(),
+            ValidationRule::Pattern(value)
+            | ValidationRule::Contains(value)
+            | ValidationRule::StartsWith(value)
+            | ValidationRule::EndsWith(value) => vec![("value", value.clone())],
+            ValidationRule::MultipleOf(step) => vec![("value", step.to_string())],
+            ValidationRule::Conditional { rule, .. } | ValidationRule::WithMessage { rule, .. } => {
+                rule.message_params()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// メッセージカタログに登録する既定の文言 (英語)
+    pub fn default_message(&self) -> String {
+        let bounds = |min: bool, max: bool, both: &str, lower: &str, upper: &str, none: &str| {
+            match (min, max) {
+                (true, true) => both,
+                (true, false) => lower,
+                (false, true) => upper,
+                (false, false) => none,
+            }
+            .to_string()
+        };
+        match self {
+            ValidationRule::Range { min, max } => bounds(
+                min.is_some(),
+                max.is_some(),
+                "Must be between {min} and {max}",
+                "Must be at least {min}",
+                "Must be at most {max}",
+                "Invalid value",
+            ),
+            ValidationRule::ExclusiveRange { min, max } => bounds(
+                min.is_some(),
+                max.is_some(),
+                "Must be greater than {min} and less than {max}",
+                "Must be greater than {min}",
+                "Must be less than {max}",
+                "Invalid value",
+            ),
+            ValidationRule::Length { min, max } if min.is_some() && min == max => {
+                "Length must be exactly {min}".to_string()
+            }
+            ValidationRule::Length { min, max } => bounds(
+                min.is_some(),
+                max.is_some(),
+                "Length must be between {min} and {max}",
+                "Length must be at least {min}",
+                "Length must be at most {max}",
+                "Invalid length",
+            ),
+            ValidationRule::NonEmpty => "Must not be empty".to_string(),
+            ValidationRule::Unique => "Items must be unique".to_string(),
+            ValidationRule::Each(_) => "Contains an invalid item".to_string(),
+            ValidationRule::Pattern(_) => "Invalid format".to_string(),
+            ValidationRule::Contains(_) => "Must contain {value}".to_string(),
+            ValidationRule::StartsWith(_) => "Must start with {value}".to_string(),
+            ValidationRule::EndsWith(_) => "Must end with {value}".to_string(),
+            ValidationRule::Email => "Invalid email address".to_string(),
+            ValidationRule::Url => "Invalid URL".to_string(),
+            ValidationRule::Uuid => "Invalid UUID".to_string(),
+            ValidationRule::Ip(_) => "Invalid IP address".to_string(),
+            ValidationRule::Ascii => "Must contain only ASCII characters".to_string(),
+            ValidationRule::Alphanumeric => "Must contain only letters and digits".to_string(),
+            ValidationRule::MultipleOf(_) => "Must be a multiple of {value}".to_string(),
+            ValidationRule::Required => "Required".to_string(),
+            ValidationRule::Custom {
+                message: Some(message),
+                ..
+            }
+            | ValidationRule::CrossField {
+                message: Some(message),
+                ..
+            } => message.clone(),
+            ValidationRule::Custom { .. } | ValidationRule::CrossField { .. } => {
+                "Invalid value".to_string()
+            }
+            ValidationRule::Conditional { rule, .. } | ValidationRule::WithMessage { rule, .. } => {
+                rule.default_message()
+            }
+        }
+    }
+
     /// TypeScriptのバリデーションコードを生成
     pub fn to_typescript_check(&self, field_name: &str) -> String {
+        self.check_expression(&format!("obj.{field_name}"))
+    }
+
+    /// `target` の値に対するTypeScriptの検査式を生成
+    pub fn check_expression(&self, target: &str) -> String {
+        self.check_expression_for(target, ValueKind::Scalar, QuoteStyle::Mixed)
+    }
+
+    /// 値の種類に応じて `target` の値に対するTypeScriptの検査式を生成
+    pub fn check_expression_for(&self, target: &str, kind: ValueKind, quote: QuoteStyle) -> String {
+        let length = match kind {
+            ValueKind::Set => format!("{target}.size"),
+            ValueKind::Map => format!("Object.keys({target}).length"),
+            ValueKind::Scalar | ValueKind::Array | ValueKind::IntegerString => {
+                format!("{target}.length")
+            }
+        };
         match self {
-            ValidationRule::Range { min, max } => {
-                let mut checks = Vec::new();
-                if let Some(min) = min {
-                    checks.push(format!("obj.{field_name} >= {min}"));
-                }
-                if let Some(max) = max {
-                    checks.push(format!("obj.{field_name} <= {max}"));
-                }
-                if checks.is_empty() {
-                    "true".to_string()
-                } else {
-                    checks.join(" && ")
-                }
+            ValidationRule::Range { .. }
+            | ValidationRule::ExclusiveRange { .. }
+            | ValidationRule::MultipleOf(_)
+                if kind == ValueKind::IntegerString =>
+            {
+                let value = format!("BigInt({target})");
+                let bound = |bound: f64| format_bound(bound, true);
+                let check = match self {
+                    ValidationRule::Range { min, max } => join_checks(vec![
+                        min.map(|min| format!("{value} >= {}", bound(min))),
+                        max.map(|max| format!("{value} <= {}", bound(max))),
+                    ]),
+                    ValidationRule::ExclusiveRange { min, max } => join_checks(vec![
+                        min.map(|min| format!("{value} > {}", bound(min))),
+                        max.map(|max| format!("{value} < {}", bound(max))),
+                    ]),
+                    ValidationRule::MultipleOf(step) => {
+                        format!("{value} % {} === 0n", bound(*step))
+                    }
+                    _ => unreachable!(),
+                };
+                // 数値でない文字列は正規表現のチェックで報告される
+                format!(
+                    "!{}.test({target}) || ({check})",
+                    js_regex_literal(INTEGER_STRING_PATTERN)
+                )
             }
-            ValidationRule::Length { min, max } => {
-                let mut checks = Vec::new();
-                if let Some(min) = min {
-                    checks.push(format!("obj.{field_name}.length >= {min}"));
-                }
-                if let Some(max) = max {
-                    checks.push(format!("obj.{field_name}.length <= {max}"));
-                }
-                if checks.is_empty() {
-                    "true".to_string()
-                } else {
-                    checks.join(" && ")
-                }
+            ValidationRule::Range { min, max } => join_checks(vec![
+                min.map(|min| format!("{target} >= {min}")),
+                max.map(|max| format!("{target} <= {max}")),
+            ]),
+            ValidationRule::ExclusiveRange { min, max } => join_checks(vec![
+                min.map(|min| format!("{target} > {min}")),
+                max.map(|max| format!("{target} < {max}")),
+            ]),
+            ValidationRule::Length { min, max } => join_checks(vec![
+                min.map(|min| format!("{length} >= {min}")),
+                max.map(|max| format!("{length} <= {max}")),
+            ]),
+            ValidationRule::NonEmpty => format!("{length} > 0"),
+            ValidationRule::Unique => match kind {
+                ValueKind::Set | ValueKind::Map => "true".to_string(),
+                _ => format!("new Set({target}).size === {target}.length"),
+            },
+            ValidationRule::Each(rules) => {
+                let checks = rules
+                    .iter()
+                    .map(|rule| rule.check_expression_for("item", ValueKind::Scalar, quote))
+                    .collect::<Vec<_>>();
+                format!(
+                    "Object.values({target}).every((item) => {})",
+                    join_checks(checks.into_iter().map(Some).collect())
+                )
             }
             ValidationRule::Pattern(pattern) => {
-                format!("/{pattern}/.test(obj.{field_name})")
+                format!("{}.test({target})", js_regex_literal(pattern))
+            }
+            ValidationRule::Contains(needle) => match kind {
+                ValueKind::Map => format!(
+                    "Object.keys({target}).includes({})",
+                    js_string(needle, quote)
+                ),
+                _ => format!("{target}.includes({})", js_string(needle, quote)),
+            },
+            ValidationRule::StartsWith(prefix) => {
+                format!("{target}.startsWith({})", js_string(prefix, quote))
+            }
+            ValidationRule::EndsWith(suffix) => {
+                format!("{target}.endsWith({})", js_string(suffix, quote))
             }
             ValidationRule::Email => {
-                format!(r#"/^[^\s@]+@[^\s@]+\.[^\s@]+$/.test(obj.{field_name})"#)
+                format!(r#"/^[^\s@]+@[^\s@]+\.[^\s@]+$/.test({target})"#)
             }
             ValidationRule::Url => {
-                format!(r#"/^https?:\/\/[^\s]+$/.test(obj.{field_name})"#)
+                format!(r#"/^https?:\/\/[^\s]+$/.test({target})"#)
+            }
+            ValidationRule::Uuid => format!("/{UUID_PATTERN}/.test({target})"),
+            // IPアドレスの厳密な判定はZodスキーマに任せる
+            ValidationRule::Ip(_) => {
+                format!("typeof {target} === {}", js_string("string", quote))
+            }
+            ValidationRule::Ascii => {
+                format!("{}.test({target})", js_regex_literal(ASCII_PATTERN))
             }
+            ValidationRule::Alphanumeric => {
+                format!("{}.test({target})", js_regex_literal(ALPHANUMERIC_PATTERN))
+            }
+            ValidationRule::MultipleOf(step) => format!("{target} % {step} === 0"),
             ValidationRule::Required => {
-                format!("obj.{field_name} !== undefined && obj.{field_name} !== null")
+                format!("{target} !== undefined && {target} !== null")
             }
             ValidationRule::Custom { name, .. } => {
-                format!("validate{name}(obj.{field_name})")
+                format!("validate{name}({target})")
+            }
+            ValidationRule::WithMessage { rule, .. } => {
+                rule.check_expression_for(target, kind, quote)
             }
             // Cross-field and conditional rules depend on whole-object state and are emitted
             // only as runtime Zod refinements, not as field-local TypeScript checks.
@@ -106,66 +519,587 @@ impl ValidationRule {
 
     /// Zodスキーマコードを生成
     pub fn to_zod_schema(&self, is_bigint: bool) -> String {
+        self.to_zod_schema_for(ValueKind::Scalar, is_bigint)
+    }
+
+    /// 値の種類に応じたZodスキーマコードを生成
+    ///
+    /// `Each` は要素のスキーマに適用する必要があるため、ここでは何も出力しません。
+    /// メッセージキーは実行時のロケールで解決するため、`gearMeshMessage()` を呼ぶ
+    /// `superRefine` として出力します。
+    pub fn to_zod_schema_for(&self, kind: ValueKind, is_bigint: bool) -> String {
+        self.to_zod_schema_with(kind, is_bigint, ZodVersion::V3, QuoteStyle::Mixed)
+    }
+
+    /// 指定したZodのバージョンの書き方でスキーマコードを生成
+    ///
+    /// v4ではメッセージキーを `{ error: () => gearMeshMessage(...) }` として渡します。
+    /// 文字列リテラルは `quote` の引用符で出力します。
+    pub fn to_zod_schema_with(
+        &self,
+        kind: ValueKind,
+        is_bigint: bool,
+        version: ZodVersion,
+        quote: QuoteStyle,
+    ) -> String {
+        match self {
+            ValidationRule::WithMessage {
+                rule,
+                message: ValidationMessage::Text(text),
+            } => rule.zod_checks(
+                kind,
+                is_bigint,
+                version,
+                quote,
+                Some(&js_string(
+                    &interpolate_message(text, &rule.message_params()),
+                    quote,
+                )),
+            ),
+            ValidationRule::WithMessage {
+                rule,
+                message: ValidationMessage::Key { key, .. },
+            } => match rule.without_message() {
+                ValidationRule::Each(_)
+                | ValidationRule::CrossField { .. }
+                | ValidationRule::Conditional { .. } => String::new(),
+                inner if version == ZodVersion::V4 => format!(
+                    ".refine((value) => {}, {{ error: () => {} }})",
+                    inner.check_expression_for("value", kind, quote),
+                    message_key_call(key, &inner.message_params(), quote)
+                ),
+                inner => format!(
+                    ".superRefine((value, ctx) => {{ if (!({})) {{ ctx.addIssue({{ code: {}, message: {} }}); }} }})",
+                    inner.check_expression_for("value", kind, quote),
+                    js_string("custom", quote),
+                    message_key_call(key, &inner.message_params(), quote)
+                ),
+            },
+            rule => rule.zod_checks(kind, is_bigint, version, quote, None),
+        }
+    }
+
+    /// `to_zod_schema_with` が `.refine`・`.superRefine` を出力するか
+    ///
+    /// Zod v3の `.refine` は `ZodEffects` を返し、`.min()` などのメソッドを持たないため、
+    /// 複数のルールを並べる場合はこれらを組み込みのチェックの後に置いてください。
+    pub fn is_zod_refinement(&self, kind: ValueKind, version: ZodVersion) -> bool {
+        match self {
+            ValidationRule::WithMessage {
+                rule,
+                message: ValidationMessage::Key { .. },
+            } => !matches!(
+                rule.without_message(),
+                ValidationRule::Each(_)
+                    | ValidationRule::CrossField { .. }
+                    | ValidationRule::Conditional { .. }
+            ),
+            ValidationRule::WithMessage { rule, .. } => rule.is_zod_refinement(kind, version),
+            ValidationRule::Range { .. }
+            | ValidationRule::ExclusiveRange { .. }
+            | ValidationRule::MultipleOf(_) => kind == ValueKind::IntegerString,
+            ValidationRule::Length { .. } | ValidationRule::NonEmpty => kind == ValueKind::Map,
+            ValidationRule::Unique => kind == ValueKind::Array,
+            ValidationRule::Contains(_) => matches!(kind, ValueKind::Array | ValueKind::Map),
+            ValidationRule::Email
+            | ValidationRule::Url
+            | ValidationRule::Uuid
+            | ValidationRule::Ip(_) => version == ZodVersion::V4,
+            ValidationRule::Custom { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Zod v4で `z.string()` の代わりに使うフォーマットのスキーマ (`z.email()` など)
+    ///
+    /// 文字列のフォーマットを表すルールでなければ `None` を返します。
+    /// このスキーマを使った場合、ルール自体のチェックは出力しないでください。
+    pub fn zod_format_schema(&self, quote: QuoteStyle) -> Option<String> {
+        let (rule, message) = match self {
+            ValidationRule::WithMessage { rule, message } => {
+                let message = match message {
+                    ValidationMessage::Text(text) => {
+                        js_string(&interpolate_message(text, &rule.message_params()), quote)
+                    }
+                    ValidationMessage::Key { key, .. } => format!(
+                        "() => {}",
+                        message_key_call(key, &rule.message_params(), quote)
+                    ),
+                };
+                (rule.as_ref(), Some(message))
+            }
+            rule => (rule, None),
+        };
+        let constructor = match rule {
+            ValidationRule::Email => "email",
+            ValidationRule::Url => "url",
+            ValidationRule::Uuid => "uuid",
+            ValidationRule::Ip(Some(IpVersion::V4)) => "ipv4",
+            ValidationRule::Ip(Some(IpVersion::V6)) => "ipv6",
+            _ => return None,
+        };
+        Some(match message {
+            Some(message) => format!("z.{constructor}({{ error: {message} }})"),
+            None => format!("z.{constructor}()"),
+        })
+    }
+
+    /// Zodのチェックを生成 (`message` はJavaScriptの式)
+    fn zod_checks(
+        &self,
+        kind: ValueKind,
+        is_bigint: bool,
+        version: ZodVersion,
+        quote: QuoteStyle,
+        message: Option<&str>,
+    ) -> String {
+        let option = version.message_option();
+        // `.min(1, { message: "..." })` のようにチェックの引数へ追加するオプション
+        let arg = message
+            .map(|message| format!(", {{ {option}: {message} }}"))
+            .unwrap_or_default();
+        let only_arg = message
+            .map(|message| format!("{{ {option}: {message} }}"))
+            .unwrap_or_default();
+        let refine_message = |default: String| {
+            message
+                .map(ToString::to_string)
+                .unwrap_or(js_string(&default, quote))
+        };
+        let default_message =
+            || interpolate_message(&self.default_message(), &self.message_params());
         match self {
+            ValidationRule::Range { .. }
+            | ValidationRule::ExclusiveRange { .. }
+            | ValidationRule::MultipleOf(_)
+                if kind == ValueKind::IntegerString =>
+            {
+                format!(
+                    ".refine((value) => {}, {{ {option}: {} }})",
+                    self.check_expression_for("value", kind, quote),
+                    refine_message(default_message())
+                )
+            }
             ValidationRule::Range { min, max } => {
                 let mut schema = String::new();
-                let suffix = if is_bigint { "n" } else { "" };
                 if let Some(min) = min {
-                    if is_bigint {
-                        debug_assert!(
-                            min.fract() == 0.0,
-                            "Fractional value ({}) provided for integer range validation, this will be truncated to {}",
-                            min,
-                            *min as i128
-                        );
-                        schema.push_str(&format!(".min({}{suffix})", *min as i128));
-                    } else {
-                        schema.push_str(&format!(".min({min})"));
-                    }
+                    schema.push_str(&format!(".min({}{arg})", format_bound(*min, is_bigint)));
                 }
                 if let Some(max) = max {
-                    if is_bigint {
-                        debug_assert!(
-                            max.fract() == 0.0,
-                            "Fractional value ({}) provided for integer range validation, this will be truncated to {}",
-                            max,
-                            *max as i128
-                        );
-                        schema.push_str(&format!(".max({}{suffix})", *max as i128));
-                    } else {
-                        schema.push_str(&format!(".max({max})"));
-                    }
+                    schema.push_str(&format!(".max({}{arg})", format_bound(*max, is_bigint)));
                 }
                 schema
             }
-            ValidationRule::Length { min, max } => {
+            ValidationRule::ExclusiveRange { min, max } => {
                 let mut schema = String::new();
                 if let Some(min) = min {
-                    schema.push_str(&format!(".min({min})"));
+                    schema.push_str(&format!(".gt({}{arg})", format_bound(*min, is_bigint)));
                 }
                 if let Some(max) = max {
-                    schema.push_str(&format!(".max({max})"));
+                    schema.push_str(&format!(".lt({}{arg})", format_bound(*max, is_bigint)));
                 }
                 schema
             }
+            ValidationRule::Length { min, max } => match kind {
+                ValueKind::Map => {
+                    let mut schema = String::new();
+                    if let Some(min) = min {
+                        schema.push_str(&format!(
+                            ".refine((value) => Object.keys(value).length >= {min}, {{ {option}: {} }})",
+                            refine_message(format!("Must contain at least {min} entries"))
+                        ));
+                    }
+                    if let Some(max) = max {
+                        schema.push_str(&format!(
+                            ".refine((value) => Object.keys(value).length <= {max}, {{ {option}: {} }})",
+                            refine_message(format!("Must contain at most {max} entries"))
+                        ));
+                    }
+                    schema
+                }
+                _ if min.is_some() && min == max => {
+                    let method = if kind == ValueKind::Set {
+                        "size"
+                    } else {
+                        "length"
+                    };
+                    format!(".{method}({}{arg})", min.unwrap_or_default())
+                }
+                _ => {
+                    let mut schema = String::new();
+                    if let Some(min) = min {
+                        schema.push_str(&format!(".min({min}{arg})"));
+                    }
+                    if let Some(max) = max {
+                        schema.push_str(&format!(".max({max}{arg})"));
+                    }
+                    schema
+                }
+            },
+            ValidationRule::NonEmpty => match kind {
+                ValueKind::Map => format!(
+                    ".refine((value) => Object.keys(value).length > 0, {{ {option}: {} }})",
+                    refine_message("Must not be empty".to_string())
+                ),
+                _ => format!(".min(1{arg})"),
+            },
+            ValidationRule::Unique => match kind {
+                ValueKind::Array => format!(
+                    ".refine((items) => new Set(items).size === items.length, {{ {option}: {} }})",
+                    refine_message("Items must be unique".to_string())
+                ),
+                // セットとマップのキーは常に一意
+                _ => String::new(),
+            },
+            ValidationRule::Each(_) => String::new(),
             ValidationRule::Pattern(pattern) => {
-                format!(".regex(/{pattern}/)")
+                format!(".regex({}{arg})", js_regex_literal(pattern))
+            }
+            ValidationRule::Contains(needle) => match kind {
+                ValueKind::Array => format!(
+                    ".refine((items) => items.includes({0}), {{ {option}: {1} }})",
+                    js_string(needle, quote),
+                    refine_message(format!("Must contain {needle}"))
+                ),
+                ValueKind::Map => format!(
+                    ".refine((value) => Object.keys(value).includes({0}), {{ {option}: {1} }})",
+                    js_string(needle, quote),
+                    refine_message(format!("Must contain key {needle}"))
+                ),
+                _ => format!(".includes({}{arg})", js_string(needle, quote)),
+            },
+            ValidationRule::StartsWith(prefix) => {
+                format!(".startsWith({}{arg})", js_string(prefix, quote))
+            }
+            ValidationRule::EndsWith(suffix) => {
+                format!(".endsWith({}{arg})", js_string(suffix, quote))
+            }
+            // v4ではフォーマットをスキーマで表すため、ベースに使えなかったものは検証だけ行う
+            ValidationRule::Email | ValidationRule::Url | ValidationRule::Uuid
+                if version == ZodVersion::V4 =>
+            {
+                format!(
+                    ".refine((value) => {}.safeParse(value).success, {{ error: {} }})",
+                    self.zod_format_schema(quote).unwrap_or_default(),
+                    refine_message(default_message())
+                )
+            }
+            ValidationRule::Ip(None) if version == ZodVersion::V4 => format!(
+                ".refine((value) => z.ipv4().safeParse(value).success || z.ipv6().safeParse(value).success, {{ error: {} }})",
+                refine_message(default_message())
+            ),
+            ValidationRule::Ip(Some(_)) if version == ZodVersion::V4 => format!(
+                ".refine((value) => {}.safeParse(value).success, {{ error: {} }})",
+                self.zod_format_schema(quote).unwrap_or_default(),
+                refine_message(default_message())
+            ),
+            ValidationRule::Email => format!(".email({only_arg})"),
+            ValidationRule::Url => format!(".url({only_arg})"),
+            ValidationRule::Uuid => format!(".uuid({only_arg})"),
+            ValidationRule::Ip(None) => format!(".ip({only_arg})"),
+            ValidationRule::Ip(Some(version)) => {
+                let version = js_string(version.as_str(), quote);
+                match message {
+                    Some(message) => format!(".ip({{ version: {version}, message: {message} }})"),
+                    None => format!(".ip({{ version: {version} }})"),
+                }
+            }
+            ValidationRule::Ascii => {
+                format!(".regex({}{arg})", js_regex_literal(ASCII_PATTERN))
+            }
+            ValidationRule::Alphanumeric => {
+                format!(".regex({}{arg})", js_regex_literal(ALPHANUMERIC_PATTERN))
+            }
+            ValidationRule::MultipleOf(step) => {
+                format!(".multipleOf({}{arg})", format_bound(*step, is_bigint))
             }
-            ValidationRule::Email => ".email()".to_string(),
-            ValidationRule::Url => ".url()".to_string(),
             ValidationRule::Required => String::new(), // Zodではデフォルトで必須
-            ValidationRule::Custom { name, message } => {
-                if let Some(msg) = message {
-                    format!(".refine(validate{name}, {{ message: \"{msg}\" }})")
-                } else {
-                    format!(".refine(validate{name})")
+            ValidationRule::Custom { name, message: own } => {
+                match message
+                    .map(ToString::to_string)
+                    .or_else(|| own.as_deref().map(|own| js_string(own, quote)))
+                {
+                    Some(message) => format!(".refine(validate{name}, {{ {option}: {message} }})"),
+                    None => format!(".refine(validate{name})"),
                 }
             }
+            ValidationRule::WithMessage { .. } => {
+                self.to_zod_schema_with(kind, is_bigint, version, quote)
+            }
             ValidationRule::CrossField { .. } | ValidationRule::Conditional { .. } => String::new(),
         }
     }
 }
 
+/// バリデータ関数名や `custom = "..."` の名前をカスタムルール名に正規化
+///
+/// `crate::validators::validate_username`・`validateUsername`・`Username` はいずれも `Username`
+/// になります (TypeScriptの関数は `validateUsername`)。
+pub fn custom_validator_name(function: &str) -> String {
+    let name = function.rsplit("::").next().unwrap_or(function).trim();
+    let name = name
+        .strip_prefix("validate_")
+        .or_else(|| {
+            name.strip_prefix("validate")
+                .filter(|rest| rest.starts_with(|ch: char| ch.is_ascii_uppercase()))
+        })
+        .filter(|rest| !rest.is_empty())
+        .unwrap_or(name);
+    crate::RenameRule::PascalCase.apply(name)
+}
+
+/// メッセージ中の `{name}` をルールの値で置き換える
+pub fn interpolate_message(template: &str, params: &[(&str, String)]) -> String {
+    params
+        .iter()
+        .fold(template.to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+/// 生成されたメッセージカタログからキーを解決する呼び出し式
+pub fn message_key_call(key: &str, params: &[(&str, String)], quote: QuoteStyle) -> String {
+    if params.is_empty() {
+        format!("gearMeshMessage({})", js_string(key, quote))
+    } else {
+        format!(
+            "gearMeshMessage({}, {{ {} }})",
+            js_string(key, quote),
+            params
+                .iter()
+                .map(|(name, value)| format!("{name}: {}", js_string(value, quote)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+/// UUID形式の正規表現 (大文字小文字を区別しない)
+pub const UUID_PATTERN: &str =
+    "^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$";
+/// 10進数の文字列で表した整数
+pub const INTEGER_STRING_PATTERN: &str = r"^-?\d+$";
+const ASCII_PATTERN: &str = r"^[\x00-\x7F]*$";
+const ALPHANUMERIC_PATTERN: &str = r"^[\p{Alphabetic}\p{N}]*$";
+
+/// Rustの正規表現パターンをJavaScriptの正規表現リテラルに変換
+///
+/// パターンは derive 時にJavaScriptと互換な構文か検査済みである前提です。
+/// 先頭の `(?i)` などはフラグに移し、Unicodeプロパティを使う場合は `u` フラグを付け、
+/// 区切り文字の `/` をエスケープします。
+pub fn js_regex_literal(pattern: &str) -> String {
+    let mut flags = String::new();
+    let mut source = pattern;
+    if let Some(rest) = pattern.strip_prefix("(?")
+        && let Some(end) = rest.find(')')
+        && end > 0
+        && rest[..end]
+            .chars()
+            .all(|flag| matches!(flag, 'i' | 'm' | 's'))
+    {
+        flags.push_str(&rest[..end]);
+        source = &rest[end + 1..];
+    }
+
+    let mut escaped = String::with_capacity(source.len());
+    let mut unicode = false;
+    let mut chars = source.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => {
+                escaped.push(ch);
+                if let Some(next) = chars.next() {
+                    unicode |= matches!(next, 'p' | 'P');
+                    escaped.push(next);
+                }
+            }
+            '/' => escaped.push_str("\\/"),
+            ch => escaped.push(ch),
+        }
+    }
+    if unicode {
+        flags.push('u');
+    }
+    if escaped.is_empty() {
+        escaped.push_str("(?:)");
+    }
+    format!("/{escaped}/{flags}")
+}
+
+/// JavaScriptの `\s` に一致する文字 (文字クラスの中身)
+const JS_WHITESPACE: &str =
+    r"\t\n\x0B\x0C\r\x20\xA0\u1680\u2000-\u200A\u2028\u2029\u202F\u205F\u3000\uFEFF";
+
+/// Rustの`regex`クレートでJavaScriptと同じ意味になるようにパターンを書き換え
+///
+/// Rustの `\d`・`\w`・`\s`・`\b` はUnicode対応ですが、JavaScriptではASCII
+/// (`\s` はJavaScript独自の空白文字) のみなので、同じ文字集合に展開します。
+/// 文字クラス内の `\D` などの否定は derive 時に拒否済みである前提です。
+pub fn rust_regex_pattern(pattern: &str) -> String {
+    let mut rewritten = String::with_capacity(pattern.len());
+    let mut in_class = false;
+    let mut chars = pattern.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => {
+                let Some(next) = chars.next() else {
+                    rewritten.push(ch);
+                    break;
+                };
+                let class = match next.to_ascii_lowercase() {
+                    'd' => "0-9",
+                    'w' => "0-9A-Za-z_",
+                    's' => JS_WHITESPACE,
+                    _ => "",
+                };
+                match next {
+                    'd' | 'w' | 's' if in_class => rewritten.push_str(class),
+                    'd' | 'w' | 's' => {
+                        rewritten.push('[');
+                        rewritten.push_str(class);
+                        rewritten.push(']');
+                    }
+                    'D' | 'W' | 'S' if !in_class => {
+                        rewritten.push_str("[^");
+                        rewritten.push_str(class);
+                        rewritten.push(']');
+                    }
+                    'b' | 'B' if !in_class => {
+                        rewritten.push_str("(?-u:\\");
+                        rewritten.push(next);
+                        rewritten.push(')');
+                    }
+                    _ => {
+                        rewritten.push(ch);
+                        rewritten.push(next);
+                    }
+                }
+            }
+            '[' if !in_class => {
+                in_class = true;
+                rewritten.push(ch);
+                // 先頭の `^` と `]` は文字クラスの終わりではない
+                for _ in 0..2 {
+                    let mut rest = chars.clone();
+                    match rest.next() {
+                        Some(first @ ('^' | ']')) => {
+                            rewritten.push(first);
+                            chars = rest;
+                            if first == ']' {
+                                break;
+                            }
+                        }
+                        _ => break,
+                    }
+                }
+            }
+            ']' if in_class => {
+                in_class = false;
+                rewritten.push(ch);
+            }
+            ch => rewritten.push(ch),
+        }
+    }
+    rewritten
+}
+
+/// `usr_${string}` のようなテンプレートリテラル型に一致する正規表現 (`^...$`)
+///
+/// 使えるプレースホルダーは `${string}`・`${number}`・`${bigint}`・`${boolean}` です。
+/// それ以外はプレースホルダー名を `Err` で返します。
+pub fn template_literal_pattern(template: &str) -> Result<String, String> {
+    let mut pattern = String::from("^");
+    let mut rest = template;
+    while let Some(start) = rest.find("${") {
+        push_escaped(&mut pattern, &rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            return Err(after.to_string());
+        };
+        pattern.push_str(match &after[..end] {
+            "string" => "[\\s\\S]*",
+            "number" => "-?(?:\\d+\\.?\\d*|\\.\\d+)(?:[eE][+-]?\\d+)?",
+            "bigint" => "-?\\d+",
+            "boolean" => "(?:true|false)",
+            other => return Err(other.to_string()),
+        });
+        rest = &after[end + 1..];
+    }
+    push_escaped(&mut pattern, rest);
+    pattern.push('$');
+    Ok(pattern)
+}
+
+/// 正規表現の特殊文字をエスケープして追加 (RustとJavaScriptで共通の書き方)
+fn push_escaped(pattern: &mut String, text: &str) {
+    for ch in text.chars() {
+        if "\\.^$|?*+()[]{}".contains(ch) {
+            pattern.push('\\');
+        }
+        pattern.push(ch);
+    }
+}
+
+fn join_checks(checks: Vec<Option<String>>) -> String {
+    let checks = checks.into_iter().flatten().collect::<Vec<_>>();
+    if checks.is_empty() {
+        "true".to_string()
+    } else {
+        checks.join(" && ")
+    }
+}
+
+/// JavaScriptの文字列リテラルに変換
+///
+/// `Double`・`Single` では、Prettierと同様にエスケープが少なくなる方の引用符を使います。
+/// 制御文字と行区切り文字 (U+2028, U+2029) は `\uXXXX` にするので、`Mixed` と `Double` の結果は
+/// JSONの文字列としても有効です。
+pub fn js_string(value: &str, quote: QuoteStyle) -> String {
+    let count = |target: char| value.chars().filter(|&ch| ch == target).count();
+    let quote = match quote {
+        QuoteStyle::Mixed => '"',
+        QuoteStyle::Double if count('"') > count('\'') => '\'',
+        QuoteStyle::Double => '"',
+        QuoteStyle::Single if count('\'') > count('"') => '"',
+        QuoteStyle::Single => '\'',
+    };
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push(quote);
+    for ch in value.chars() {
+        match ch {
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            ch if ch.is_control() || matches!(ch, '\u{2028}' | '\u{2029}') => {
+                literal.push_str(&format!("\\u{:04x}", ch as u32));
+            }
+            ch if ch == quote => {
+                literal.push('\\');
+                literal.push(ch);
+            }
+            ch => literal.push(ch),
+        }
+    }
+    literal.push(quote);
+    literal
+}
+
+/// 範囲の境界値をZodのリテラルとして整形する
+fn format_bound(value: f64, is_bigint: bool) -> String {
+    if is_bigint {
+        debug_assert!(
+            value.fract() == 0.0,
+            "Fractional value ({}) provided for integer range validation, this will be truncated to {}",
+            value,
+            value as i128
+        );
+        format!("{}n", value as i128)
+    } else {
+        value.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,9 +1116,329 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_exclusive_range_validation() {
+        let rule = ValidationRule::ExclusiveRange {
+            min: Some(0.0),
+            max: Some(1.0),
+        };
+        assert_eq!(
+            rule.to_typescript_check("ratio"),
+            "obj.ratio > 0 && obj.ratio < 1"
+        );
+        assert_eq!(rule.to_zod_schema(false), ".gt(0).lt(1)");
+        assert_eq!(rule.to_zod_schema(true), ".gt(0n).lt(1n)");
+    }
+
     #[test]
     fn test_email_validation() {
         let rule = ValidationRule::Email;
         assert!(rule.to_typescript_check("email").contains("@"));
     }
+
+    #[test]
+    fn test_collection_rules_depend_on_value_kind() {
+        let length = ValidationRule::Length {
+            min: Some(1),
+            max: Some(3),
+        };
+        assert_eq!(
+            length.to_zod_schema_for(ValueKind::Array, false),
+            ".min(1).max(3)"
+        );
+        assert_eq!(
+            length.to_zod_schema_for(ValueKind::Map, false),
+            ".refine((value) => Object.keys(value).length >= 1, { message: \"Must contain at least 1 entries\" })\
+             .refine((value) => Object.keys(value).length <= 3, { message: \"Must contain at most 3 entries\" })"
+        );
+
+        let exact = ValidationRule::Length {
+            min: Some(2),
+            max: Some(2),
+        };
+        assert_eq!(exact.to_zod_schema(false), ".length(2)");
+        assert_eq!(exact.to_zod_schema_for(ValueKind::Set, false), ".size(2)");
+
+        assert_eq!(
+            ValidationRule::Unique.to_zod_schema_for(ValueKind::Array, false),
+            ".refine((items) => new Set(items).size === items.length, { message: \"Items must be unique\" })"
+        );
+        assert_eq!(
+            ValidationRule::Unique.to_zod_schema_for(ValueKind::Set, false),
+            ""
+        );
+        assert_eq!(
+            ValueKind::of(&TypeRef::with_generics(
+                "Option",
+                vec![TypeRef::with_generics("HashMap", vec![])]
+            )),
+            ValueKind::Map
+        );
+    }
+
+    #[test]
+    fn test_string_format_rules() {
+        assert_eq!(
+            ValidationRule::Contains("\"@".to_string()).to_zod_schema(false),
+            ".includes(\"\\\"@\")"
+        );
+        assert_eq!(
+            ValidationRule::StartsWith("usr_".to_string()).to_zod_schema(false),
+            ".startsWith(\"usr_\")"
+        );
+        assert_eq!(ValidationRule::Uuid.to_zod_schema(false), ".uuid()");
+        assert_eq!(
+            ValidationRule::Ip(Some(IpVersion::V4)).to_zod_schema(false),
+            ".ip({ version: \"v4\" })"
+        );
+        assert_eq!(
+            ValidationRule::MultipleOf(5.0).to_zod_schema(true),
+            ".multipleOf(5n)"
+        );
+        assert_eq!(
+            ValidationRule::Each(vec![ValidationRule::Email]).to_typescript_check("emails"),
+            "Object.values(obj.emails).every((item) => /^[^\\s@]+@[^\\s@]+\\.[^\\s@]+$/.test(item))"
+        );
+    }
+
+    #[test]
+    fn test_js_regex_literal() {
+        assert_eq!(js_regex_literal("^a/b$"), r"/^a\/b$/");
+        assert_eq!(js_regex_literal(r"^a\/b$"), r"/^a\/b$/");
+        assert_eq!(js_regex_literal("(?i)^abc$"), "/^abc$/i");
+        assert_eq!(js_regex_literal(r"(?is)^\p{L}+$"), r"/^\p{L}+$/isu");
+        assert_eq!(js_regex_literal(r"^[\\p]$"), r"/^[\\p]$/");
+        assert_eq!(js_regex_literal(""), "/(?:)/");
+        assert_eq!(
+            ValidationRule::Pattern("^https://".to_string()).to_zod_schema(false),
+            r".regex(/^https:\/\//)"
+        );
+    }
+
+    #[test]
+    fn test_rust_regex_pattern() {
+        assert_eq!(rust_regex_pattern(r"^\d+$"), "^[0-9]+$");
+        assert_eq!(
+            rust_regex_pattern(r"^[\w-]\W$"),
+            "^[0-9A-Za-z_-][^0-9A-Za-z_]$"
+        );
+        assert_eq!(rust_regex_pattern(r"\bab\B"), r"(?-u:\b)ab(?-u:\B)");
+        assert_eq!(
+            rust_regex_pattern(r"[^\s@]"),
+            format!("[^{JS_WHITESPACE}@]")
+        );
+        assert_eq!(rust_regex_pattern(r"[\]\d]\\d\p{L}"), r"[\]0-9]\\d\p{L}");
+    }
+
+    #[test]
+    fn test_template_literal_pattern() {
+        assert_eq!(
+            template_literal_pattern("usr_${string}").unwrap(),
+            r"^usr_[\s\S]*$"
+        );
+        assert_eq!(
+            template_literal_pattern("v${bigint}.json").unwrap(),
+            r"^v-?\d+\.json$"
+        );
+        assert_eq!(template_literal_pattern("${uuid}").unwrap_err(), "uuid");
+    }
+
+    #[test]
+    fn test_zod_v4_syntax() {
+        let with_text = ValidationRule::WithMessage {
+            rule: Box::new(ValidationRule::Length {
+                min: Some(3),
+                max: None,
+            }),
+            message: ValidationMessage::Text("At least {min} characters".to_string()),
+        };
+        assert_eq!(
+            with_text.to_zod_schema_with(
+                ValueKind::Scalar,
+                false,
+                ZodVersion::V4,
+                QuoteStyle::Mixed
+            ),
+            r#".min(3, { error: "At least 3 characters" })"#
+        );
+
+        let email = ValidationRule::WithMessage {
+            rule: Box::new(ValidationRule::Email),
+            message: ValidationMessage::Key {
+                key: "user.email".to_string(),
+                default: None,
+            },
+        };
+        assert_eq!(
+            email.zod_format_schema(QuoteStyle::Mixed).unwrap(),
+            r#"z.email({ error: () => gearMeshMessage("user.email") })"#
+        );
+        assert_eq!(
+            ValidationRule::Ip(Some(IpVersion::V6))
+                .zod_format_schema(QuoteStyle::Mixed)
+                .unwrap(),
+            "z.ipv6()"
+        );
+        assert_eq!(
+            ValidationRule::Ip(None).zod_format_schema(QuoteStyle::Mixed),
+            None
+        );
+        assert_eq!(
+            ValidationRule::Url.to_zod_schema_with(
+                ValueKind::Scalar,
+                false,
+                ZodVersion::V4,
+                QuoteStyle::Mixed
+            ),
+            r#".refine((value) => z.url().safeParse(value).success, { error: "Invalid URL" })"#
+        );
+    }
+
+    #[test]
+    fn refinements_are_classified_like_their_zod_output() {
+        let key = |rule: ValidationRule| ValidationRule::WithMessage {
+            rule: Box::new(rule),
+            message: ValidationMessage::Key {
+                key: "key".to_string(),
+                default: None,
+            },
+        };
+        let rules = [
+            ValidationRule::Range {
+                min: Some(1.0),
+                max: None,
+            },
+            ValidationRule::MultipleOf(2.0),
+            ValidationRule::Length {
+                min: Some(1),
+                max: Some(3),
+            },
+            ValidationRule::NonEmpty,
+            ValidationRule::Unique,
+            ValidationRule::Contains("a".to_string()),
+            ValidationRule::Pattern("^a$".to_string()),
+            ValidationRule::Email,
+            ValidationRule::Ip(None),
+            ValidationRule::Ip(Some(IpVersion::V4)),
+            ValidationRule::Custom {
+                name: "Slug".to_string(),
+                message: None,
+            },
+            key(ValidationRule::Pattern("^a$".to_string())),
+            key(ValidationRule::Each(vec![ValidationRule::NonEmpty])),
+        ];
+        let kinds = [
+            ValueKind::Scalar,
+            ValueKind::Array,
+            ValueKind::Set,
+            ValueKind::Map,
+            ValueKind::IntegerString,
+        ];
+        for rule in &rules {
+            for kind in kinds {
+                for version in [ZodVersion::V3, ZodVersion::V4] {
+                    let schema = rule.to_zod_schema_with(kind, false, version, QuoteStyle::Mixed);
+                    assert_eq!(
+                        rule.is_zod_refinement(kind, version),
+                        schema.starts_with(".refine(") || schema.starts_with(".superRefine("),
+                        "{rule:?} ({kind:?}, {version:?}): {schema}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rule_messages() {
+        let with_text = ValidationRule::WithMessage {
+            rule: Box::new(ValidationRule::Length {
+                min: Some(3),
+                max: None,
+            }),
+            message: ValidationMessage::Text("At least {min} characters".to_string()),
+        };
+        assert_eq!(
+            with_text.to_zod_schema(false),
+            r#".min(3, { message: "At least 3 characters" })"#
+        );
+
+        let with_key = ValidationRule::WithMessage {
+            rule: Box::new(ValidationRule::Email),
+            message: ValidationMessage::Key {
+                key: "user.email.invalid".to_string(),
+                default: None,
+            },
+        };
+        assert_eq!(
+            with_key.to_zod_schema(false),
+            r#".superRefine((value, ctx) => { if (!(/^[^\s@]+@[^\s@]+\.[^\s@]+$/.test(value))) { ctx.addIssue({ code: "custom", message: gearMeshMessage("user.email.invalid") }); } })"#
+        );
+
+        let map_key = ValidationRule::WithMessage {
+            rule: Box::new(ValidationRule::Length {
+                min: Some(1),
+                max: Some(5),
+            }),
+            message: ValidationMessage::Key {
+                key: "tags.size".to_string(),
+                default: None,
+            },
+        };
+        assert!(
+            map_key
+                .to_zod_schema_for(ValueKind::Map, false)
+                .contains(r#"Object.keys(value).length >= 1 && Object.keys(value).length <= 5"#)
+        );
+        assert!(
+            map_key
+                .to_zod_schema_for(ValueKind::Map, false)
+                .contains(r#"gearMeshMessage("tags.size", { min: "1", max: "5" })"#)
+        );
+        assert_eq!(
+            map_key.default_message(),
+            "Length must be between {min} and {max}"
+        );
+    }
+
+    #[test]
+    fn js_string_escapes_quotes_and_control_characters() {
+        let value = "it's \"bad\"\\ \u{0}\u{1b}\u{7f}\u{2028}\n";
+        let literal = js_string(value, QuoteStyle::Mixed);
+        assert_eq!(literal, r#""it's \"bad\"\\ \u0000\u001b\u007f\u2028\n""#);
+        assert_eq!(serde_json::from_str::<String>(&literal).unwrap(), value);
+    }
+
+    #[test]
+    fn js_string_prefers_the_quote_that_needs_fewer_escapes() {
+        assert_eq!(js_string("it's", QuoteStyle::Single), r#""it's""#);
+        assert_eq!(js_string("a\"b", QuoteStyle::Single), r#"'a"b'"#);
+        assert_eq!(js_string("it's", QuoteStyle::Double), r#""it's""#);
+        assert_eq!(
+            js_string(r#"say "it's""#, QuoteStyle::Double),
+            r#"'say "it\'s"'"#
+        );
+        // `Mixed` は常に二重引用符 (JSONとしても読める)
+        assert_eq!(js_string("a\"b", QuoteStyle::Mixed), r#""a\"b""#);
+        assert_eq!(
+            ValidationRule::Ip(Some(IpVersion::V4)).to_zod_schema_with(
+                ValueKind::Scalar,
+                false,
+                ZodVersion::V3,
+                QuoteStyle::Single
+            ),
+            ".ip({ version: 'v4' })"
+        );
+    }
+
+    #[test]
+    fn custom_validator_names_are_normalized() {
+        assert_eq!(
+            custom_validator_name("crate::validators::validate_username"),
+            "Username"
+        );
+        assert_eq!(custom_validator_name("validateUsername"), "Username");
+        assert_eq!(custom_validator_name("Username"), "Username");
+        assert_eq!(custom_validator_name("validated_email"), "ValidatedEmail");
+        assert_eq!(custom_validator_name("validate"), "Validate");
+    }
 }
@@ -2,9 +2,16 @@
 //!
 //! `#[gear_mesh(...)]` と `#[validate(...)]` 属性を解析します。
 
+use syn::meta::ParseNestedMeta;
 use syn::{Attribute, Expr, Lit, Meta, Result};
 
-use gear_mesh_core::{CrossFieldRule, RenameRule, SerdeTypeAttrs, TypeAttributes, ValidationRule};
+use gear_mesh_core::{
+    BigIntPolicy, Condition, ConditionValue, CrossFieldRule, Deprecation, FieldAttributes,
+    IpVersion, RenameRule, SerdeTypeAttrs, TypeAttributes, ValidationMessage, ValidationRule,
+    custom_validator_name, template_literal_pattern,
+};
+
+use crate::pattern::check_pattern;
 
 /// gear_mesh属性を解析
 pub fn parse_gear_mesh_attrs(attrs: &[Attribute]) -> Result<TypeAttributes> {
@@ -18,22 +25,30 @@ pub fn parse_gear_mesh_attrs(attrs: &[Attribute]) -> Result<TypeAttributes> {
                 } else if meta.path.is_ident("validate") {
                     result.validate = true;
                 } else if meta.path.is_ident("bigint") && meta.input.peek(syn::Token![=]) {
-                    let _ = meta.input.parse::<syn::Token![=]>()?;
-                    let value: syn::LitStr = meta.input.parse()?;
-                    if value.value() == "auto" {
+                    let value = parse_string_value(&meta)?;
+                    if value == "auto" {
                         result.bigint_auto = true;
                     } else {
-                        return Err(meta.error(
-                            "invalid value for `bigint`\nhelp: use `#[gear_mesh(bigint = \"auto\")]`",
-                        ));
+                        result.bigint = Some(bigint_policy(&meta, &value)?);
                     }
                 } else if meta.path.is_ident("output") && meta.input.peek(syn::Token![=]) {
                     let _ = meta.input.parse::<syn::Token![=]>()?;
                     let value: syn::LitStr = meta.input.parse()?;
                     result.output_path = Some(value.value());
+                } else if meta.path.is_ident("rename") {
+                    result.rename = Some(parse_identifier_value(&meta, "rename")?);
+                } else if meta.path.is_ident("export") {
+                    result.export = parse_bool_value(&meta, "export")?;
+                } else if meta.path.is_ident("option_style") {
+                    let value = parse_string_value(&meta)?;
+                    result.option_style = Some(value.parse().map_err(|_| {
+                        meta.error(
+                            "invalid value for `option_style`\nhelp: use `\"nullable\"`, `\"optional\"` or `\"both\"`",
+                        )
+                    })?);
                 } else {
                     return Err(meta.error(
-                        "unsupported #[gear_mesh(...)] option\nhelp: supported options are `branded`, `validate`, `bigint = \"auto\"`, and `output = \"path\"`",
+                        "unsupported #[gear_mesh(...)] option\nhelp: supported options are `branded`, `validate`, `bigint = \"...\"`, `output = \"path\"`, `rename = \"...\"`, `export = false`, and `option_style = \"...\"`",
                     ));
                 }
                 Ok(())
@@ -46,7 +61,172 @@ pub fn parse_gear_mesh_attrs(attrs: &[Attribute]) -> Result<TypeAttributes> {
     Ok(result)
 }
 
+/// フィールドの `#[gear_mesh(...)]` 属性と、整数を文字列で送るserde属性を解析
+pub fn parse_field_attrs(attrs: &[Attribute]) -> Result<FieldAttributes> {
+    let mut result = FieldAttributes::default();
+    if is_string_encoded(attrs) {
+        result.bigint = Some(BigIntPolicy::String);
+    }
+
+    for attr in attrs {
+        if attr.path().is_ident("gear_mesh") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("bigint") {
+                    let value = parse_string_value(&meta)?;
+                    result.bigint = Some(bigint_policy(&meta, &value)?);
+                } else if meta.path.is_ident("type") {
+                    result.ts_type = Some(parse_expression_value(&meta, "type")?);
+                } else if meta.path.is_ident("zod") {
+                    result.zod = Some(parse_expression_value(&meta, "zod")?);
+                } else if meta.path.is_ident("optional") {
+                    result.optional = true;
+                } else if meta.path.is_ident("nullable") {
+                    result.nullable = true;
+                } else if meta.path.is_ident("readonly") {
+                    result.readonly = true;
+                } else if meta.path.is_ident("skip") {
+                    result.skip = true;
+                } else if meta.path.is_ident("rename") {
+                    let value = parse_string_value(&meta)?;
+                    if value.is_empty() {
+                        return Err(meta.error("`rename` must not be empty"));
+                    }
+                    result.rename = Some(value);
+                } else if (meta.path.is_ident("literal") && result.template.is_some())
+                    || (meta.path.is_ident("template") && result.literal.is_some())
+                {
+                    return Err(meta.error(
+                        "`literal` and `template` cannot be used together\nhelp: keep only one of them",
+                    ));
+                } else if meta.path.is_ident("literal") {
+                    let _ = meta.input.parse::<syn::Token![=]>()?;
+                    let expr: Expr = meta.input.parse()?;
+                    let Some(value) = parse_condition_value(&expr) else {
+                        return Err(syn::Error::new_spanned(
+                            &expr,
+                            "invalid value for `literal`\nhelp: use a string, number, or boolean literal, e.g. `literal = \"v2\"`",
+                        ));
+                    };
+                    result.literal = Some(value);
+                } else if meta.path.is_ident("template") {
+                    let value = parse_string_value(&meta)?;
+                    if let Err(placeholder) = template_literal_pattern(&value) {
+                        return Err(meta.error(format!(
+                            "unsupported placeholder `${{{placeholder}}}` in `template`\nhelp: use `${{string}}`, `${{number}}`, `${{bigint}}`, or `${{boolean}}`, e.g. `template = \"usr_${{string}}\"`"
+                        )));
+                    }
+                    result.template = Some(value);
+                } else {
+                    return Err(meta.error(
+                        "unsupported #[gear_mesh(...)] field option\nhelp: supported options are `bigint = \"...\"`, `type = \"...\"`, `zod = \"...\"`, `optional`, `nullable`, `readonly`, `skip`, `rename = \"...\"`, `literal = ...`, and `template = \"...\"`",
+                    ));
+                }
+                Ok(())
+            })?;
+        }
+    }
+
+    Ok(result)
+}
+
+/// 生成コードにそのまま埋め込む式 (空文字列は受け付けない)
+fn parse_expression_value(meta: &ParseNestedMeta<'_>, option: &str) -> Result<String> {
+    let value = parse_string_value(meta)?;
+    if value.trim().is_empty() {
+        return Err(meta.error(format!(
+            "`{option}` must not be empty\nhelp: e.g. `#[gear_mesh(type = \"string\", zod = \"z.string().uuid()\")]`"
+        )));
+    }
+    Ok(value)
+}
+
+/// TypeScriptの識別子として使える名前
+fn parse_identifier_value(meta: &ParseNestedMeta<'_>, option: &str) -> Result<String> {
+    let value = parse_string_value(meta)?;
+    let mut chars = value.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || first == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if !valid {
+        return Err(meta.error(format!(
+            "invalid value for `{option}`\nhelp: use a TypeScript identifier, e.g. `{option} = \"UserDto\"`"
+        )));
+    }
+    Ok(value)
+}
+
+fn parse_bool_value(meta: &ParseNestedMeta<'_>, option: &str) -> Result<bool> {
+    let _ = meta.input.parse::<syn::Token![=]>()?;
+    let value: syn::LitBool = meta.input.parse().map_err(|_| {
+        meta.error(format!(
+            "invalid value for `{option}`\nhelp: use `{option} = true` or `{option} = false`"
+        ))
+    })?;
+    Ok(value.value)
+}
+
+fn bigint_policy(meta: &ParseNestedMeta<'_>, value: &str) -> Result<BigIntPolicy> {
+    if value == "auto" {
+        return Ok(BigIntPolicy::BigInt);
+    }
+    value.parse().map_err(|_| {
+        meta.error(
+            "invalid value for `bigint`\nhelp: use `bigint = \"auto\"`, `\"bigint\"`, `\"number\"` or `\"string\"`",
+        )
+    })
+}
+
+/// `#[serde(with = "string")]` や `serde_with::DisplayFromStr` で文字列としてシリアライズされるか
+fn is_string_encoded(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let Meta::List(list) = &attr.meta else {
+            return false;
+        };
+        let tokens = list.tokens.to_string();
+        if attr.path().is_ident("serde_as") {
+            return tokens.contains("DisplayFromStr");
+        }
+        if !attr.path().is_ident("serde") {
+            return false;
+        }
+        let mut encoded = false;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("with") {
+                let value = parse_string_value(&meta)?;
+                let module = value.rsplit("::").next().unwrap_or_default();
+                encoded |= module == "string" || value.contains("DisplayFromStr");
+            } else {
+                skip_meta_value(&meta)?;
+            }
+            Ok(())
+        });
+        encoded
+    })
+}
+
+/// バリデーション属性の記法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleSyntax {
+    /// `#[validate(...)]` (gear-mesh独自記法とvalidatorクレート互換記法)
+    Validate,
+    /// `#[garde(...)]`
+    Garde,
+}
+
+impl RuleSyntax {
+    fn attribute(self) -> &'static str {
+        match self {
+            RuleSyntax::Validate => "validate",
+            RuleSyntax::Garde => "garde",
+        }
+    }
+}
+
 /// validate属性を解析
+///
+/// `#[validate(...)]` はgear-mesh独自の記法に加えて `validator` クレートの記法を、
+/// `#[garde(...)]` は `garde` クレートの記法を受け付けます。
 pub fn parse_validate_attrs(
     attrs: &[Attribute],
     field_name: Option<&str>,
@@ -54,217 +234,843 @@ pub fn parse_validate_attrs(
     let mut rules = Vec::new();
 
     for attr in attrs {
-        if attr.path().is_ident("validate") {
-            let mut last_message_target = None;
-            attr.parse_nested_meta(|meta| {
-                if meta.path.is_ident("range") {
-                    let mut min = None;
-                    let mut max = None;
-                    meta.parse_nested_meta(|inner| {
-                        if inner.path.is_ident("min") {
-                            let _ = inner.input.parse::<syn::Token![=]>()?;
-                            let lit: syn::Lit = inner.input.parse()?;
-                            match lit {
-                                syn::Lit::Int(i) => min = Some(i.base10_parse::<i64>()? as f64),
-                                syn::Lit::Float(f) => min = Some(f.base10_parse::<f64>()?),
-                                _ => {
-                                    return Err(syn::Error::new_spanned(
-                                        lit,
-                                        "invalid `range(min = ...)` value\nhelp: use an integer or float literal, e.g. `min = 1`",
-                                    ))
-                                }
-                            }
-                        } else if inner.path.is_ident("max") {
-                            let _ = inner.input.parse::<syn::Token![=]>()?;
-                            let lit: syn::Lit = inner.input.parse()?;
-                            match lit {
-                                syn::Lit::Int(i) => max = Some(i.base10_parse::<i64>()? as f64),
-                                syn::Lit::Float(f) => max = Some(f.base10_parse::<f64>()?),
-                                _ => {
-                                    return Err(syn::Error::new_spanned(
-                                        lit,
-                                        "invalid `range(max = ...)` value\nhelp: use an integer or float literal, e.g. `max = 10`",
-                                    ))
-                                }
-                            }
-                        } else {
-                            return Err(inner.error(
-                                "unsupported `range(...)` option\nhelp: supported options are `min = ...` and `max = ...`",
-                            ));
-                        }
-                        Ok(())
-                    })?;
-                    rules.push(ValidationRule::Range { min, max });
-                    last_message_target = None;
-                } else if meta.path.is_ident("length") {
-                    let mut min = None;
-                    let mut max = None;
-                    meta.parse_nested_meta(|inner| {
-                        if inner.path.is_ident("min") {
-                            let _ = inner.input.parse::<syn::Token![=]>()?;
-                            let lit: syn::LitInt = inner.input.parse()?;
-                            min = Some(lit.base10_parse()?);
-                        } else if inner.path.is_ident("max") {
-                            let _ = inner.input.parse::<syn::Token![=]>()?;
-                            let lit: syn::LitInt = inner.input.parse()?;
-                            max = Some(lit.base10_parse()?);
-                        } else {
-                            return Err(inner.error(
-                                "unsupported `length(...)` option\nhelp: supported options are `min = ...` and `max = ...`",
-                            ));
-                        }
-                        Ok(())
-                    })?;
-                    rules.push(ValidationRule::Length { min, max });
-                    last_message_target = None;
-                } else if meta.path.is_ident("email") {
-                    rules.push(ValidationRule::Email);
-                    last_message_target = None;
-                } else if meta.path.is_ident("url") {
-                    rules.push(ValidationRule::Url);
-                    last_message_target = None;
-                } else if meta.path.is_ident("pattern") {
-                    let _ = meta.input.parse::<syn::Token![=]>()?;
-                    let lit: syn::LitStr = meta.input.parse()?;
-                    rules.push(ValidationRule::Pattern(lit.value()));
-                    last_message_target = None;
-                } else if meta.path.is_ident("custom") {
-                    let _ = meta.input.parse::<syn::Token![=]>()?;
-                    let lit: syn::LitStr = meta.input.parse()?;
-                    rules.push(ValidationRule::Custom {
-                        name: lit.value(),
-                        message: None,
-                    });
-                    last_message_target = Some(rules.len() - 1);
-                } else if meta.path.is_ident("message") {
-                    let _ = meta.input.parse::<syn::Token![=]>()?;
-                    let lit: syn::LitStr = meta.input.parse()?;
-                    if let Some(index) = last_message_target
-                        && let Some(ValidationRule::Custom { message, .. }) =
-                            rules.get_mut(index)
-                    {
-                        *message = Some(lit.value());
-                    }
-                } else if meta.path.is_ident("cross_field") {
-                    let mut fields = field_name
-                        .into_iter()
-                        .map(ToOwned::to_owned)
-                        .collect::<Vec<_>>();
-                    let mut rule = None;
-                    let mut message = None;
-
-                    meta.parse_nested_meta(|inner| {
-                        if inner.path.is_ident("match") {
-                            let _ = inner.input.parse::<syn::Token![=]>()?;
-                            let lit: syn::LitStr = inner.input.parse()?;
-                            let other = lit.value().trim().to_string();
-                            if other.is_empty() {
-                                return Err(inner.error(
-                                    "`cross_field(match = ...)` requires a non-empty field name",
-                                ));
-                            }
-                            fields.push(other);
-                            rule = Some(CrossFieldRule::Match);
-                        } else if inner.path.is_ident("at_least_one") {
-                            fields.extend(parse_csv_field_list(&inner)?);
-                            rule = Some(CrossFieldRule::AtLeastOne);
-                        } else if inner.path.is_ident("mutually_exclusive") {
-                            fields.extend(parse_csv_field_list(&inner)?);
-                            rule = Some(CrossFieldRule::MutuallyExclusive);
-                        } else if inner.path.is_ident("message") {
-                            let _ = inner.input.parse::<syn::Token![=]>()?;
-                            let lit: syn::LitStr = inner.input.parse()?;
-                            message = Some(lit.value());
-                        } else {
-                            return Err(inner.error(
-                                "unsupported `cross_field(...)` option\nhelp: supported options are `match = \"field\"`, `at_least_one = \"field1,field2\"`, `mutually_exclusive = \"field1,field2\"`, and `message = \"...\"`",
-                            ));
-                        }
-                        Ok(())
-                    })?;
+        let syntax = if attr.path().is_ident("validate") {
+            RuleSyntax::Validate
+        } else if attr.path().is_ident("garde") {
+            RuleSyntax::Garde
+        } else {
+            continue;
+        };
 
-                    let Some(rule) = rule else {
-                        return Err(meta.error(
-                            "missing `cross_field(...)` rule\nhelp: use `match = \"field\"`, `at_least_one = \"field1,field2\"`, or `mutually_exclusive = \"field1,field2\"`",
-                        ));
-                    };
-                    if matches!(rule, CrossFieldRule::Match) && fields.len() < 2 {
-                        return Err(meta.error(
-                            "`cross_field(match = ...)` requires at least two fields",
-                        ));
-                    }
+        // 直前のルールと、後ろに続く `message = "..."` で指定されたメッセージ
+        let mut last_rule: Option<(usize, Vec<ValidationRule>, RuleMessage)> = None;
+        attr.parse_nested_meta(|meta| {
+            if syntax == RuleSyntax::Validate
+                && let Some((start, original, message)) = &mut last_rule
+                && message.parse_option(&meta)?
+            {
+                rules.truncate(*start);
+                rules.extend(original.iter().cloned().map(|rule| message.apply(rule)));
+                return Ok(());
+            }
+            if syntax == RuleSyntax::Validate
+                && (meta.path.is_ident("message") || meta.path.is_ident("message_key"))
+            {
+                return Err(meta.error(format!(
+                    "`{}` must follow a rule\nhelp: e.g. `#[validate(length(min = 1), message = \"...\")]`",
+                    meta.path.get_ident().map(ToString::to_string).unwrap_or_default()
+                )));
+            }
 
-                    rules.push(ValidationRule::CrossField {
-                        fields,
-                        rule,
-                        message,
-                        path: field_name.map(ToOwned::to_owned),
-                    });
-                    last_message_target = None;
-                } else if meta.path.is_ident("conditional") {
-                    let mut condition = None;
-                    let mut nested_rule = None;
-                    let mut message = None;
-
-                    meta.parse_nested_meta(|inner| {
-                        if inner.path.is_ident("condition") {
-                            let _ = inner.input.parse::<syn::Token![=]>()?;
-                            let lit: syn::LitStr = inner.input.parse()?;
-                            condition = Some(lit.value());
-                        } else if inner.path.is_ident("custom") {
-                            let _ = inner.input.parse::<syn::Token![=]>()?;
-                            let lit: syn::LitStr = inner.input.parse()?;
-                            nested_rule = Some(ValidationRule::Custom {
-                                name: lit.value(),
-                                message: None,
-                            });
-                        } else if inner.path.is_ident("message") {
-                            let _ = inner.input.parse::<syn::Token![=]>()?;
-                            let lit: syn::LitStr = inner.input.parse()?;
-                            message = Some(lit.value());
-                        } else {
-                            return Err(inner.error(
-                                "unsupported `conditional(...)` option\nhelp: supported options are `condition = \"...\"`, `custom = \"validator\"`, and `message = \"...\"`",
-                            ));
-                        }
-                        Ok(())
-                    })?;
+            let before = rules.len();
+            parse_rule(&meta, syntax, field_name, &mut rules)?;
+            last_rule = Some((before, rules[before..].to_vec(), RuleMessage::default()));
+            Ok(())
+        })?;
+    }
 
-                    let Some(condition) = condition else {
-                        return Err(meta.error(
-                            "missing `conditional(condition = ...)` expression",
-                        ));
-                    };
-                    let Some(mut nested_rule) = nested_rule else {
-                        return Err(meta.error(
-                            "missing nested conditional rule\nhelp: use `conditional(condition = \"...\", custom = \"validator\")`",
-                        ));
-                    };
+    Ok(rules)
+}
 
-                    if let ValidationRule::Custom {
-                        message: nested_message,
-                        ..
-                    } = &mut nested_rule
-                    {
-                        *nested_message = message;
-                    }
+fn parse_rule(
+    meta: &ParseNestedMeta<'_>,
+    syntax: RuleSyntax,
+    field_name: Option<&str>,
+    rules: &mut Vec<ValidationRule>,
+) -> Result<()> {
+    let Some(ident) = meta.path.get_ident().map(ToString::to_string) else {
+        return Err(unsupported_rule_error(meta, syntax));
+    };
 
-                    rules.push(ValidationRule::Conditional {
-                        condition,
-                        rule: Box::new(nested_rule),
-                    });
-                    last_message_target = None;
-                } else {
-                    return Err(meta.error(
-                        "unsupported #[validate(...)] rule\nhelp: supported rules are `range`, `length`, `email`, `url`, `pattern = \"...\"`, `custom = \"validator\"`, `cross_field(...)`, and `conditional(...)`",
-                    ));
+    let before = rules.len();
+    let mut message = RuleMessage::default();
+    match (ident.as_str(), syntax) {
+        ("range", _) => parse_range(meta, rules, &mut message)?,
+        ("length", _) => {
+            let (min, max) = parse_length(meta, &mut message)?;
+            rules.push(ValidationRule::Length { min, max });
+        }
+        ("email", _) => {
+            parse_rule_options(meta, "email", &mut message)?;
+            rules.push(ValidationRule::Email);
+        }
+        ("url", _) => {
+            parse_rule_options(meta, "url", &mut message)?;
+            rules.push(ValidationRule::Url);
+        }
+        ("required", _) | ("required_nested", RuleSyntax::Validate) => {
+            parse_rule_options(meta, &ident, &mut message)?;
+            rules.push(ValidationRule::Required);
+        }
+        // ネストした型のスキーマは常に参照されるため、追加のルールは不要
+        ("nested", RuleSyntax::Validate)
+        | ("dive", RuleSyntax::Garde)
+        | ("skip", RuleSyntax::Garde) => {}
+        ("pattern", RuleSyntax::Validate) => {
+            let pattern = parse_string_value(meta)?;
+            check_js_pattern(meta, &pattern)?;
+            rules.push(ValidationRule::Pattern(pattern));
+        }
+        ("pattern", RuleSyntax::Garde) => {
+            let pattern = parse_positional_string(
+                meta,
+                "pattern",
+                "a regex literal, e.g. `pattern(r\"^[a-z]+$\")`",
+            )?;
+            check_js_pattern(meta, &pattern)?;
+            rules.push(ValidationRule::Pattern(pattern));
+        }
+        ("contains", RuleSyntax::Validate) => {
+            let needle = if meta.input.peek(syn::Token![=]) {
+                parse_string_value(meta)?
+            } else {
+                let mut needle = None;
+                meta.parse_nested_meta(|inner| {
+                    if inner.path.is_ident("pattern") {
+                        needle = Some(parse_string_value(&inner)?);
+                        Ok(())
+                    } else {
+                        parse_ignored_rule_option(&inner, "contains", &mut message)
+                    }
+                })?;
+                needle.ok_or_else(|| {
+                    meta.error("missing `contains(pattern = ...)` value\nhelp: use `contains(pattern = \"text\")`")
+                })?
+            };
+            rules.push(ValidationRule::Contains(needle));
+        }
+        ("contains", RuleSyntax::Garde) => {
+            let needle = parse_positional_string(
+                meta,
+                "contains",
+                "a string literal, e.g. `contains(\"@\")`",
+            )?;
+            rules.push(ValidationRule::Contains(needle));
+        }
+        ("starts_with", _) | ("prefix", RuleSyntax::Garde) => {
+            rules.push(ValidationRule::StartsWith(parse_text_rule(
+                meta, syntax, &ident, "usr_",
+            )?));
+        }
+        ("ends_with", _) | ("suffix", RuleSyntax::Garde) => {
+            rules.push(ValidationRule::EndsWith(parse_text_rule(
+                meta, syntax, &ident, ".json",
+            )?));
+        }
+        ("uuid", _) => {
+            parse_rule_options(meta, "uuid", &mut message)?;
+            rules.push(ValidationRule::Uuid);
+        }
+        ("ip", RuleSyntax::Validate) => {
+            let mut version = None;
+            if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|inner| {
+                    if inner.path.is_ident("v4") {
+                        version = Some(IpVersion::V4);
+                    } else if inner.path.is_ident("v6") {
+                        version = Some(IpVersion::V6);
+                    } else {
+                        parse_ignored_rule_option(&inner, "ip", &mut message)?;
+                    }
+                    Ok(())
+                })?;
+            }
+            rules.push(ValidationRule::Ip(version));
+        }
+        ("ip", RuleSyntax::Garde) => rules.push(ValidationRule::Ip(None)),
+        ("ipv4", RuleSyntax::Garde) => rules.push(ValidationRule::Ip(Some(IpVersion::V4))),
+        ("ipv6", RuleSyntax::Garde) => rules.push(ValidationRule::Ip(Some(IpVersion::V6))),
+        ("ascii", _) => {
+            parse_rule_options(meta, "ascii", &mut message)?;
+            rules.push(ValidationRule::Ascii);
+        }
+        ("alphanumeric", _) => {
+            parse_rule_options(meta, "alphanumeric", &mut message)?;
+            rules.push(ValidationRule::Alphanumeric);
+        }
+        ("non_empty", _) => {
+            parse_rule_options(meta, "non_empty", &mut message)?;
+            rules.push(ValidationRule::NonEmpty);
+        }
+        ("unique", _) => {
+            parse_rule_options(meta, "unique", &mut message)?;
+            rules.push(ValidationRule::Unique);
+        }
+        ("multiple_of", _) => {
+            let _ = meta.input.parse::<syn::Token![=]>()?;
+            let expr: Expr = meta.input.parse()?;
+            let step = parse_number_literal(&expr).filter(|step| *step != 0.0);
+            let Some(step) = step else {
+                return Err(syn::Error::new_spanned(
+                    &expr,
+                    "invalid `multiple_of = ...` value\nhelp: use a non-zero integer or float literal, e.g. `multiple_of = 5`",
+                ));
+            };
+            rules.push(ValidationRule::MultipleOf(step));
+        }
+        ("each", _) | ("inner", RuleSyntax::Garde) => {
+            let mut element_rules = Vec::new();
+            meta.parse_nested_meta(|inner| {
+                if !message.parse_option(&inner)? {
+                    parse_rule(&inner, syntax, None, &mut element_rules)?;
                 }
                 Ok(())
             })?;
+            if element_rules.is_empty() {
+                return Err(meta.error(format!(
+                    "`{ident}(...)` requires at least one rule\nhelp: e.g. `{ident}(length(min = 1))`"
+                )));
+            }
+            if element_rules.iter().any(|rule| {
+                matches!(
+                    rule,
+                    ValidationRule::CrossField { .. } | ValidationRule::Conditional { .. }
+                )
+            }) {
+                return Err(meta.error(format!(
+                    "`{ident}(...)` cannot contain `cross_field` or `conditional` rules\nhelp: element rules apply to each value on its own; move object-level rules to the field"
+                )));
+            }
+            rules.push(ValidationRule::Each(element_rules));
+        }
+        ("custom", RuleSyntax::Validate) => {
+            if meta.input.peek(syn::Token![=]) {
+                // `custom = "validateUsername"` は `custom(function = "validate_username")` と同じ名前にする
+                rules.push(ValidationRule::Custom {
+                    name: custom_validator_name(&parse_string_value(meta)?),
+                    message: None,
+                });
+            } else {
+                let mut function = None;
+                meta.parse_nested_meta(|inner| {
+                    if inner.path.is_ident("function") {
+                        let _ = inner.input.parse::<syn::Token![=]>()?;
+                        let expr: Expr = inner.input.parse()?;
+                        function = Some(function_path(&expr).ok_or_else(|| {
+                            syn::Error::new_spanned(
+                                &expr,
+                                "invalid `custom(function = ...)` value\nhelp: use a function path such as `function = \"validate_username\"`",
+                            )
+                        })?);
+                    } else if inner.path.is_ident("use_context") {
+                        return Err(inner.error(
+                            "`custom(use_context)` is not supported by #[derive(GearMesh)]\nhelp: context-dependent validators cannot be mirrored in TypeScript; remove `use_context`",
+                        ));
+                    } else {
+                        parse_ignored_rule_option(&inner, "custom", &mut message)?;
+                    }
+                    Ok(())
+                })?;
+                let Some(function) = function else {
+                    return Err(meta.error(
+                        "missing `custom(function = ...)`\nhelp: use `custom(function = \"validate_username\")`",
+                    ));
+                };
+                rules.push(ValidationRule::Custom {
+                    name: custom_validator_name(&function),
+                    message: None,
+                });
+            }
+        }
+        ("custom", RuleSyntax::Garde) => {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            let expr: Expr = content.parse()?;
+            let Some(function) = function_path(&expr) else {
+                return Err(syn::Error::new_spanned(
+                    &expr,
+                    "unsupported `custom(...)` validator\nhelp: only named functions can be mirrored in TypeScript, e.g. `custom(validate_username)`",
+                ));
+            };
+            rules.push(ValidationRule::Custom {
+                name: custom_validator_name(&function),
+                message: None,
+            });
+        }
+        ("must_match", RuleSyntax::Validate) => {
+            let other = if meta.input.peek(syn::Token![=]) {
+                parse_string_value(meta)?
+            } else {
+                let mut other = None;
+                meta.parse_nested_meta(|inner| {
+                    if inner.path.is_ident("other") {
+                        let _ = inner.input.parse::<syn::Token![=]>()?;
+                        let expr: Expr = inner.input.parse()?;
+                        other = Some(field_reference(&expr).ok_or_else(|| {
+                            syn::Error::new_spanned(
+                                &expr,
+                                "invalid `must_match(other = ...)` value\nhelp: use a field name such as `other = \"password\"`",
+                            )
+                        })?);
+                        Ok(())
+                    } else {
+                        parse_ignored_rule_option(&inner, "must_match", &mut message)
+                    }
+                })?;
+                other.ok_or_else(|| {
+                    meta.error("missing `must_match(other = ...)`\nhelp: use `must_match(other = \"password\")`")
+                })?
+            };
+            rules.push(match_rule(meta, field_name, other)?);
         }
+        ("matches", RuleSyntax::Garde) => {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            let expr: Expr = content.parse()?;
+            let Some(other) = field_reference(&expr) else {
+                return Err(syn::Error::new_spanned(
+                    &expr,
+                    "invalid `matches(...)` value\nhelp: use a field name such as `matches(password)`",
+                ));
+            };
+            rules.push(match_rule(meta, field_name, other)?);
+        }
+        ("regex", RuleSyntax::Validate) => {
+            return Err(meta.error(
+                "`regex(path = ...)` is not supported by #[derive(GearMesh)]\nhelp: the regex static cannot be read at compile time; use `pattern = \"...\"` with the same expression instead",
+            ));
+        }
+        ("does_not_contain", RuleSyntax::Validate) => {
+            return Err(unsupported_known_rule(
+                meta,
+                syntax,
+                "does_not_contain",
+                "it needs a regex lookahead, which the Rust regex engine cannot evaluate; use `custom(function = ...)` instead",
+            ));
+        }
+        ("credit_card", _)
+        | ("non_control_character", RuleSyntax::Validate)
+        | ("phone_number", RuleSyntax::Garde) => {
+            return Err(unsupported_known_rule(
+                meta,
+                syntax,
+                &ident,
+                "it has no Zod equivalent; use `custom(...)` and provide the validator on both sides",
+            ));
+        }
+        ("byte_length", RuleSyntax::Garde) => {
+            return Err(unsupported_known_rule(
+                meta,
+                syntax,
+                &ident,
+                "byte lengths cannot be checked by Zod; use `length(...)` instead",
+            ));
+        }
+        ("cross_field", RuleSyntax::Validate) => {
+            parse_cross_field(meta, field_name, rules, &mut message)?
+        }
+        ("conditional" | "when", RuleSyntax::Validate) => {
+            parse_conditional(meta, syntax, &ident, rules, &mut message)?
+        }
+        _ => return Err(unsupported_rule_error(meta, syntax)),
     }
 
-    Ok(rules)
+    let parsed = rules.split_off(before);
+    rules.extend(parsed.into_iter().map(|rule| message.apply(rule)));
+    Ok(())
+}
+
+/// ルールに付ける `message = "..."` と `message_key = "..."`
+#[derive(Debug, Clone, Default)]
+struct RuleMessage {
+    text: Option<String>,
+    key: Option<String>,
+}
+
+impl RuleMessage {
+    /// `message` / `message_key` オプションであれば読み取り、`true` を返す
+    fn parse_option(&mut self, meta: &ParseNestedMeta<'_>) -> Result<bool> {
+        if meta.path.is_ident("message") {
+            self.text = Some(parse_string_value(meta)?);
+        } else if meta.path.is_ident("message_key") {
+            let key = parse_string_value(meta)?;
+            if key.trim().is_empty() {
+                return Err(meta.error(
+                    "`message_key` must not be empty\nhelp: use a catalog key such as `message_key = \"user.name.too_short\"`",
+                ));
+            }
+            self.key = Some(key);
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    fn to_message(&self) -> Option<ValidationMessage> {
+        match (&self.key, &self.text) {
+            (Some(key), default) => Some(ValidationMessage::Key {
+                key: key.clone(),
+                default: default.clone(),
+            }),
+            (None, Some(text)) => Some(ValidationMessage::Text(text.clone())),
+            (None, None) => None,
+        }
+    }
+
+    /// ルールにメッセージを付ける
+    ///
+    /// `custom` と `cross_field` の固定文言はルール自身に保持し、`each(...)` と
+    /// 条件付きルールでは中のルールに付けます。
+    fn apply(&self, rule: ValidationRule) -> ValidationRule {
+        match self.to_message() {
+            Some(message) => with_message(rule, &message),
+            None => rule,
+        }
+    }
+}
+
+fn with_message(rule: ValidationRule, message: &ValidationMessage) -> ValidationRule {
+    match (rule, message) {
+        (ValidationRule::Custom { name, .. }, ValidationMessage::Text(text)) => {
+            ValidationRule::Custom {
+                name,
+                message: Some(text.clone()),
+            }
+        }
+        (
+            ValidationRule::CrossField {
+                fields, rule, path, ..
+            },
+            ValidationMessage::Text(text),
+        ) => ValidationRule::CrossField {
+            fields,
+            rule,
+            message: Some(text.clone()),
+            path,
+        },
+        (ValidationRule::Each(rules), message) => ValidationRule::Each(
+            rules
+                .into_iter()
+                .map(|rule| with_message(rule, message))
+                .collect(),
+        ),
+        (ValidationRule::Conditional { condition, rule }, message) => ValidationRule::Conditional {
+            condition,
+            rule: Box::new(with_message(*rule, message)),
+        },
+        (ValidationRule::WithMessage { rule, .. }, message) => with_message(*rule, message),
+        (rule, message) => ValidationRule::WithMessage {
+            rule: Box::new(rule),
+            message: message.clone(),
+        },
+    }
+}
+
+fn check_js_pattern(meta: &ParseNestedMeta<'_>, pattern: &str) -> Result<()> {
+    check_pattern(pattern).map_err(|help| {
+        meta.error(format!(
+            "unsupported regex pattern `{pattern}`\nhelp: {help}"
+        ))
+    })
+}
+
+fn unsupported_rule_error(meta: &ParseNestedMeta<'_>, syntax: RuleSyntax) -> syn::Error {
+    match syntax {
+        RuleSyntax::Validate => meta.error(
+            "unsupported #[validate(...)] rule\nhelp: supported rules are `range`, `length`, `non_empty`, `unique`, `each(...)`, `email`, `url`, `uuid`, `ip`, `ascii`, `alphanumeric`, `multiple_of = ...`, `required`, `pattern = \"...\"`, `contains`, `starts_with`, `ends_with`, `must_match`, `custom`, `nested`, `cross_field(...)`, `when(...)`, and `conditional(...)`",
+        ),
+        RuleSyntax::Garde => meta.error(
+            "unsupported #[garde(...)] rule\nhelp: supported rules are `range`, `length`, `inner(...)`, `email`, `url`, `ip`, `ipv4`, `ipv6`, `ascii`, `alphanumeric`, `required`, `pattern(...)`, `contains(...)`, `prefix(...)`, `suffix(...)`, `matches(...)`, `custom(...)`, `dive`, and `skip`",
+        ),
+    }
+}
+
+fn unsupported_known_rule(
+    meta: &ParseNestedMeta<'_>,
+    syntax: RuleSyntax,
+    rule: &str,
+    help: &str,
+) -> syn::Error {
+    meta.error(format!(
+        "`#[{}({rule})]` is not supported by #[derive(GearMesh)]\nhelp: {help}",
+        syntax.attribute()
+    ))
+}
+
+fn parse_range(
+    meta: &ParseNestedMeta<'_>,
+    rules: &mut Vec<ValidationRule>,
+    message: &mut RuleMessage,
+) -> Result<()> {
+    let mut min = None;
+    let mut max = None;
+    let mut exclusive_min = None;
+    let mut exclusive_max = None;
+    meta.parse_nested_meta(|inner| {
+        if inner.path.is_ident("min") {
+            min = Some(parse_number_value(&inner, "min")?);
+        } else if inner.path.is_ident("max") {
+            max = Some(parse_number_value(&inner, "max")?);
+        } else if inner.path.is_ident("exclusive_min") {
+            exclusive_min = Some(parse_number_value(&inner, "exclusive_min")?);
+        } else if inner.path.is_ident("exclusive_max") {
+            exclusive_max = Some(parse_number_value(&inner, "exclusive_max")?);
+        } else if inner.path.is_ident("equal") {
+            let value = parse_number_value(&inner, "equal")?;
+            min = Some(value);
+            max = Some(value);
+        } else if message.parse_option(&inner)? {
+        } else if inner.path.is_ident("code") {
+            parse_ignored_rule_option(&inner, "range", message)?;
+        } else {
+            return Err(inner.error(
+                "unsupported `range(...)` option\nhelp: supported options are `min = ...`, `max = ...`, `exclusive_min = ...`, `exclusive_max = ...`, `equal = ...`, `message = \"...\"`, and `message_key = \"...\"`",
+            ));
+        }
+        Ok(())
+    })?;
+
+    if min.is_some() || max.is_some() || (exclusive_min.is_none() && exclusive_max.is_none()) {
+        rules.push(ValidationRule::Range { min, max });
+    }
+    if exclusive_min.is_some() || exclusive_max.is_some() {
+        rules.push(ValidationRule::ExclusiveRange {
+            min: exclusive_min,
+            max: exclusive_max,
+        });
+    }
+    Ok(())
+}
+
+fn parse_length(
+    meta: &ParseNestedMeta<'_>,
+    message: &mut RuleMessage,
+) -> Result<(Option<usize>, Option<usize>)> {
+    let mut min = None;
+    let mut max = None;
+    meta.parse_nested_meta(|inner| {
+        if inner.path.is_ident("min") {
+            let _ = inner.input.parse::<syn::Token![=]>()?;
+            let lit: syn::LitInt = inner.input.parse()?;
+            min = Some(lit.base10_parse()?);
+        } else if inner.path.is_ident("max") {
+            let _ = inner.input.parse::<syn::Token![=]>()?;
+            let lit: syn::LitInt = inner.input.parse()?;
+            max = Some(lit.base10_parse()?);
+        } else if inner.path.is_ident("equal") {
+            let _ = inner.input.parse::<syn::Token![=]>()?;
+            let lit: syn::LitInt = inner.input.parse()?;
+            let value = lit.base10_parse()?;
+            min = Some(value);
+            max = Some(value);
+        } else if inner.path.is_ident("simple")
+            || inner.path.is_ident("chars")
+            || inner.path.is_ident("utf16")
+        {
+            // garde's counting modes that agree with JavaScript's `.length` closely enough
+        } else if inner.path.is_ident("bytes") || inner.path.is_ident("graphemes") {
+            return Err(inner.error(
+                "unsupported `length(...)` mode\nhelp: byte and grapheme lengths cannot be checked by Zod; use `chars` or `simple`",
+            ));
+        } else if message.parse_option(&inner)? {
+        } else if inner.path.is_ident("code") {
+            parse_ignored_rule_option(&inner, "length", message)?;
+        } else {
+            return Err(inner.error(
+                "unsupported `length(...)` option\nhelp: supported options are `min = ...`, `max = ...`, `equal = ...`, `message = \"...\"`, and `message_key = \"...\"`",
+            ));
+        }
+        Ok(())
+    })?;
+    Ok((min, max))
+}
+
+fn parse_cross_field(
+    meta: &ParseNestedMeta<'_>,
+    field_name: Option<&str>,
+    rules: &mut Vec<ValidationRule>,
+    message: &mut RuleMessage,
+) -> Result<()> {
+    let mut fields = field_name
+        .into_iter()
+        .map(ToOwned::to_owned)
+        .collect::<Vec<_>>();
+    let mut rule = None;
+
+    meta.parse_nested_meta(|inner| {
+        if message.parse_option(&inner)? {
+        } else if inner.path.is_ident("match") {
+            let _ = inner.input.parse::<syn::Token![=]>()?;
+            let lit: syn::LitStr = inner.input.parse()?;
+            let other = lit.value().trim().to_string();
+            if other.is_empty() {
+                return Err(inner.error(
+                    "`cross_field(match = ...)` requires a non-empty field name",
+                ));
+            }
+            fields.push(other);
+            rule = Some(CrossFieldRule::Match);
+        } else if inner.path.is_ident("at_least_one") {
+            fields.extend(parse_csv_field_list(&inner)?);
+            rule = Some(CrossFieldRule::AtLeastOne);
+        } else if inner.path.is_ident("mutually_exclusive") {
+            fields.extend(parse_csv_field_list(&inner)?);
+            rule = Some(CrossFieldRule::MutuallyExclusive);
+        } else {
+            return Err(inner.error(
+                "unsupported `cross_field(...)` option\nhelp: supported options are `match = \"field\"`, `at_least_one = \"field1,field2\"`, `mutually_exclusive = \"field1,field2\"`, `message = \"...\"`, and `message_key = \"...\"`",
+            ));
+        }
+        Ok(())
+    })?;
+
+    let Some(rule) = rule else {
+        return Err(meta.error(
+            "missing `cross_field(...)` rule\nhelp: use `match = \"field\"`, `at_least_one = \"field1,field2\"`, or `mutually_exclusive = \"field1,field2\"`",
+        ));
+    };
+    if matches!(rule, CrossFieldRule::Match) && fields.len() < 2 {
+        return Err(meta.error("`cross_field(match = ...)` requires at least two fields"));
+    }
+
+    rules.push(ValidationRule::CrossField {
+        fields,
+        rule,
+        message: None,
+        path: field_name.map(ToOwned::to_owned),
+    });
+    Ok(())
+}
+
+/// `conditional(condition = "...", ...)` と `when(field = "...", eq = ..., ...)` を解析
+///
+/// 条件以外のオプションは組み込みルールとして解析し、ルールごとに条件付きルールを生成します。
+fn parse_conditional(
+    meta: &ParseNestedMeta<'_>,
+    syntax: RuleSyntax,
+    keyword: &str,
+    rules: &mut Vec<ValidationRule>,
+    message: &mut RuleMessage,
+) -> Result<()> {
+    let mut expression = None;
+    let mut field = None;
+    let mut comparison = None;
+    let mut presence = None;
+    let mut nested_rules = Vec::new();
+
+    meta.parse_nested_meta(|inner| {
+        if message.parse_option(&inner)? {
+        } else if inner.path.is_ident("condition") {
+            expression = Some(parse_string_value(&inner)?);
+        } else if inner.path.is_ident("field") {
+            field = Some(parse_string_value(&inner)?);
+        } else if inner.path.is_ident("eq") || inner.path.is_ident("ne") {
+            let _ = inner.input.parse::<syn::Token![=]>()?;
+            let expr: Expr = inner.input.parse()?;
+            let Some(value) = parse_condition_value(&expr) else {
+                return Err(syn::Error::new_spanned(
+                    &expr,
+                    "invalid condition value\nhelp: use a string, number, or boolean literal, e.g. `eq = \"company\"`",
+                ));
+            };
+            comparison = Some((inner.path.is_ident("eq"), value));
+        } else if inner.path.is_ident("present") || inner.path.is_ident("absent") {
+            presence = Some((inner.path.is_ident("present"), parse_string_value(&inner)?));
+        } else {
+            parse_rule(&inner, syntax, None, &mut nested_rules)?;
+        }
+        Ok(())
+    })?;
+
+    let condition = match (expression, field, comparison, presence) {
+        (Some(expression), None, None, None) => Condition::Expression(expression),
+        (None, Some(field), Some((true, value)), None) => Condition::Equals { field, value },
+        (None, Some(field), Some((false, value)), None) => Condition::NotEquals { field, value },
+        (None, None, None, Some((true, field))) => Condition::Present(field),
+        (None, None, None, Some((false, field))) => Condition::Absent(field),
+        (None, None, None, None) => {
+            return Err(meta.error(format!(
+                "missing `{keyword}(...)` condition\nhelp: use `field = \"kind\", eq = \"company\"`, `present = \"field\"`, or `absent = \"field\"`"
+            )));
+        }
+        _ => {
+            return Err(meta.error(format!(
+                "invalid `{keyword}(...)` condition\nhelp: use exactly one of `field = \"...\"` with `eq`/`ne`, `present = \"...\"`, `absent = \"...\"`, or `condition = \"...\"`"
+            )));
+        }
+    };
+
+    if nested_rules.is_empty() {
+        return Err(meta.error(format!(
+            "missing nested conditional rule\nhelp: add a built-in rule, e.g. `{keyword}(present = \"vat_id\", required)`"
+        )));
+    }
+    if nested_rules.iter().any(|rule| {
+        matches!(
+            rule,
+            ValidationRule::CrossField { .. } | ValidationRule::Conditional { .. }
+        )
+    }) {
+        return Err(meta.error(format!(
+            "`{keyword}(...)` cannot contain `cross_field`, `conditional`, or `when` rules"
+        )));
+    }
+
+    for rule in nested_rules {
+        rules.push(ValidationRule::Conditional {
+            condition: condition.clone(),
+            rule: Box::new(rule),
+        });
+    }
+    Ok(())
+}
+
+fn parse_condition_value(expr: &Expr) -> Option<ConditionValue> {
+    if let Expr::Lit(expr_lit) = expr {
+        match &expr_lit.lit {
+            Lit::Str(lit) => return Some(ConditionValue::String(lit.value())),
+            Lit::Bool(lit) => return Some(ConditionValue::Bool(lit.value)),
+            _ => {}
+        }
+    }
+    parse_number_literal(expr).map(ConditionValue::Number)
+}
+
+fn match_rule(
+    meta: &ParseNestedMeta<'_>,
+    field_name: Option<&str>,
+    other: String,
+) -> Result<ValidationRule> {
+    let Some(field_name) = field_name else {
+        return Err(meta.error("field-matching rules can only be used on named fields"));
+    };
+    Ok(ValidationRule::CrossField {
+        fields: vec![field_name.to_string(), other],
+        rule: CrossFieldRule::Match,
+        message: None,
+        path: Some(field_name.to_string()),
+    })
+}
+
+/// validatorクレートの `email(message = "...")` のような任意オプションを解析
+fn parse_rule_options(
+    meta: &ParseNestedMeta<'_>,
+    rule: &str,
+    message: &mut RuleMessage,
+) -> Result<()> {
+    if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|inner| parse_ignored_rule_option(&inner, rule, message))?;
+    }
+    Ok(())
+}
+
+fn parse_ignored_rule_option(
+    inner: &ParseNestedMeta<'_>,
+    rule: &str,
+    message: &mut RuleMessage,
+) -> Result<()> {
+    if message.parse_option(inner)? {
+        Ok(())
+    } else if inner.path.is_ident("code") {
+        // Rust側のエラーコードはTypeScript出力に影響しない
+        let _ = parse_string_value(inner)?;
+        Ok(())
+    } else {
+        Err(inner.error(format!(
+            "unsupported `{rule}(...)` option\nhelp: supported options are `message = \"...\"`, `message_key = \"...\"`, and `code = \"...\"`"
+        )))
+    }
+}
+
+fn parse_number_value(inner: &ParseNestedMeta<'_>, option: &str) -> Result<f64> {
+    let _ = inner.input.parse::<syn::Token![=]>()?;
+    let expr: Expr = inner.input.parse()?;
+    parse_number_literal(&expr).ok_or_else(|| invalid_number(&expr, option))
+}
+
+/// 符号付きの整数・浮動小数点リテラルを解析
+fn parse_number_literal(expr: &Expr) -> Option<f64> {
+    let (negative, lit) = match expr {
+        Expr::Lit(expr_lit) => (false, &expr_lit.lit),
+        Expr::Unary(unary) if matches!(unary.op, syn::UnOp::Neg(_)) => match &*unary.expr {
+            Expr::Lit(expr_lit) => (true, &expr_lit.lit),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let value = match lit {
+        Lit::Int(i) => i.base10_parse::<f64>().ok()?,
+        Lit::Float(f) => f.base10_parse::<f64>().ok()?,
+        _ => return None,
+    };
+    Some(if negative { -value } else { value })
+}
+
+fn invalid_number(expr: &Expr, option: &str) -> syn::Error {
+    syn::Error::new_spanned(
+        expr,
+        format!(
+            "invalid `range({option} = ...)` value\nhelp: use an integer or float literal, e.g. `{option} = 1`"
+        ),
+    )
+}
+
+/// `rule = "text"` と `rule("text")` の両方の書き方を受け付ける
+fn parse_text_rule(
+    meta: &ParseNestedMeta<'_>,
+    syntax: RuleSyntax,
+    rule: &str,
+    example: &str,
+) -> Result<String> {
+    if syntax == RuleSyntax::Validate && meta.input.peek(syn::Token![=]) {
+        return parse_string_value(meta);
+    }
+    parse_positional_string(
+        meta,
+        rule,
+        &format!("a string literal, e.g. `{rule}(\"{example}\")`"),
+    )
+}
+
+fn parse_positional_string(
+    meta: &ParseNestedMeta<'_>,
+    rule: &str,
+    expected: &str,
+) -> Result<String> {
+    let content;
+    syn::parenthesized!(content in meta.input);
+    let expr: Expr = content.parse()?;
+    if let Expr::Lit(expr_lit) = &expr
+        && let Lit::Str(lit) = &expr_lit.lit
+    {
+        return Ok(lit.value());
+    }
+    Err(syn::Error::new_spanned(
+        &expr,
+        format!(
+            "invalid `{rule}(...)` value\nhelp: expected {expected}; statics and expressions cannot be read at compile time"
+        ),
+    ))
+}
+
+/// `function = "path"` / `function = path` の関数パスを取得
+fn function_path(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            Lit::Str(lit) => Some(lit.value()),
+            _ => None,
+        },
+        Expr::Path(path) => Some(
+            path.path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::"),
+        ),
+        _ => None,
+    }
+}
+
+fn field_reference(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            Lit::Str(lit) if !lit.value().trim().is_empty() => Some(lit.value().trim().to_string()),
+            _ => None,
+        },
+        Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+        _ => None,
+    }
 }
 
 /// serde属性を解析してリネーム情報を取得
@@ -306,6 +1112,16 @@ pub fn parse_serde_type_attrs(attrs: &[Attribute]) -> Result<SerdeTypeAttrs> {
                             "invalid `serde(rename_all = ...)` value\nhelp: supported values are `lowercase`, `UPPERCASE`, `camelCase`, `snake_case`, `PascalCase`, `SCREAMING_SNAKE_CASE`, `kebab-case`, and `SCREAMING-KEBAB-CASE`",
                         )
                     })?);
+                } else if meta.path.is_ident("tag") {
+                    result.tag = Some(parse_string_value(&meta)?);
+                } else if meta.path.is_ident("content") {
+                    result.content = Some(parse_string_value(&meta)?);
+                } else if meta.path.is_ident("untagged") {
+                    result.untagged = true;
+                } else if meta.path.is_ident("transparent") {
+                    result.transparent = true;
+                } else {
+                    skip_meta_value(&meta)?;
                 }
                 Ok(())
             })?;
@@ -315,13 +1131,25 @@ pub fn parse_serde_type_attrs(attrs: &[Attribute]) -> Result<SerdeTypeAttrs> {
     Ok(result)
 }
 
-fn parse_string_value(meta: &syn::meta::ParseNestedMeta<'_>) -> Result<String> {
+/// 解釈しない属性の値 (`= ...` や `(...)`) を読み飛ばす
+fn skip_meta_value(meta: &ParseNestedMeta<'_>) -> Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        let _: Expr = meta.value()?.parse()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in meta.input);
+        let _: proc_macro2::TokenStream = content.parse()?;
+    }
+    Ok(())
+}
+
+fn parse_string_value(meta: &ParseNestedMeta<'_>) -> Result<String> {
     let _ = meta.input.parse::<syn::Token![=]>()?;
     let value: syn::LitStr = meta.input.parse()?;
     Ok(value.value())
 }
 
-fn parse_csv_field_list(meta: &syn::meta::ParseNestedMeta<'_>) -> Result<Vec<String>> {
+fn parse_csv_field_list(meta: &ParseNestedMeta<'_>) -> Result<Vec<String>> {
     let _ = meta.input.parse::<syn::Token![=]>()?;
     let lit: syn::LitStr = meta.input.parse()?;
     Ok(lit
@@ -351,6 +1179,49 @@ pub fn extract_doc_comments(attrs: &[Attribute]) -> String {
         .join("\n")
 }
 
+/// `#[deprecated]`・`#[deprecated = "..."]`・`#[deprecated(since = "...", note = "...")]` を解析
+pub fn parse_deprecated(attrs: &[Attribute]) -> Result<Option<Deprecation>> {
+    let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident("deprecated")) else {
+        return Ok(None);
+    };
+    let mut deprecation = Deprecation::default();
+    match &attr.meta {
+        Meta::Path(_) => {}
+        Meta::NameValue(nv) => {
+            if let Expr::Lit(expr_lit) = &nv.value
+                && let Lit::Str(note) = &expr_lit.lit
+            {
+                deprecation.note = Some(note.value());
+            }
+        }
+        Meta::List(_) => attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("since") {
+                deprecation.since = Some(parse_string_value(&meta)?);
+            } else if meta.path.is_ident("note") {
+                deprecation.note = Some(parse_string_value(&meta)?);
+            } else {
+                skip_meta_value(&meta)?;
+            }
+            Ok(())
+        })?,
+    }
+    Ok(Some(deprecation))
+}
+
+/// `#[doc(hidden)]` が付いているか
+pub fn is_doc_hidden(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let mut hidden = false;
+        if attr.path().is_ident("doc") && matches!(attr.meta, Meta::List(_)) {
+            let _ = attr.parse_nested_meta(|meta| {
+                hidden |= meta.path.is_ident("hidden");
+                skip_meta_value(&meta)
+            });
+        }
+        hidden
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -388,6 +1259,127 @@ mod tests {
         assert!(message.contains("bigint = \"auto\""));
     }
 
+    #[test]
+    fn test_parse_bigint_policies() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[gear_mesh(bigint = "number")]
+            struct Order {
+                id: u64,
+            }
+        };
+        let attrs = parse_gear_mesh_attrs(&input.attrs).unwrap();
+        assert_eq!(attrs.bigint_policy(), Some(BigIntPolicy::Number));
+
+        let field: syn::Field = parse_quote! {
+            #[gear_mesh(bigint = "bigint")]
+            total: u64
+        };
+        assert_eq!(
+            parse_field_attrs(&field.attrs).unwrap().bigint,
+            Some(BigIntPolicy::BigInt)
+        );
+
+        for field in [
+            parse_quote! {
+                #[serde(rename = "id", with = "string")]
+                id: u64
+            },
+            parse_quote! {
+                #[serde(with = "::serde_with::As::<::serde_with::DisplayFromStr>")]
+                id: u64
+            },
+        ] {
+            let field: syn::Field = field;
+            assert_eq!(
+                parse_field_attrs(&field.attrs).unwrap().bigint,
+                Some(BigIntPolicy::String)
+            );
+        }
+
+        let field: syn::Field = parse_quote! {
+            #[serde(with = "chrono::serde::ts_seconds")]
+            created_at: i64
+        };
+        assert_eq!(parse_field_attrs(&field.attrs).unwrap().bigint, None);
+    }
+
+    #[test]
+    fn test_parse_override_attributes() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[gear_mesh(rename = "UserDto", export = false, option_style = "optional")]
+            struct User {
+                id: i32,
+            }
+        };
+        let attrs = parse_gear_mesh_attrs(&input.attrs).unwrap();
+        assert_eq!(attrs.rename.as_deref(), Some("UserDto"));
+        assert!(!attrs.export);
+        assert_eq!(
+            attrs.option_style,
+            Some(gear_mesh_core::OptionStyle::Optional)
+        );
+
+        let field: syn::Field = parse_quote! {
+            #[gear_mesh(type = "string", zod = "z.string().uuid()", optional, nullable, readonly)]
+            #[gear_mesh(skip, rename = "userId")]
+            id: Uuid
+        };
+        let attrs = parse_field_attrs(&field.attrs).unwrap();
+        assert_eq!(attrs.ts_type.as_deref(), Some("string"));
+        assert_eq!(attrs.zod.as_deref(), Some("z.string().uuid()"));
+        assert!(attrs.optional && attrs.nullable && attrs.readonly && attrs.skip);
+        assert_eq!(attrs.rename.as_deref(), Some("userId"));
+
+        let field: syn::Field = parse_quote! {
+            #[gear_mesh(frozen)]
+            id: Uuid
+        };
+        let message = parse_field_attrs(&field.attrs).unwrap_err().to_string();
+        assert!(message.contains("unsupported #[gear_mesh(...)] field option"));
+        assert!(message.contains("`readonly`"));
+
+        let input: syn::DeriveInput = parse_quote! {
+            #[gear_mesh(rename = "user-dto")]
+            struct User;
+        };
+        let message = parse_gear_mesh_attrs(&input.attrs).unwrap_err().to_string();
+        assert!(message.contains("invalid value for `rename`"));
+    }
+
+    #[test]
+    fn test_parse_literal_and_template_attributes() {
+        let field: syn::Field = parse_quote! {
+            #[gear_mesh(literal = "v2")]
+            version: String
+        };
+        let attrs = parse_field_attrs(&field.attrs).unwrap();
+        assert_eq!(
+            attrs.literal,
+            Some(ConditionValue::String("v2".to_string()))
+        );
+
+        let field: syn::Field = parse_quote! {
+            #[gear_mesh(literal = -1)]
+            code: i32
+        };
+        let attrs = parse_field_attrs(&field.attrs).unwrap();
+        assert_eq!(attrs.literal, Some(ConditionValue::Number(-1.0)));
+
+        let field: syn::Field = parse_quote! {
+            #[gear_mesh(template = "usr_${string}")]
+            id: String
+        };
+        let attrs = parse_field_attrs(&field.attrs).unwrap();
+        assert_eq!(attrs.template.as_deref(), Some("usr_${string}"));
+
+        let field: syn::Field = parse_quote! {
+            #[gear_mesh(template = "usr_${uuid}")]
+            id: String
+        };
+        let message = parse_field_attrs(&field.attrs).unwrap_err().to_string();
+        assert!(message.contains("unsupported placeholder `${uuid}`"));
+    }
+
     #[test]
     fn test_invalid_validate_rule_reports_supported_rules() {
         let field: syn::Field = parse_quote! {
@@ -415,6 +1407,76 @@ mod tests {
         assert_eq!(attrs.serde.rename_all, Some(RenameRule::CamelCase));
     }
 
+    #[test]
+    fn test_parse_serde_enum_representation() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[serde(tag = "type", content = "data", rename_all = "snake_case", deny_unknown_fields)]
+            enum ApiEvent {
+                Ping,
+            }
+        };
+        let attrs = parse_gear_mesh_attrs(&input.attrs).unwrap();
+        assert_eq!(attrs.serde.tag.as_deref(), Some("type"));
+        assert_eq!(attrs.serde.content.as_deref(), Some("data"));
+        assert!(!attrs.serde.untagged);
+        assert_eq!(attrs.serde.rename_all, Some(RenameRule::SnakeCase));
+    }
+
+    #[test]
+    fn test_doc_hidden_is_detected() {
+        let input: syn::DeriveInput = parse_quote! {
+            /// Internal bookkeeping
+            #[doc(hidden)]
+            struct Audit {
+                id: i32,
+            }
+        };
+        assert!(is_doc_hidden(&input.attrs));
+        assert_eq!(extract_doc_comments(&input.attrs), " Internal bookkeeping");
+
+        let input: syn::DeriveInput = parse_quote! {
+            #[doc(alias = "Member")]
+            struct User {
+                id: i32,
+            }
+        };
+        assert!(!is_doc_hidden(&input.attrs));
+    }
+
+    #[test]
+    fn test_parse_deprecated_forms() {
+        let field: syn::Field = parse_quote! {
+            #[deprecated(since = "1.2.0", note = "Use `email` instead")]
+            login: String
+        };
+        assert_eq!(
+            parse_deprecated(&field.attrs).unwrap(),
+            Some(Deprecation {
+                since: Some("1.2.0".to_string()),
+                note: Some("Use `email` instead".to_string()),
+            })
+        );
+
+        let field: syn::Field = parse_quote! {
+            #[deprecated = "Gone soon"]
+            login: String
+        };
+        let deprecation = parse_deprecated(&field.attrs).unwrap().unwrap();
+        assert_eq!(deprecation.note.as_deref(), Some("Gone soon"));
+
+        let field: syn::Field = parse_quote! {
+            #[deprecated]
+            login: String
+        };
+        assert_eq!(
+            parse_deprecated(&field.attrs).unwrap(),
+            Some(Deprecation::default())
+        );
+
+        let field: syn::Field = parse_quote! { login: String };
+        assert_eq!(parse_deprecated(&field.attrs).unwrap(), None);
+    }
+
     #[test]
     fn test_invalid_serde_rename_all_reports_supported_values() {
         let input: syn::DeriveInput = parse_quote! {
@@ -441,7 +1503,7 @@ mod tests {
         let err = parse_validate_attrs(&field.attrs, Some("value")).unwrap_err();
         let message = err.to_string();
         assert!(message.contains("unsupported `range(...)` option"));
-        assert!(message.contains("supported options are `min = ...` and `max = ...`"));
+        assert!(message.contains("supported options are `min = ...`, `max = ...`"));
     }
 
     #[test]
@@ -454,7 +1516,7 @@ mod tests {
         let err = parse_validate_attrs(&field.attrs, Some("value")).unwrap_err();
         let message = err.to_string();
         assert!(message.contains("unsupported `length(...)` option"));
-        assert!(message.contains("supported options are `min = ...` and `max = ...`"));
+        assert!(message.contains("supported options are `min = ...`, `max = ...`"));
     }
 
     #[test]
@@ -470,7 +1532,7 @@ mod tests {
             ValidationRule::Custom {
                 name,
                 message: Some(message),
-            } if name == "validateUsername" && message == "Username taken"
+            } if name == "Username" && message == "Username taken"
         ));
         assert!(matches!(
             &rules[1],
@@ -484,6 +1546,23 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_custom_validator_spellings_share_a_name() {
+        for attr in [
+            quote::quote!(#[validate(custom = "Username")]),
+            quote::quote!(#[validate(custom = "validateUsername")]),
+            quote::quote!(#[validate(custom = "validate_username")]),
+            quote::quote!(#[validate(custom(function = "crate::validators::validate_username"))]),
+        ] {
+            let field: syn::Field = parse_quote! { #attr name: String };
+            let rules = parse_validate_attrs(&field.attrs, Some("name")).unwrap();
+            assert!(
+                matches!(&rules[0], ValidationRule::Custom { name, .. } if name == "Username"),
+                "{attr}"
+            );
+        }
+    }
+
     #[test]
     fn test_cross_field_match_requires_other_field() {
         let field: syn::Field = parse_quote! {
@@ -494,4 +1573,376 @@ mod tests {
         let err = parse_validate_attrs(&field.attrs, Some("password_confirmation")).unwrap_err();
         assert!(err.to_string().contains("requires a non-empty field name"));
     }
+
+    #[test]
+    fn test_parse_validator_crate_syntax() {
+        let field: syn::Field = parse_quote! {
+            #[validate(
+                length(equal = 8, code = "len"),
+                range(exclusive_min = -1.5, max = 10),
+                contains(pattern = "a.b"),
+                must_match(other = "password"),
+                custom(function = "crate::validators::validate_username", message = "Username taken"),
+                email(code = "email"),
+                nested
+            )]
+            password_confirmation: String
+        };
+
+        let rules = parse_validate_attrs(&field.attrs, Some("password_confirmation")).unwrap();
+        assert_eq!(
+            rules,
+            vec![
+                ValidationRule::Length {
+                    min: Some(8),
+                    max: Some(8),
+                },
+                ValidationRule::Range {
+                    min: None,
+                    max: Some(10.0),
+                },
+                ValidationRule::ExclusiveRange {
+                    min: Some(-1.5),
+                    max: None,
+                },
+                ValidationRule::Contains("a.b".to_string()),
+                ValidationRule::CrossField {
+                    fields: vec!["password_confirmation".to_string(), "password".to_string(),],
+                    rule: CrossFieldRule::Match,
+                    message: None,
+                    path: Some("password_confirmation".to_string()),
+                },
+                ValidationRule::Custom {
+                    name: "Username".to_string(),
+                    message: Some("Username taken".to_string()),
+                },
+                ValidationRule::Email,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_garde_syntax() {
+        let field: syn::Field = parse_quote! {
+            #[garde(
+                length(chars, min = 3, max = 20),
+                range(min = 0),
+                prefix("usr_"),
+                suffix(".id"),
+                pattern(r"^[a-z_.]+$"),
+                matches(user_id),
+                custom(validate_user_id),
+                dive
+            )]
+            confirm_id: String
+        };
+
+        let rules = parse_validate_attrs(&field.attrs, Some("confirm_id")).unwrap();
+        assert_eq!(
+            rules,
+            vec![
+                ValidationRule::Length {
+                    min: Some(3),
+                    max: Some(20),
+                },
+                ValidationRule::Range {
+                    min: Some(0.0),
+                    max: None,
+                },
+                ValidationRule::StartsWith("usr_".to_string()),
+                ValidationRule::EndsWith(".id".to_string()),
+                ValidationRule::Pattern("^[a-z_.]+$".to_string()),
+                ValidationRule::CrossField {
+                    fields: vec!["confirm_id".to_string(), "user_id".to_string()],
+                    rule: CrossFieldRule::Match,
+                    message: None,
+                    path: Some("confirm_id".to_string()),
+                },
+                ValidationRule::Custom {
+                    name: "UserId".to_string(),
+                    message: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_compat_rules_report_alternatives() {
+        let field: syn::Field = parse_quote! {
+            #[validate(regex(path = *RE_USERNAME))]
+            value: String
+        };
+        let message = parse_validate_attrs(&field.attrs, Some("value"))
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("`regex(path = ...)` is not supported"));
+        assert!(message.contains("use `pattern = \"...\"`"));
+
+        let field: syn::Field = parse_quote! {
+            #[garde(credit_card)]
+            value: String
+        };
+        let message = parse_validate_attrs(&field.attrs, Some("value"))
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("`#[garde(credit_card)]` is not supported"));
+
+        let field: syn::Field = parse_quote! {
+            #[garde(length(bytes, max = 10))]
+            value: String
+        };
+        let message = parse_validate_attrs(&field.attrs, Some("value"))
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("unsupported `length(...)` mode"));
+    }
+
+    #[test]
+    fn test_parse_per_rule_messages() {
+        let field: syn::Field = parse_quote! {
+            #[validate(
+                email(message = "bad email"),
+                length(min = 3, message_key = "user.name.too_short", message = "At least {min}"),
+                pattern = "^[a-z]+$",
+                message = "lowercase only",
+                custom = "Username",
+                message_key = "user.name.taken"
+            )]
+            value: String
+        };
+
+        let rules = parse_validate_attrs(&field.attrs, Some("value")).unwrap();
+        assert_eq!(
+            rules,
+            vec![
+                ValidationRule::WithMessage {
+                    rule: Box::new(ValidationRule::Email),
+                    message: ValidationMessage::Text("bad email".to_string()),
+                },
+                ValidationRule::WithMessage {
+                    rule: Box::new(ValidationRule::Length {
+                        min: Some(3),
+                        max: None,
+                    }),
+                    message: ValidationMessage::Key {
+                        key: "user.name.too_short".to_string(),
+                        default: Some("At least {min}".to_string()),
+                    },
+                },
+                ValidationRule::WithMessage {
+                    rule: Box::new(ValidationRule::Pattern("^[a-z]+$".to_string())),
+                    message: ValidationMessage::Text("lowercase only".to_string()),
+                },
+                ValidationRule::WithMessage {
+                    rule: Box::new(ValidationRule::Custom {
+                        name: "Username".to_string(),
+                        message: None,
+                    }),
+                    message: ValidationMessage::Key {
+                        key: "user.name.taken".to_string(),
+                        default: None,
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_messages_apply_to_element_and_conditional_rules() {
+        let field: syn::Field = parse_quote! {
+            #[validate(
+                each(length(min = 1), message_key = "tags.empty"),
+                when(present = "company", required, message = "VAT ID is required")
+            )]
+            value: Vec<String>
+        };
+
+        let rules = parse_validate_attrs(&field.attrs, Some("value")).unwrap();
+        assert_eq!(
+            rules,
+            vec![
+                ValidationRule::Each(vec![ValidationRule::WithMessage {
+                    rule: Box::new(ValidationRule::Length {
+                        min: Some(1),
+                        max: None,
+                    }),
+                    message: ValidationMessage::Key {
+                        key: "tags.empty".to_string(),
+                        default: None,
+                    },
+                }]),
+                ValidationRule::Conditional {
+                    condition: Condition::Present("company".to_string()),
+                    rule: Box::new(ValidationRule::WithMessage {
+                        rule: Box::new(ValidationRule::Required),
+                        message: ValidationMessage::Text("VAT ID is required".to_string()),
+                    }),
+                },
+            ]
+        );
+
+        let field: syn::Field = parse_quote! {
+            #[validate(message = "orphan")]
+            value: String
+        };
+        let message = parse_validate_attrs(&field.attrs, Some("value"))
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("`message` must follow a rule"));
+    }
+
+    #[test]
+    fn test_parse_collection_and_string_rules() {
+        let field: syn::Field = parse_quote! {
+            #[validate(
+                length(min = 1, max = 10),
+                unique,
+                non_empty,
+                each(length(equal = 36), uuid),
+                starts_with = "a",
+                ends_with("z"),
+                ip(v6),
+                ascii,
+                alphanumeric,
+                multiple_of = 0.5
+            )]
+            tags: Vec<String>
+        };
+
+        let rules = parse_validate_attrs(&field.attrs, Some("tags")).unwrap();
+        assert_eq!(
+            rules,
+            vec![
+                ValidationRule::Length {
+                    min: Some(1),
+                    max: Some(10),
+                },
+                ValidationRule::Unique,
+                ValidationRule::NonEmpty,
+                ValidationRule::Each(vec![
+                    ValidationRule::Length {
+                        min: Some(36),
+                        max: Some(36),
+                    },
+                    ValidationRule::Uuid,
+                ]),
+                ValidationRule::StartsWith("a".to_string()),
+                ValidationRule::EndsWith("z".to_string()),
+                ValidationRule::Ip(Some(IpVersion::V6)),
+                ValidationRule::Ascii,
+                ValidationRule::Alphanumeric,
+                ValidationRule::MultipleOf(0.5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_garde_inner_rules() {
+        let field: syn::Field = parse_quote! {
+            #[garde(inner(ipv4, length(max = 15)))]
+            addresses: Vec<String>
+        };
+
+        let rules = parse_validate_attrs(&field.attrs, Some("addresses")).unwrap();
+        assert_eq!(
+            rules,
+            vec![ValidationRule::Each(vec![
+                ValidationRule::Ip(Some(IpVersion::V4)),
+                ValidationRule::Length {
+                    min: None,
+                    max: Some(15),
+                },
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_each_rejects_object_level_rules() {
+        let field: syn::Field = parse_quote! {
+            #[validate(each(cross_field(at_least_one = "a,b")))]
+            values: Vec<String>
+        };
+
+        let message = parse_validate_attrs(&field.attrs, Some("values"))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.contains("`each(...)` cannot contain `cross_field` or `conditional` rules")
+        );
+    }
+
+    #[test]
+    fn test_parse_structured_conditions() {
+        let field: syn::Field = parse_quote! {
+            #[validate(
+                when(field = "kind", eq = "company", required, length(min = 8)),
+                when(present = "discount", range(max = 50)),
+                conditional(condition = "data.beta", custom = "BetaCode", message = "Invalid code")
+            )]
+            vat_id: Option<String>
+        };
+
+        let rules = parse_validate_attrs(&field.attrs, Some("vat_id")).unwrap();
+        let company = Condition::Equals {
+            field: "kind".to_string(),
+            value: ConditionValue::String("company".to_string()),
+        };
+        assert_eq!(
+            rules,
+            vec![
+                ValidationRule::Conditional {
+                    condition: company.clone(),
+                    rule: Box::new(ValidationRule::Required),
+                },
+                ValidationRule::Conditional {
+                    condition: company,
+                    rule: Box::new(ValidationRule::Length {
+                        min: Some(8),
+                        max: None,
+                    }),
+                },
+                ValidationRule::Conditional {
+                    condition: Condition::Present("discount".to_string()),
+                    rule: Box::new(ValidationRule::Range {
+                        min: None,
+                        max: Some(50.0),
+                    }),
+                },
+                ValidationRule::Conditional {
+                    condition: Condition::Expression("data.beta".to_string()),
+                    rule: Box::new(ValidationRule::Custom {
+                        name: "BetaCode".to_string(),
+                        message: Some("Invalid code".to_string()),
+                    }),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_when_requires_a_single_condition() {
+        let field: syn::Field = parse_quote! {
+            #[validate(when(field = "kind", present = "kind", required))]
+            vat_id: Option<String>
+        };
+
+        let message = parse_validate_attrs(&field.attrs, Some("vat_id"))
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("invalid `when(...)` condition"));
+    }
+
+    #[test]
+    fn test_pattern_rejects_rust_only_syntax() {
+        let field: syn::Field = parse_quote! {
+            #[validate(pattern = "(?P<id>\\d+)")]
+            code: String
+        };
+
+        let message = parse_validate_attrs(&field.attrs, Some("code"))
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("unsupported regex pattern"));
+        assert!(message.contains("(?<id>...)"));
+    }
 }
@@ -1,3 +1,4 @@
+use gear_mesh_core::FieldInfo;
 use syn::{Error, Type, spanned::Spanned};
 
 pub fn unsupported_type(ty: &Type) -> Error {
@@ -13,7 +14,23 @@ pub fn unsupported_type(ty: &Type) -> Error {
 pub fn branded_requires_newtype(span: impl Spanned) -> Error {
     Error::new(
         span.span(),
-        "#[gear_mesh(branded)] is only supported on single-field tuple structs\nhelp: use `struct UserId(i32);` or remove the `branded` option",
+        "#[gear_mesh(branded)] is only supported on single-field tuple structs\nhelp: use `struct UserId(i32);` (extra `PhantomData<T>` marker fields are allowed) or remove the `branded` option",
+    )
+}
+
+pub fn phantom_brand_requires_transparent(span: impl Spanned) -> Error {
+    Error::new(
+        span.span(),
+        "a branded struct with `PhantomData` fields must be `#[serde(transparent)]`\nhelp: add `#[serde(transparent)]` so it serializes as the inner value, like the generated `Brand` type",
+    )
+}
+
+pub fn literal_type_mismatch(span: impl Spanned, option: &str, expected: &str) -> Error {
+    Error::new(
+        span.span(),
+        format!(
+            "`{option}` expects a {expected} field\nhelp: change the field type or use a {expected} value in `#[gear_mesh({option} = ...)]`"
+        ),
     )
 }
 
@@ -23,3 +40,17 @@ pub fn unsupported_generic_argument(span: impl Spanned) -> Error {
         "unsupported generic argument in #[derive(GearMesh)]\nhelp: only type arguments like `Option<T>` or `HashMap<String, T>` are supported",
     )
 }
+
+pub fn unknown_field_reference(span: impl Spanned, name: &str, known: &[FieldInfo]) -> Error {
+    let known = known
+        .iter()
+        .map(|field| format!("`{}`", field.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Error::new(
+        span.span(),
+        format!(
+            "validation rule references unknown field `{name}`\nhelp: use the Rust field name of a sibling field; available fields are {known}"
+        ),
+    )
+}
@@ -3,12 +3,14 @@
 //! このクレートは `#[derive(GearMesh)]` マクロを提供します。
 
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{quote, quote_spanned};
+use syn::ext::IdentExt;
 use syn::{DeriveInput, parse_macro_input};
 
 mod attributes;
 mod error;
 mod parser;
+mod pattern;
 
 use parser::parse_type;
 
@@ -21,6 +23,10 @@ use parser::parse_type;
 /// - `#[gear_mesh(branded)]`: Branded Typeとして生成
 /// - `#[gear_mesh(validate)]`: バリデーション関数を生成
 /// - `#[gear_mesh(bigint = "auto")]`: BigInt自動変換を有効化
+/// - `#[gear_mesh(bigint = "number" | "string")]`: 64bit整数の表現方法を指定 (フィールドにも指定可)
+/// - `#[gear_mesh(rename = "...", export = false, option_style = "...")]`: 型名・export・`Option<T>` の表現を上書き
+/// - `#[gear_mesh(type = "...", zod = "...", optional, nullable, readonly, skip, rename = "...")]`: フィールドの生成を上書き
+/// - `#[gear_mesh(literal = "v2")]` / `#[gear_mesh(template = "usr_${string}")]`: フィールドをリテラル型・テンプレートリテラル型に固定
 ///
 /// # Example
 ///
@@ -37,7 +43,7 @@ use parser::parse_type;
 ///     name: String,
 /// }
 /// ```
-#[proc_macro_derive(GearMesh, attributes(gear_mesh, validate, serde))]
+#[proc_macro_derive(GearMesh, attributes(gear_mesh, validate, garde, serde))]
 pub fn derive_gear_mesh(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -55,23 +61,56 @@ pub fn derive_gear_mesh(input: TokenStream) -> TokenStream {
                 }
             };
 
+            // `line!()` を各識別子のスパンで展開し、定義の行番号を得る
+            let type_line = quote_spanned!(name.span()=> ::core::line!());
+            let field_lines = match &input.data {
+                syn::Data::Struct(data) => data
+                    .fields
+                    .iter()
+                    .filter_map(|field| field.ident.as_ref())
+                    .map(|ident| {
+                        let field_name = ident.unraw().to_string();
+                        let line = quote_spanned!(ident.span()=> ::core::line!());
+                        quote! { (#field_name, #line) }
+                    })
+                    .collect::<Vec<_>>(),
+                _ => Vec::new(),
+            };
+            let restore = quote! {
+                ::serde_json::from_str::<::gear_mesh::GearMeshType>(#type_json)
+                    .expect("Failed to deserialize GearMeshType")
+                    .with_source(
+                        ::gear_mesh::SourceLocation {
+                            file: ::core::file!().to_string(),
+                            line: #type_line,
+                            module: ::core::module_path!().to_string(),
+                            item: stringify!(#name).to_string(),
+                        },
+                        &[#(#field_lines),*],
+                    )
+            };
+
+            let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+            // `r#type` のような生識別子はserdeと同じく `r#` を除いた名前で登録する
+            let type_name = &gear_mesh_type.name;
             let expanded = quote! {
-                impl ::gear_mesh::GearMeshExport for #name {
+                #[allow(deprecated)]
+                impl #impl_generics ::gear_mesh::GearMeshExport for #name #ty_generics #where_clause {
                     fn gear_mesh_type() -> ::gear_mesh::GearMeshType {
-                        let json = #type_json;
-                        ::serde_json::from_str(json).expect("Failed to deserialize GearMeshType")
+                        #restore
                     }
 
                     fn type_name() -> &'static str {
-                        stringify!(#name)
+                        #type_name
                     }
                 }
 
                 // Register type with inventory for automatic collection
                 ::gear_mesh::inventory::submit! {
                     ::gear_mesh::TypeInfo {
-                        get_type: || <#name as ::gear_mesh::GearMeshExport>::gear_mesh_type(),
-                        type_name: stringify!(#name),
+                        // ジェネリックな型も登録できるよう、トレイトを経由せずに復元する
+                        get_type: || #restore,
+                        type_name: #type_name,
                     }
                 }
             };
@@ -2,29 +2,29 @@
 //!
 //! syn::DeriveInputからGearMeshTypeへ変換します。
 
-use syn::{Data, DeriveInput, Fields, Result, Type};
+use syn::ext::IdentExt;
+use syn::{Attribute, Data, DeriveInput, Fields, Result, Type};
 
 use gear_mesh_core::{
-    DocComment, EnumRepresentation, EnumType, EnumVariant, FieldInfo, GearMeshType, GenericParam,
-    NewtypeType, PrimitiveType, SerdeFieldAttrs, StructType, TypeAttributes, TypeKind, TypeRef,
-    VariantContent,
+    ConditionValue, DocComment, EnumRepresentation, EnumType, EnumVariant, FieldAttributes,
+    FieldInfo, GearMeshType, GenericParam, NewtypeType, PrimitiveType, SerdeFieldAttrs, StructType,
+    TypeAttributes, TypeKind, TypeRef, VariantContent, to_typescript_primitive,
 };
 
 use crate::attributes::{
-    extract_doc_comments, parse_gear_mesh_attrs, parse_serde_rename, parse_validate_attrs,
+    extract_doc_comments, is_doc_hidden, parse_deprecated, parse_field_attrs,
+    parse_gear_mesh_attrs, parse_serde_rename, parse_validate_attrs,
+};
+use crate::error::{
+    branded_requires_newtype, literal_type_mismatch, phantom_brand_requires_transparent,
+    unknown_field_reference, unsupported_generic_argument, unsupported_type,
 };
-use crate::error::{branded_requires_newtype, unsupported_generic_argument, unsupported_type};
 
 /// DeriveInputからGearMeshTypeを生成
 pub fn parse_type(input: &DeriveInput) -> Result<GearMeshType> {
-    let name = input.ident.to_string();
+    let name = input.ident.unraw().to_string();
     let attrs = parse_gear_mesh_attrs(&input.attrs)?;
-    let docs = extract_doc_comments(&input.attrs);
-    let doc_comment = if docs.is_empty() {
-        None
-    } else {
-        Some(DocComment::parse(&docs))
-    };
+    let doc_comment = parse_docs(&input.attrs)?;
 
     let generics = input
         .generics
@@ -40,7 +40,7 @@ pub fn parse_type(input: &DeriveInput) -> Result<GearMeshType> {
         .collect();
 
     let kind = match &input.data {
-        Data::Struct(data) => parse_struct(&data.fields, &attrs)?,
+        Data::Struct(data) => parse_struct(&data.fields, &attrs, &input.generics)?,
         Data::Enum(data) => {
             let variants = data
                 .variants
@@ -49,7 +49,7 @@ pub fn parse_type(input: &DeriveInput) -> Result<GearMeshType> {
                 .collect::<Result<Vec<_>>>()?;
             TypeKind::Enum(EnumType {
                 variants,
-                representation: EnumRepresentation::External, // デフォルト
+                representation: enum_representation(&attrs),
             })
         }
         Data::Union(_) => {
@@ -69,8 +69,26 @@ pub fn parse_type(input: &DeriveInput) -> Result<GearMeshType> {
     })
 }
 
+/// serdeの `tag`・`content`・`untagged` から列挙型の表現を決める
+fn enum_representation(attrs: &TypeAttributes) -> EnumRepresentation {
+    let serde = &attrs.serde;
+    match (&serde.tag, &serde.content) {
+        _ if serde.untagged => EnumRepresentation::Untagged,
+        (Some(tag), Some(content)) => EnumRepresentation::Adjacent {
+            tag: tag.clone(),
+            content: content.clone(),
+        },
+        (Some(tag), None) => EnumRepresentation::Internal { tag: tag.clone() },
+        (None, _) => EnumRepresentation::External,
+    }
+}
+
 /// 構造体フィールドをパース
-fn parse_struct(fields: &Fields, attrs: &TypeAttributes) -> Result<TypeKind> {
+fn parse_struct(
+    fields: &Fields,
+    attrs: &TypeAttributes,
+    generics: &syn::Generics,
+) -> Result<TypeKind> {
     match fields {
         Fields::Named(named) => {
             if attrs.branded {
@@ -81,30 +99,30 @@ fn parse_struct(fields: &Fields, attrs: &TypeAttributes) -> Result<TypeKind> {
                 .named
                 .iter()
                 .map(|f| {
-                    let name = f.ident.as_ref().unwrap().to_string();
+                    let name = f.ident.as_ref().unwrap().unraw().to_string();
                     let ty = parse_type_ref(&f.ty)?;
-                    let docs = extract_doc_comments(&f.attrs);
+                    let docs = parse_docs(&f.attrs)?;
                     let validations = parse_validate_attrs(&f.attrs, Some(&name))?;
                     let rename = parse_serde_rename(&f.attrs);
                     let optional = is_option_type(&f.ty);
+                    let attributes = parse_field_attrs(&f.attrs)?;
+                    check_literal_attrs(f, &ty, &attributes)?;
 
                     Ok(FieldInfo {
                         name,
                         ty,
-                        docs: if docs.is_empty() {
-                            None
-                        } else {
-                            Some(DocComment::parse(&docs))
-                        },
+                        docs,
                         validations,
                         optional,
                         serde_attrs: SerdeFieldAttrs {
                             rename,
                             ..Default::default()
                         },
+                        attributes,
                     })
                 })
                 .collect::<Result<Vec<_>>>()?;
+            check_field_references(named, &field_infos)?;
 
             Ok(TypeKind::Struct(StructType {
                 fields: field_infos,
@@ -112,15 +130,34 @@ fn parse_struct(fields: &Fields, attrs: &TypeAttributes) -> Result<TypeKind> {
         }
         Fields::Unnamed(unnamed) => {
             // タプル構造体
-            if unnamed.unnamed.len() == 1 && attrs.branded {
-                // newtypeパターン（Branded Type）
-                let inner = parse_type_ref(&unnamed.unnamed[0].ty)?;
-                Ok(TypeKind::Newtype(NewtypeType { inner }))
-            } else {
-                if attrs.branded {
+            if attrs.branded {
+                // newtypeパターン（Branded Type）。PhantomData はマーカーとして扱う
+                let (markers, values): (Vec<_>, Vec<_>) =
+                    unnamed.unnamed.iter().partition(|f| is_phantom_data(&f.ty));
+                let [field] = values.as_slice() else {
                     return Err(branded_requires_newtype(unnamed));
+                };
+                // serdeは `transparent` でなければタプル (`[1, null]`) としてシリアライズする
+                if !markers.is_empty() && !attrs.serde.transparent {
+                    return Err(phantom_brand_requires_transparent(unnamed));
                 }
-
+                let inner = parse_type_ref(&field.ty)?;
+                let validations = parse_validate_attrs(&field.attrs, None)?;
+                let markers = generics
+                    .type_params()
+                    .map(|tp| tp.ident.to_string())
+                    .filter(|param| {
+                        markers
+                            .iter()
+                            .any(|f| mentions_ident(quote::ToTokens::to_token_stream(&f.ty), param))
+                    })
+                    .collect();
+                Ok(TypeKind::Newtype(NewtypeType {
+                    inner,
+                    validations,
+                    markers,
+                }))
+            } else {
                 let types = unnamed
                     .unnamed
                     .iter()
@@ -140,8 +177,8 @@ fn parse_struct(fields: &Fields, attrs: &TypeAttributes) -> Result<TypeKind> {
 
 /// 列挙型バリアントをパース
 fn parse_variant(variant: &syn::Variant) -> Result<EnumVariant> {
-    let name = variant.ident.to_string();
-    let docs = extract_doc_comments(&variant.attrs);
+    let name = variant.ident.unraw().to_string();
+    let docs = parse_docs(&variant.attrs)?;
 
     let content = match &variant.fields {
         Fields::Unit => VariantContent::Unit,
@@ -158,29 +195,29 @@ fn parse_variant(variant: &syn::Variant) -> Result<EnumVariant> {
                 .named
                 .iter()
                 .map(|f| -> Result<FieldInfo> {
-                    let field_name = f.ident.as_ref().unwrap().to_string();
+                    let field_name = f.ident.as_ref().unwrap().unraw().to_string();
                     let ty = parse_type_ref(&f.ty)?;
-                    let field_docs = extract_doc_comments(&f.attrs);
+                    let docs = parse_docs(&f.attrs)?;
                     let validations = parse_validate_attrs(&f.attrs, Some(&field_name))?;
                     let rename = parse_serde_rename(&f.attrs);
+                    let attributes = parse_field_attrs(&f.attrs)?;
+                    check_literal_attrs(f, &ty, &attributes)?;
 
                     Ok(FieldInfo {
                         name: field_name,
                         ty,
-                        docs: if field_docs.is_empty() {
-                            None
-                        } else {
-                            Some(DocComment::parse(&field_docs))
-                        },
+                        docs,
                         validations,
                         optional: is_option_type(&f.ty),
                         serde_attrs: SerdeFieldAttrs {
                             rename,
                             ..Default::default()
                         },
+                        attributes,
                     })
                 })
                 .collect::<Result<Vec<_>>>()?;
+            check_field_references(named, &fields)?;
             VariantContent::Struct(fields)
         }
     };
@@ -188,14 +225,44 @@ fn parse_variant(variant: &syn::Variant) -> Result<EnumVariant> {
     Ok(EnumVariant {
         name,
         content,
-        docs: if docs.is_empty() {
-            None
-        } else {
-            Some(DocComment::parse(&docs))
-        },
+        docs,
     })
 }
 
+/// docコメントと `#[doc(hidden)]`・`#[deprecated]` を読み取る
+fn parse_docs(attrs: &[Attribute]) -> Result<Option<DocComment>> {
+    let docs = extract_doc_comments(attrs);
+    let hidden = is_doc_hidden(attrs);
+    let deprecated = parse_deprecated(attrs)?;
+    if docs.is_empty() && !hidden && deprecated.is_none() {
+        return Ok(None);
+    }
+    let mut doc_comment = DocComment::parse(&docs);
+    doc_comment.hidden = hidden;
+    doc_comment.deprecated = deprecated;
+    Ok(Some(doc_comment))
+}
+
+/// フィールド間ルールが存在するフィールドだけを参照しているか検査
+fn check_field_references(named: &syn::FieldsNamed, fields: &[FieldInfo]) -> Result<()> {
+    for (syn_field, field) in named.named.iter().zip(fields) {
+        for rule in &field.validations {
+            if let Some(unknown) = rule
+                .referenced_fields()
+                .into_iter()
+                .find(|name| !fields.iter().any(|candidate| candidate.name == *name))
+            {
+                return Err(unknown_field_reference(
+                    syn_field.ident.as_ref().unwrap(),
+                    unknown,
+                    fields,
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
 /// syn::TypeからTypeRefへ変換
 fn parse_type_ref(ty: &Type) -> Result<TypeRef> {
     match ty {
@@ -207,7 +274,7 @@ fn parse_type_ref(ty: &Type) -> Result<TypeRef> {
             }
 
             let last = segments.last().unwrap();
-            let name = last.ident.to_string();
+            let name = last.ident.unraw().to_string();
 
             // ジェネリクス引数を処理
             let generics = match &last.arguments {
@@ -262,10 +329,53 @@ fn is_option_type(ty: &Type) -> bool {
     false
 }
 
+/// `literal`・`template` がフィールドの型と合っているか検査
+fn check_literal_attrs(field: &syn::Field, ty: &TypeRef, attrs: &FieldAttributes) -> Result<()> {
+    let inner = match ty.name.as_str() {
+        "Option" => ty.generics.first().unwrap_or(ty),
+        _ => ty,
+    };
+    let actual = to_typescript_primitive(&inner.name, false);
+    let expected = match (&attrs.literal, &attrs.template) {
+        (Some(ConditionValue::String(_)), _) | (_, Some(_)) => "string",
+        (Some(ConditionValue::Number(_)), _) => "number",
+        (Some(ConditionValue::Bool(_)), _) => "boolean",
+        (None, None) => return Ok(()),
+    };
+    if actual != Some(expected) {
+        let option = if attrs.template.is_some() {
+            "template"
+        } else {
+            "literal"
+        };
+        return Err(literal_type_mismatch(&field.ty, option, expected));
+    }
+    Ok(())
+}
+
+/// `PhantomData<T>` かどうか
+fn is_phantom_data(ty: &Type) -> bool {
+    if let Type::Path(path) = ty
+        && let Some(segment) = path.path.segments.last()
+    {
+        return segment.ident == "PhantomData";
+    }
+    false
+}
+
+/// トークン列に識別子が含まれるか
+fn mentions_ident(tokens: proc_macro2::TokenStream, ident: &str) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(found) => found == ident,
+        proc_macro2::TokenTree::Group(group) => mentions_ident(group.stream(), ident),
+        _ => false,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use gear_mesh_core::{RenameRule, TypeKind};
+    use gear_mesh_core::{RenameRule, TypeKind, ValidationRule};
     use syn::parse_quote;
 
     #[test]
@@ -337,4 +447,77 @@ mod tests {
         };
         assert_eq!(fields[0].serde_attrs.rename.as_deref(), Some("user-id"));
     }
+
+    #[test]
+    fn test_branded_newtype_keeps_inner_validation_rules() {
+        let input: DeriveInput = parse_quote! {
+            #[gear_mesh(branded)]
+            struct Email(#[validate(email, length(max = 254))] String);
+        };
+
+        let ty = parse_type(&input).unwrap();
+        let TypeKind::Newtype(newtype) = ty.kind else {
+            panic!("expected newtype");
+        };
+        assert_eq!(newtype.validations.len(), 2);
+        assert!(matches!(newtype.validations[0], ValidationRule::Email));
+    }
+
+    #[test]
+    fn test_branded_newtype_with_phantom_marker() {
+        let input: DeriveInput = parse_quote! {
+            #[gear_mesh(branded)]
+            #[serde(transparent)]
+            struct Id<T>(i64, PhantomData<T>);
+        };
+
+        let ty = parse_type(&input).unwrap();
+        let TypeKind::Newtype(newtype) = ty.kind else {
+            panic!("expected newtype");
+        };
+        assert_eq!(newtype.inner.name, "i64");
+        assert_eq!(newtype.markers, vec!["T".to_string()]);
+    }
+
+    #[test]
+    fn test_phantom_marker_brand_requires_serde_transparent() {
+        let input: DeriveInput = parse_quote! {
+            #[gear_mesh(branded)]
+            struct Id<T>(i64, #[serde(skip)] PhantomData<T>);
+        };
+
+        let message = parse_type(&input).unwrap_err().to_string();
+        assert!(message.contains("must be `#[serde(transparent)]`"));
+        assert!(message.contains("help: add `#[serde(transparent)]`"));
+    }
+
+    #[test]
+    fn test_literal_must_match_field_type() {
+        let input: DeriveInput = parse_quote! {
+            struct Envelope {
+                #[gear_mesh(literal = "v2")]
+                version: u32,
+            }
+        };
+
+        let err = parse_type(&input).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("`literal` expects a string field"));
+    }
+
+    #[test]
+    fn test_conditional_rule_reports_unknown_field() {
+        let input: DeriveInput = parse_quote! {
+            struct Billing {
+                kind: String,
+                #[validate(when(field = "knd", eq = "company", required))]
+                vat_id: Option<String>,
+            }
+        };
+
+        let err = parse_type(&input).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("references unknown field `knd`"));
+        assert!(message.contains("`kind`, `vat_id`"));
+    }
 }
@@ -0,0 +1,430 @@
+//! 正規表現パターンの検査
+//!
+//! `pattern` ルールはRustの`regex`クレートとJavaScriptの正規表現の両方で評価されるため、
+//! 両者で同じ意味になる構文だけを受け付けます。`\d`・`\w`・`\s`・`\b` はRust側でも
+//! JavaScriptと同じ文字集合として評価されます (`gear_mesh_core::rust_regex_pattern`)。
+
+use regex_syntax::ast::{
+    self, Assertion, AssertionKind, Ast, ClassSet, ClassSetItem, ClassUnicode, ClassUnicodeKind,
+    ClassUnicodeOpKind, Flag, FlagsItemKind, GroupKind, HexLiteralKind, Literal, LiteralKind,
+    SpecialLiteralKind,
+};
+
+/// JavaScriptで名前だけで指定できるUnicodeプロパティ (一般カテゴリと主な二値プロパティ)
+const JS_PROPERTY_NAMES: &[&str] = &[
+    "C",
+    "Cc",
+    "Cf",
+    "Cn",
+    "Co",
+    "Cs",
+    "L",
+    "LC",
+    "Ll",
+    "Lm",
+    "Lo",
+    "Lt",
+    "Lu",
+    "M",
+    "Mc",
+    "Me",
+    "Mn",
+    "N",
+    "Nd",
+    "Nl",
+    "No",
+    "P",
+    "Pc",
+    "Pd",
+    "Pe",
+    "Pf",
+    "Pi",
+    "Po",
+    "Ps",
+    "S",
+    "Sc",
+    "Sk",
+    "Sm",
+    "So",
+    "Z",
+    "Zl",
+    "Zp",
+    "Zs",
+    "Other",
+    "Control",
+    "Format",
+    "Unassigned",
+    "Private_Use",
+    "Surrogate",
+    "Letter",
+    "Cased_Letter",
+    "Lowercase_Letter",
+    "Modifier_Letter",
+    "Other_Letter",
+    "Titlecase_Letter",
+    "Uppercase_Letter",
+    "Mark",
+    "Spacing_Mark",
+    "Enclosing_Mark",
+    "Nonspacing_Mark",
+    "Number",
+    "Decimal_Number",
+    "Letter_Number",
+    "Other_Number",
+    "Punctuation",
+    "Connector_Punctuation",
+    "Dash_Punctuation",
+    "Close_Punctuation",
+    "Final_Punctuation",
+    "Initial_Punctuation",
+    "Other_Punctuation",
+    "Open_Punctuation",
+    "Symbol",
+    "Currency_Symbol",
+    "Modifier_Symbol",
+    "Math_Symbol",
+    "Other_Symbol",
+    "Separator",
+    "Line_Separator",
+    "Paragraph_Separator",
+    "Space_Separator",
+    "ASCII",
+    "ASCII_Hex_Digit",
+    "Alphabetic",
+    "Any",
+    "Assigned",
+    "Cased",
+    "Dash",
+    "Diacritic",
+    "Emoji",
+    "Emoji_Component",
+    "Emoji_Modifier",
+    "Emoji_Presentation",
+    "Extended_Pictographic",
+    "Hex_Digit",
+    "Ideographic",
+    "Lowercase",
+    "Math",
+    "Uppercase",
+    "White_Space",
+];
+
+/// JavaScriptで `name=value` 形式に使えるプロパティ名
+const JS_PROPERTY_KEYS: &[&str] = &[
+    "General_Category",
+    "gc",
+    "Script",
+    "sc",
+    "Script_Extensions",
+    "scx",
+];
+
+/// パターンがRustとJavaScriptで同じように解釈できるか検査
+///
+/// 問題がある場合は `help:` に続けて表示する説明を返します。
+pub fn check_pattern(pattern: &str) -> Result<(), String> {
+    let ast = ast::parse::Parser::new()
+        .parse(pattern)
+        .map_err(|err| format!("the pattern is not a valid Rust regex: {}", err.kind()))?;
+    regex_syntax::hir::translate::Translator::new()
+        .translate(pattern, &ast)
+        .map_err(|err| format!("the pattern is not a valid Rust regex: {}", err.kind()))?;
+
+    let mut checker = Checker { unicode: false };
+    checker.detect_unicode(&ast);
+
+    // 先頭の `(?i)` などはJavaScriptのフラグに変換できる
+    let items = match &ast {
+        Ast::Concat(concat) => concat.asts.iter().collect::<Vec<_>>(),
+        ast => vec![ast],
+    };
+    for (index, item) in items.into_iter().enumerate() {
+        if index == 0
+            && let Ast::Flags(set) = item
+        {
+            check_leading_flags(&set.flags)?;
+            continue;
+        }
+        checker.check(item)?;
+    }
+    Ok(())
+}
+
+fn check_leading_flags(flags: &ast::Flags) -> Result<(), String> {
+    for item in &flags.items {
+        match item.kind {
+            FlagsItemKind::Flag(
+                Flag::CaseInsensitive | Flag::MultiLine | Flag::DotMatchesNewLine,
+            ) => {}
+            _ => {
+                return Err(
+                    "only the leading flags `i`, `m`, and `s` can be expressed in JavaScript; \
+                     remove `x`, `U`, `R`, `u`, and negated flags"
+                        .to_string(),
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+struct Checker {
+    /// JavaScript側で `u` フラグが必要か
+    unicode: bool,
+}
+
+impl Checker {
+    fn detect_unicode(&mut self, ast: &Ast) {
+        match ast {
+            Ast::ClassUnicode(_) => self.unicode = true,
+            Ast::ClassBracketed(class) => self.detect_unicode_set(&class.kind),
+            Ast::Repetition(repetition) => self.detect_unicode(&repetition.ast),
+            Ast::Group(group) => self.detect_unicode(&group.ast),
+            Ast::Alternation(alternation) => alternation
+                .asts
+                .iter()
+                .for_each(|ast| self.detect_unicode(ast)),
+            Ast::Concat(concat) => concat.asts.iter().for_each(|ast| self.detect_unicode(ast)),
+            _ => {}
+        }
+    }
+
+    fn detect_unicode_set(&mut self, set: &ClassSet) {
+        match set {
+            ClassSet::Item(ClassSetItem::Unicode(_)) => self.unicode = true,
+            ClassSet::Item(ClassSetItem::Union(union)) => {
+                if union
+                    .items
+                    .iter()
+                    .any(|item| matches!(item, ClassSetItem::Unicode(_)))
+                {
+                    self.unicode = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check(&self, ast: &Ast) -> Result<(), String> {
+        match ast {
+            Ast::Empty(_) | Ast::Dot(_) | Ast::ClassPerl(_) => Ok(()),
+            Ast::Flags(_) => Err(
+                "inline flags are only supported at the start of the pattern, e.g. `(?i)^abc$`"
+                    .to_string(),
+            ),
+            Ast::Literal(literal) => self.check_literal(literal, false),
+            Ast::Assertion(assertion) => check_assertion(assertion),
+            Ast::ClassUnicode(class) => check_unicode_class(class),
+            Ast::ClassBracketed(class) => self.check_set(&class.kind),
+            Ast::Repetition(repetition) => self.check(&repetition.ast),
+            Ast::Group(group) => {
+                match &group.kind {
+                    GroupKind::CaptureName {
+                        starts_with_p,
+                        name,
+                    } => {
+                        if *starts_with_p {
+                            return Err(format!(
+                                "`(?P<{0}>...)` is not supported in JavaScript; write `(?<{0}>...)` instead",
+                                name.name
+                            ));
+                        }
+                        if !is_js_group_name(&name.name) {
+                            return Err(format!(
+                                "capture group name `{}` is not a valid JavaScript identifier",
+                                name.name
+                            ));
+                        }
+                    }
+                    GroupKind::NonCapturing(flags) if !flags.items.is_empty() => {
+                        return Err(
+                            "scoped flags such as `(?i:...)` are not supported in JavaScript; \
+                             move the flags to the start of the pattern"
+                                .to_string(),
+                        );
+                    }
+                    _ => {}
+                }
+                self.check(&group.ast)
+            }
+            Ast::Alternation(alternation) => {
+                alternation.asts.iter().try_for_each(|ast| self.check(ast))
+            }
+            Ast::Concat(concat) => concat.asts.iter().try_for_each(|ast| self.check(ast)),
+        }
+    }
+
+    fn check_set(&self, set: &ClassSet) -> Result<(), String> {
+        match set {
+            ClassSet::BinaryOp(_) => Err(
+                "character class set operations (`&&`, `--`, `~~`) are not supported in JavaScript"
+                    .to_string(),
+            ),
+            ClassSet::Item(item) => self.check_set_item(item),
+        }
+    }
+
+    fn check_set_item(&self, item: &ClassSetItem) -> Result<(), String> {
+        match item {
+            ClassSetItem::Empty(_) => Ok(()),
+            // 文字クラス内の `\D` などはRust側でASCIIの範囲に展開できない
+            ClassSetItem::Perl(class) if class.negated => Err(
+                "negated classes such as `\\D`, `\\W`, and `\\S` are not supported inside `[...]`; \
+                 write the negation with `[^...]` instead, e.g. `[^0-9]`"
+                    .to_string(),
+            ),
+            ClassSetItem::Perl(_) => Ok(()),
+            ClassSetItem::Literal(literal) => self.check_literal(literal, true),
+            ClassSetItem::Range(range) => {
+                self.check_literal(&range.start, true)?;
+                self.check_literal(&range.end, true)
+            }
+            ClassSetItem::Ascii(_) => Err(
+                "POSIX classes such as `[[:alpha:]]` are not supported in JavaScript; \
+                 write the range explicitly, e.g. `[a-zA-Z]`"
+                    .to_string(),
+            ),
+            ClassSetItem::Unicode(class) => check_unicode_class(class),
+            ClassSetItem::Bracketed(_) => Err(
+                "nested character classes are not supported in JavaScript; flatten them into one class"
+                    .to_string(),
+            ),
+            ClassSetItem::Union(union) => union
+                .items
+                .iter()
+                .try_for_each(|item| self.check_set_item(item)),
+        }
+    }
+
+    fn check_literal(&self, literal: &Literal, in_class: bool) -> Result<(), String> {
+        match &literal.kind {
+            LiteralKind::Verbatim => Ok(()),
+            // `u` フラグ付きのJavaScriptでは構文文字と `/` 以外のエスケープは使えない
+            LiteralKind::Meta | LiteralKind::Superfluous => {
+                let allowed = !self.unicode
+                    || "^$\\.*+?()[]{}|/".contains(literal.c)
+                    || (in_class && literal.c == '-');
+                if allowed {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "`\\{}` is not a valid escape in a Unicode-aware JavaScript regex; remove the backslash",
+                        literal.c
+                    ))
+                }
+            }
+            LiteralKind::HexFixed(HexLiteralKind::X | HexLiteralKind::UnicodeShort) => Ok(()),
+            LiteralKind::HexFixed(HexLiteralKind::UnicodeLong) | LiteralKind::HexBrace(_) => {
+                Err(
+                    "`\\x{...}` and `\\U...` escapes are not supported in JavaScript; \
+                     use `\\xNN`, `\\uNNNN`, or the literal character"
+                        .to_string(),
+                )
+            }
+            LiteralKind::Octal => {
+                Err("octal escapes are not supported in JavaScript".to_string())
+            }
+            LiteralKind::Special(SpecialLiteralKind::Bell | SpecialLiteralKind::Space) => Err(
+                "`\\a` and escaped spaces are not supported in JavaScript; use `\\x07` or a plain space"
+                    .to_string(),
+            ),
+            LiteralKind::Special(_) => Ok(()),
+        }
+    }
+}
+
+fn check_assertion(assertion: &Assertion) -> Result<(), String> {
+    match assertion.kind {
+        AssertionKind::StartLine
+        | AssertionKind::EndLine
+        | AssertionKind::WordBoundary
+        | AssertionKind::NotWordBoundary => Ok(()),
+        AssertionKind::StartText | AssertionKind::EndText => Err(
+            "`\\A` and `\\z` are not supported in JavaScript; use `^` and `$` without the `m` flag"
+                .to_string(),
+        ),
+        _ => Err(
+            "word boundary variants such as `\\b{start}` or `\\<` are not supported in JavaScript; use `\\b`"
+                .to_string(),
+        ),
+    }
+}
+
+fn check_unicode_class(class: &ClassUnicode) -> Result<(), String> {
+    match &class.kind {
+        ClassUnicodeKind::OneLetter(letter) => Err(format!(
+            "`\\p{letter}` is not supported in JavaScript; write `\\p{{{letter}}}` instead"
+        )),
+        ClassUnicodeKind::Named(name) => {
+            if JS_PROPERTY_NAMES.contains(&name.as_str()) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "`\\p{{{name}}}` is not a canonical JavaScript property name; use a general category \
+                     such as `\\p{{L}}` or a script such as `\\p{{Script=Greek}}`"
+                ))
+            }
+        }
+        ClassUnicodeKind::NamedValue { op, name, .. } => {
+            if !matches!(op, ClassUnicodeOpKind::Equal) {
+                return Err(format!(
+                    "only `\\p{{{name}=...}}` is supported in JavaScript; use `=` and `\\P{{...}}` for negation"
+                ));
+            }
+            if JS_PROPERTY_KEYS.contains(&name.as_str()) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "`{name}` is not a JavaScript property key; use `General_Category`, `Script`, or `Script_Extensions`"
+                ))
+            }
+        }
+    }
+}
+
+fn is_js_group_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first == '_' || first == '$' || first.is_alphabetic())
+        && chars.all(|ch| ch == '_' || ch == '$' || ch.is_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_patterns_shared_by_rust_and_javascript() {
+        for pattern in [
+            r"^[a-z0-9_]+$",
+            r"(?i)^[a-z]+/[0-9]{2,4}$",
+            r"^\p{Script=Greek}+$",
+            r"^[\p{L}\p{N}-]*$",
+            r"^(?<year>\d{4})-(?:\d{2})$",
+            r"^[\w.]+\b[^\s]*$",
+        ] {
+            assert_eq!(check_pattern(pattern), Ok(()), "{pattern}");
+        }
+    }
+
+    #[test]
+    fn rejects_rust_only_syntax() {
+        for (pattern, expected) in [
+            (r"(?x) a b", "leading flags"),
+            (r"a(?i)b", "inline flags"),
+            (r"\p{Greek}", "Script=Greek"),
+            (r"\pL", r"\p{L}"),
+            (r"(?P<id>\d+)", "(?<id>...)"),
+            (r"[[:alpha:]]", "POSIX"),
+            (r"[a-z&&[^aeiou]]", "set operations"),
+            (r"\Aabc\z", r"`\A`"),
+            (r"\p{L}\#", "remove the backslash"),
+            (r"[\Da]", "[^0-9]"),
+            (r"(", "not a valid Rust regex"),
+        ] {
+            let err = check_pattern(pattern).unwrap_err();
+            assert!(err.contains(expected), "{pattern}: {err}");
+        }
+    }
+}
@@ -1,7 +1,18 @@
 //! Helpers for generating Branded Types.
 
-use crate::GeneratorConfig;
-use gear_mesh_core::{GearMeshType, TypeKind};
+use std::collections::HashMap;
+
+use crate::escape::js_string;
+use crate::printer::Printer;
+use crate::ts_ast::{Body, Const, Expr, Function, Item, Param, Stmt, TypeAlias};
+use crate::{BrandConstructorStyle, BrandStyle, GeneratorConfig, QuoteStyle, ValidationGenerator};
+use gear_mesh_core::{
+    BigIntPolicy, GearMeshType, NewtypeType, TypeKind, TypeRef, ValidationMessage, ValidationRule,
+    ValueKind, ZodVersion, interpolate_message, message_key_call,
+};
+
+/// Shared Branded Type utilities (relative to the output directory)
+pub const BRAND_MODULE: &str = "gear-mesh.brand.ts";
 
 /// Generator for Branded Types
 pub struct BrandedTypeGenerator {
@@ -14,47 +25,205 @@ impl BrandedTypeGenerator {
         Self { config }
     }
 
-    /// Generates helper code for Branded Types.
-    pub fn generate_helpers() -> String {
-        r#"// Branded Type utilities
-type Brand<T, B> = T & { readonly __brand: B };
+    /// Generates `gear-mesh.brand.ts`, which declares `Brand` and `isBranded`.
+    ///
+    /// Every generated module imports `Brand` from here, so `export *` in `index.ts`
+    /// never sees the helpers twice.
+    pub fn generate_helpers(&self) -> String {
+        let mut items = vec![Item::Comment("Branded Type utilities".to_string())];
+        let brand = |params: &str, ty: String| {
+            Item::TypeAlias(TypeAlias {
+                export: true,
+                name: "Brand".to_string(),
+                params: params.to_string(),
+                ty: ty.into(),
+            })
+        };
+        match self.config.brand_style {
+            // `.brand()` の推論結果と同じ型にして、パース結果をそのまま代入できるようにする
+            _ if self.config.generate_zod => {
+                items.splice(
+                    0..0,
+                    [Item::import("import type { z } from 'zod';"), Item::Blank],
+                );
+                let zod_brand = match self.config.zod_version {
+                    ZodVersion::V3 => "z.BRAND",
+                    ZodVersion::V4 => "z.$brand",
+                };
+                items.push(brand(
+                    "<T, B extends string>",
+                    format!("T & {zod_brand}<B>"),
+                ));
+            }
+            BrandStyle::Intersection => {
+                items.push(brand("<T, B>", "T & { readonly __brand: B }".to_string()))
+            }
+            BrandStyle::UniqueSymbol => {
+                items.push(Item::Statement(Stmt::expr(
+                    "declare const __brand: unique symbol",
+                )));
+                items.push(brand("<T, B>", "T & { readonly [__brand]: B }".to_string()));
+            }
+        }
+        items.extend([
+            Item::Blank,
+            Item::Comment("Type guard helper".to_string()),
+            Item::Function(Function {
+                export: true,
+                name: "isBranded".to_string(),
+                type_params: "<T, B extends string>".to_string(),
+                params: vec![
+                    Param::new("value", "unknown"),
+                    Param::new("_brand", "B"),
+                    Param::new("typeCheck", "(v: unknown) => v is T"),
+                ],
+                multiline: true,
+                return_type: "value is Brand<T, B>".to_string(),
+                body: vec![Stmt::expr("return typeCheck(value)")],
+            }),
+        ]);
+        Printer::new(&self.config).print(&items)
+    }
 
-// Type guard helper
-export function isBranded<T, B extends string>(
-    value: unknown,
-    brand: B,
-    typeCheck: (v: unknown) => v is T
-): value is Brand<T, B> {
-    return typeCheck(value);
-}
-"#
-        .to_string()
+    /// Generates the type, constructor and `isX` guard of a Branded Type.
+    ///
+    /// When the inner field has validation rules, the constructor checks them and
+    /// reports the first broken rule as configured by `brand_constructor_style`.
+    pub fn generate(&self, ty: &GearMeshType, inner_ts_type: &str) -> Option<String> {
+        let items = self.items(ty, inner_ts_type)?;
+        Some(Printer::new(&self.config).print(&items))
     }
 
-    /// Generates code used for Branded Types from a Type.
-    pub fn generate(ty: &GearMeshType, inner_ts_type: &str) -> Option<String> {
+    pub(crate) fn items(&self, ty: &GearMeshType, inner_ts_type: &str) -> Option<Vec<Item>> {
         if !ty.attributes.branded {
             return None;
         }
+        let TypeKind::Newtype(newtype) = &ty.kind else {
+            return None;
+        };
+        let quote = self.config.format.quote_style;
+
+        let name = &ty.name;
+        let export = ty.attributes.export;
+        let kind = match ty.attributes.bigint_policy().or(self.config.bigint_policy) {
+            Some(policy @ BigIntPolicy::String) if policy.applies_to(&newtype.inner.name) => {
+                ValueKind::IntegerString
+            }
+            _ => ValueKind::of(&newtype.inner),
+        };
+        let rules = newtype
+            .validations
+            .iter()
+            .filter(|rule| {
+                !matches!(
+                    rule.without_message(),
+                    ValidationRule::CrossField { .. } | ValidationRule::Conditional { .. }
+                )
+            })
+            .collect::<Vec<_>>();
 
-        if let TypeKind::Newtype(_) = &ty.kind {
-            let name = &ty.name;
-            Some(format!(
-                r#"export type {name} = Brand<{inner_ts_type}, "{name}">;
-
-export const {name} = (value: {inner_ts_type}): {name} => value as {name};
-
-export function is{name}(value: unknown): value is {name} {{
-    return typeof value === '{ts_typeof}';
-}}
-"#,
-                name = name,
-                inner_ts_type = inner_ts_type,
-                ts_typeof = typescript_typeof(inner_ts_type),
-            ))
+        // `Id<T>` のようなジェネリックなブランドはマーカーごとに別のブランドになる
+        let params = brand_type_params(ty, newtype);
+        let type_name = brand_type_name(ty);
+        let brand = if newtype.is_generic_brand() {
+            let keys = newtype
+                .markers
+                .iter()
+                .map(|marker| format!("${{{marker}}}"))
+                .collect::<Vec<_>>();
+            format!("`{name}<{}>`", keys.join(", "))
+        } else {
+            js_string(name, quote)
+        };
+        // 型をZodスキーマから推論する場合、ブランドはスキーマの `.brand()` が付ける
+        let mut items = Vec::new();
+        if !self.config.infers_types() {
+            items.push(Item::TypeAlias(TypeAlias {
+                export,
+                name: name.clone(),
+                params: params.clone(),
+                ty: format!("Brand<{inner_ts_type}, {brand}>").into(),
+            }));
+        }
+        let constructor = |return_type: &str, body: Body| {
+            Item::Const(Const {
+                export,
+                name: name.clone(),
+                ty: None,
+                value: Expr::arrow(
+                    format!("{params}(value: {inner_ts_type}): {return_type}"),
+                    body,
+                ),
+            })
+        };
+        if rules.is_empty() {
+            items.push(constructor(
+                &type_name,
+                Body::Expr(format!("value as {type_name}").into()),
+            ));
         } else {
-            None
+            let (doc, return_type, success) = match self.config.brand_constructor_style {
+                BrandConstructorStyle::Throw => (
+                    "throwing a `RangeError` if it breaks a validation rule",
+                    type_name.clone(),
+                    format!("value as {type_name}"),
+                ),
+                BrandConstructorStyle::Result => (
+                    "or reports the first validation rule it breaks",
+                    format!("{{ ok: true; value: {type_name} }} | {{ ok: false; error: string }}"),
+                    format!("{{ ok: true, value: value as {type_name} }}"),
+                ),
+            };
+            let mut body = rules
+                .iter()
+                .map(|rule| {
+                    let failure = match self.config.brand_constructor_style {
+                        BrandConstructorStyle::Throw => {
+                            format!("throw new RangeError({})", rule_message(rule, quote))
+                        }
+                        BrandConstructorStyle::Result => {
+                            format!(
+                                "return {{ ok: false, error: {} }}",
+                                rule_message(rule, quote)
+                            )
+                        }
+                    };
+                    Stmt::block(
+                        format!(
+                            "if (!({}))",
+                            rule.check_expression_for("value", kind, quote)
+                        ),
+                        vec![Stmt::expr(failure)],
+                    )
+                })
+                .collect::<Vec<_>>();
+            body.push(Stmt::expr(format!("return {success}")));
+            items.push(Item::Doc(format!(
+                "/** Brands `value` as `{name}`, {doc}. */"
+            )));
+            items.push(constructor(&return_type, Body::Block(body)));
         }
+
+        // ルールの検査は値の型を絞り込める場合だけ行う
+        let (type_check, narrowed) = runtime_check(inner_ts_type, quote);
+        let mut checks = vec![type_check];
+        if narrowed {
+            checks.extend(
+                rules
+                    .iter()
+                    .map(|rule| format!("({})", rule.check_expression_for("value", kind, quote))),
+            );
+        }
+        items.push(Item::Function(Function {
+            export,
+            name: format!("is{name}"),
+            type_params: params,
+            params: vec![Param::new("value", "unknown")],
+            multiline: false,
+            return_type: format!("value is {type_name}"),
+            body: vec![Stmt::expr(format!("return {}", checks.join(" && ")))],
+        }));
+        Some(items)
     }
 
     /// Generates a Zod schema for a Branded Type.
@@ -62,43 +231,106 @@ export function is{name}(value: unknown): value is {name} {{
         if !ty.attributes.branded {
             return None;
         }
+        ValidationGenerator::new(self.config.clone()).generate_zod_schema(ty)
+    }
+}
 
-        if let TypeKind::Newtype(newtype) = &ty.kind {
-            let name = &ty.name;
-            let inner_type = &newtype.inner;
-
-            // 内部型に応じたZodスキーマを生成
-            let base_schema = match inner_type.name.as_str() {
-                "i8" | "i16" | "i32" | "u8" | "u16" | "u32" | "f32" | "f64" => "z.number()",
-                "i64" | "i128" | "u64" | "u128" | "isize" | "usize" => {
-                    if self.config.use_bigint {
-                        "z.bigint()"
-                    } else {
-                        "z.number()"
-                    }
-                }
-                "String" | "str" => "z.string()",
-                "bool" => "z.boolean()",
-                _ => "z.unknown()",
-            };
+/// ジェネリックなブランドの型引数のうち、マーカーである位置を型名ごとに集める
+pub(crate) fn marker_positions(types: &[GearMeshType]) -> HashMap<String, Vec<bool>> {
+    types
+        .iter()
+        .filter_map(|ty| match &ty.kind {
+            TypeKind::Newtype(newtype) if newtype.is_generic_brand() => Some((
+                ty.name.clone(),
+                ty.generics
+                    .iter()
+                    .map(|param| newtype.markers.contains(&param.name))
+                    .collect(),
+            )),
+            _ => None,
+        })
+        .collect()
+}
 
-            Some(format!(
-                r#"export const {}Schema = {}.brand<"{}">();
-"#,
-                name, base_schema, name
-            ))
-        } else {
-            None
+/// 型定義の型引数 (マーカーは `T extends string`)
+pub(crate) fn brand_type_params(ty: &GearMeshType, newtype: &NewtypeType) -> String {
+    if ty.generics.is_empty() {
+        return String::new();
+    }
+    let params = ty
+        .generics
+        .iter()
+        .map(|param| {
+            if newtype.markers.contains(&param.name) {
+                format!("{} extends string", param.name)
+            } else {
+                param.name.clone()
+            }
+        })
+        .collect::<Vec<_>>();
+    format!("<{}>", params.join(", "))
+}
+
+/// 型引数を付けた型名 (`Id<T>`)
+fn brand_type_name(ty: &GearMeshType) -> String {
+    if ty.generics.is_empty() {
+        return ty.name.clone();
+    }
+    let params = ty
+        .generics
+        .iter()
+        .map(|param| param.name.as_str())
+        .collect::<Vec<_>>();
+    format!("{}<{}>", ty.name, params.join(", "))
+}
+
+/// マーカー型をブランドのキーとなる文字列リテラルにする (`User` -> `"User"`)
+pub(crate) fn marker_literal(marker: &TypeRef, quote: QuoteStyle) -> String {
+    fn key(type_ref: &TypeRef) -> String {
+        if type_ref.generics.is_empty() {
+            return type_ref.name.clone();
         }
+        let generics = type_ref.generics.iter().map(key).collect::<Vec<_>>();
+        format!("{}<{}>", type_ref.name, generics.join(", "))
     }
+    js_string(&key(marker), quote)
 }
 
-fn typescript_typeof(ts_type: &str) -> &'static str {
+/// 型ガードで使う実行時の型検査と、それで値の型が絞り込めるか
+fn runtime_check(ts_type: &str, quote: QuoteStyle) -> (String, bool) {
     match ts_type {
-        "number" | "bigint" => "number",
-        "string" => "string",
-        "boolean" => "boolean",
-        _ => "object",
+        "number" | "bigint" | "string" | "boolean" => (
+            format!("typeof value === {}", js_string(ts_type, quote)),
+            true,
+        ),
+        "null" => ("value === null".to_string(), false),
+        _ if ts_type.ends_with("[]") => ("Array.isArray(value)".to_string(), true),
+        _ if ts_type.starts_with("Set<") => ("value instanceof Set".to_string(), true),
+        _ if ts_type.starts_with("Map<") => ("value instanceof Map".to_string(), true),
+        _ => (
+            format!(
+                "typeof value === {} && value !== null",
+                js_string("object", quote)
+            ),
+            false,
+        ),
+    }
+}
+
+/// ルールが破られたときの文言の式
+fn rule_message(rule: &ValidationRule, quote: QuoteStyle) -> String {
+    let inner = rule.without_message();
+    match rule.message() {
+        Some(ValidationMessage::Text(text)) => {
+            js_string(&interpolate_message(text, &inner.message_params()), quote)
+        }
+        Some(ValidationMessage::Key { key, .. }) => {
+            message_key_call(key, &inner.message_params(), quote)
+        }
+        None => js_string(
+            &interpolate_message(&inner.default_message(), &inner.message_params()),
+            quote,
+        ),
     }
 }
 
@@ -107,12 +339,13 @@ mod tests {
     use super::*;
     use gear_mesh_core::{NewtypeType, TypeAttributes, TypeRef};
 
-    #[test]
-    fn test_generate_branded() {
-        let ty = GearMeshType {
-            name: "UserId".to_string(),
+    fn branded(name: &str, inner: &str, validations: Vec<ValidationRule>) -> GearMeshType {
+        GearMeshType {
+            name: name.to_string(),
             kind: TypeKind::Newtype(NewtypeType {
-                inner: TypeRef::new("i32"),
+                inner: TypeRef::new(inner),
+                validations,
+                markers: vec![],
             }),
             docs: None,
             generics: vec![],
@@ -120,28 +353,72 @@ mod tests {
                 branded: true,
                 ..Default::default()
             },
-        };
+        }
+    }
 
-        let output = BrandedTypeGenerator::generate(&ty, "number");
+    #[test]
+    fn test_generate_branded() {
+        let ty = branded("UserId", "i32", vec![]);
+
+        let output = BrandedTypeGenerator::new(GeneratorConfig::new()).generate(&ty, "number");
         assert!(output.is_some());
         let code = output.unwrap();
         assert!(code.contains("export type UserId = Brand<number, \"UserId\">"));
+        assert!(code.contains("export function isUserId(value: unknown): value is UserId {"));
+        assert!(code.contains("return typeof value === \"number\";"));
+    }
+
+    #[test]
+    fn test_validating_constructor_returns_result() {
+        let ty = branded(
+            "Username",
+            "String",
+            vec![ValidationRule::Length {
+                min: Some(3),
+                max: None,
+            }],
+        );
+
+        let config =
+            GeneratorConfig::new().with_brand_constructor_style(BrandConstructorStyle::Result);
+        let code = BrandedTypeGenerator::new(config)
+            .generate(&ty, "string")
+            .unwrap();
+        assert!(code.contains(
+            "(value: string): { ok: true; value: Username } | { ok: false; error: string } => {"
+        ));
+        assert!(code.contains("return { ok: false, error: \"Length must be at least 3\" };"));
+        assert!(code.contains("return typeof value === \"string\" && (value.length >= 3);"));
+    }
+
+    #[test]
+    fn test_unique_symbol_helpers() {
+        let config = GeneratorConfig::new().with_brand_style(BrandStyle::UniqueSymbol);
+        let helpers = BrandedTypeGenerator::new(config).generate_helpers();
+        assert!(helpers.contains("declare const __brand: unique symbol;"));
+        assert!(helpers.contains("export type Brand<T, B> = T & { readonly [__brand]: B };"));
+    }
+
+    #[test]
+    fn test_helpers_use_the_zod_brand() {
+        let config = GeneratorConfig::new()
+            .with_zod(true)
+            .with_brand_style(BrandStyle::UniqueSymbol);
+        let helpers = BrandedTypeGenerator::new(config.clone()).generate_helpers();
+        assert!(
+            helpers.starts_with("import type { z } from 'zod';\n\n// Branded Type utilities\n")
+        );
+        assert!(helpers.contains("export type Brand<T, B extends string> = T & z.BRAND<B>;"));
+        assert!(!helpers.contains("__brand"));
+
+        let helpers =
+            BrandedTypeGenerator::new(config.with_zod_version(ZodVersion::V4)).generate_helpers();
+        assert!(helpers.contains("export type Brand<T, B extends string> = T & z.$brand<B>;"));
     }
 
     #[test]
     fn test_generate_branded_zod_bigint() {
-        let ty = GearMeshType {
-            name: "BigId".to_string(),
-            kind: TypeKind::Newtype(NewtypeType {
-                inner: TypeRef::new("i64"),
-            }),
-            docs: None,
-            generics: vec![],
-            attributes: TypeAttributes {
-                branded: true,
-                ..Default::default()
-            },
-        };
+        let ty = branded("BigId", "i64", vec![]);
 
         let config = GeneratorConfig::new().with_bigint(true);
         let generator = BrandedTypeGenerator::new(config);
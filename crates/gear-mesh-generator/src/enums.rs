@@ -0,0 +1,282 @@
+//! Companion outputs for enums: value lists, TS enums, type guards and match helpers.
+
+use gear_mesh_core::{EnumRepresentation, GenericParam};
+
+use crate::escape::{js_string, member_access, property_key};
+use crate::ts_ast::{
+    Const, Enum, EnumMember, Expr, Function, Item, Member, Param, Property, Stmt, TsType, TypeAlias,
+};
+use crate::{EnumStyle, GeneratorConfig, QuoteStyle};
+
+/// バリアント1つ分の生成結果
+pub(crate) struct VariantShape {
+    /// Rustでの名前 (TS enumのメンバー名・ガード名・matchのキーに使う)
+    pub name: String,
+    /// JSONでの名前
+    pub tag: String,
+    /// ユニオンのメンバーになる型 (1行)
+    pub ts_type: String,
+    /// ユニオンでの宣言 (フィールドのドキュメントがあれば複数行)
+    pub declaration: TsType,
+    /// バリアントのJSDoc
+    pub jsdoc: Option<String>,
+    /// matchのハンドラが受け取る値の型 (ユニットバリアントは `None`)
+    pub payload: Option<String>,
+}
+
+/// 列挙型のコンパニオンを生成する
+pub(crate) struct EnumCompanions<'a> {
+    pub config: &'a GeneratorConfig,
+    pub name: &'a str,
+    pub generics: &'a [GenericParam],
+    pub repr: &'a EnumRepresentation,
+    pub export: bool,
+    pub variants: &'a [VariantShape],
+}
+
+impl EnumCompanions<'_> {
+    /// 外部タグのユニットバリアントだけからなる列挙型か
+    pub fn is_unit_only(&self) -> bool {
+        matches!(self.repr, EnumRepresentation::External)
+            && self.generics.is_empty()
+            && self.variants.iter().all(|v| v.payload.is_none())
+    }
+
+    /// `enum_style` に従った型定義 (ユニオン以外で生成できるときだけ `Some`)
+    pub fn declaration(&self) -> Option<Vec<Item>> {
+        if !self.is_unit_only() {
+            return None;
+        }
+        let (name, export) = (self.name, self.export);
+        match self.config.enum_style {
+            EnumStyle::Union => None,
+            EnumStyle::Enum => Some(vec![Item::Enum(Enum {
+                export,
+                name: name.to_string(),
+                members: self
+                    .variants
+                    .iter()
+                    .map(|variant| EnumMember {
+                        jsdoc: variant.jsdoc.clone(),
+                        name: property_key(&variant.name, self.quote()),
+                        value: js_string(&variant.tag, self.quote()),
+                    })
+                    .collect(),
+            })]),
+            EnumStyle::ConstObject => {
+                let properties = self
+                    .variants
+                    .iter()
+                    .map(|variant| Property {
+                        jsdoc: variant.jsdoc.clone(),
+                        key: property_key(&variant.name, self.quote()),
+                        value: Expr::Str(variant.tag.clone()),
+                    })
+                    .collect();
+                Some(vec![
+                    Item::Const(Const {
+                        export,
+                        name: name.to_string(),
+                        ty: None,
+                        value: Expr::Object(properties).suffixed(" as const"),
+                    }),
+                    Item::TypeAlias(TypeAlias {
+                        export,
+                        name: name.to_string(),
+                        params: String::new(),
+                        ty: format!("(typeof {name})[keyof typeof {name}]").into(),
+                    }),
+                ])
+            }
+        }
+    }
+
+    /// `XValues`: ユニットのみの列挙型の値の一覧
+    pub fn values(&self) -> Option<Item> {
+        if !self.config.generate_enum_values || !self.is_unit_only() {
+            return None;
+        }
+        let name = self.name;
+        let values = self
+            .variants
+            .iter()
+            .map(|variant| match self.config.enum_style {
+                // スキーマから推論した型はTS enumを持たない
+                EnumStyle::Enum if !self.config.infers_types() => {
+                    member_access(name, &variant.name, self.quote()).into()
+                }
+                _ => Expr::Str(variant.tag.clone()),
+            })
+            .collect();
+        Some(Item::Const(Const {
+            export: self.export,
+            name: format!("{name}Values"),
+            ty: None,
+            value: Expr::InlineArray(values).suffixed(" as const"),
+        }))
+    }
+
+    /// データを持つ列挙型か (タグなしはバリアントを判別できないので対象外)
+    fn is_discriminated(&self) -> bool {
+        !matches!(self.repr, EnumRepresentation::Untagged)
+            && self.variants.iter().any(|v| v.payload.is_some())
+    }
+
+    /// バリアントごとの `isEnumVariant` 型ガード (列挙型をまたいで名前が重ならないよう列挙型名を付ける)
+    pub fn guards(&self) -> Option<Vec<Item>> {
+        if !self.config.generate_enum_guards || !self.is_discriminated() {
+            return None;
+        }
+        let params = self.type_params(&[]);
+        let enum_type = self.enum_type();
+        let guards = self
+            .variants
+            .iter()
+            .map(|variant| {
+                Item::Function(Function {
+                    export: self.export,
+                    name: format!("is{}{}", self.name, variant.name),
+                    type_params: params.clone(),
+                    params: vec![Param::new("value", enum_type.clone())],
+                    multiline: false,
+                    return_type: format!("value is {}", variant.ts_type),
+                    body: vec![Stmt::expr(format!("return {}", self.check(variant)))],
+                })
+            })
+            .collect();
+        Some(guards)
+    }
+
+    /// serdeの表現に合わせてバリアントを振り分ける `matchX`
+    pub fn match_helper(&self) -> Vec<Item> {
+        if !self.config.generate_enum_match || !self.is_discriminated() {
+            return Vec::new();
+        }
+        let name = self.name;
+        let result = if self.generics.iter().any(|g| g.name == "R") {
+            "TResult"
+        } else {
+            "R"
+        };
+        let cases = self
+            .variants
+            .iter()
+            .map(|variant| {
+                let arg = match &variant.payload {
+                    Some(payload) => format!("payload: {payload}"),
+                    None => String::new(),
+                };
+                Member::new(
+                    property_key(&variant.name, self.quote()),
+                    format!("({arg}) => {result}"),
+                )
+            })
+            .collect();
+
+        let access = |object: &str, key: &str| member_access(object, key, self.quote());
+        let call = |variant: &VariantShape, payload: &str| match variant.payload {
+            Some(_) => format!("{}({payload})", access("cases", &variant.name)),
+            None => format!("{}()", access("cases", &variant.name)),
+        };
+        let mut body = match self.repr {
+            EnumRepresentation::External => {
+                // 文字列のバリアントを先に除くと、残りはオブジェクトに絞り込まれる
+                let mut ordered = self.variants.iter().collect::<Vec<_>>();
+                ordered.sort_by_key(|variant| variant.payload.is_some());
+                ordered
+                    .into_iter()
+                    .map(|variant| {
+                        Stmt::block(
+                            format!("if ({})", self.check(variant)),
+                            vec![Stmt::expr(format!(
+                                "return {}",
+                                call(variant, &access("value", &variant.tag))
+                            ))],
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            }
+            EnumRepresentation::Internal { tag } | EnumRepresentation::Adjacent { tag, .. } => {
+                let payload = match self.repr {
+                    EnumRepresentation::Adjacent { content, .. } => access("value", content),
+                    _ => "value".to_string(),
+                };
+                let cases = self
+                    .variants
+                    .iter()
+                    .map(|variant| Stmt::Case {
+                        label: js_string(&variant.tag, self.quote()),
+                        body: vec![Stmt::expr(format!("return {}", call(variant, &payload)))],
+                    })
+                    .collect();
+                vec![Stmt::block(
+                    format!("switch ({})", access("value", tag)),
+                    cases,
+                )]
+            }
+            EnumRepresentation::Untagged => unreachable!("untagged enums have no match helper"),
+        };
+        body.push(Stmt::expr(format!(
+            "throw new Error(`Unknown {name} variant: ${{JSON.stringify(value)}}`)"
+        )));
+
+        vec![
+            Item::Doc("/** Calls the handler for the variant of `value`. */".to_string()),
+            Item::Function(Function {
+                export: self.export,
+                name: format!("match{name}"),
+                type_params: self.type_params(&[result]),
+                params: vec![
+                    Param::new("value", self.enum_type()),
+                    Param::new("cases", TsType::Block(cases)),
+                ],
+                multiline: true,
+                return_type: result.to_string(),
+                body,
+            }),
+        ]
+    }
+
+    /// 値が `variant` であるかの検査式
+    fn check(&self, variant: &VariantShape) -> String {
+        let tag = js_string(&variant.tag, self.quote());
+        match self.repr {
+            EnumRepresentation::External => match variant.payload {
+                Some(_) => format!(
+                    "typeof value === {} && {tag} in value",
+                    js_string("object", self.quote())
+                ),
+                None => format!("value === {tag}"),
+            },
+            EnumRepresentation::Internal { tag: key }
+            | EnumRepresentation::Adjacent { tag: key, .. } => {
+                format!("{} === {tag}", member_access("value", key, self.quote()))
+            }
+            EnumRepresentation::Untagged => "false".to_string(),
+        }
+    }
+
+    /// 列挙型の型引数 (`extra` は関数側で追加する型引数)
+    fn type_params(&self, extra: &[&str]) -> String {
+        let params = self
+            .generics
+            .iter()
+            .map(|g| g.name.as_str())
+            .chain(extra.iter().copied())
+            .collect::<Vec<_>>();
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", params.join(", "))
+        }
+    }
+
+    fn quote(&self) -> QuoteStyle {
+        self.config.format.quote_style
+    }
+
+    /// 型引数を付けた列挙型の名前
+    fn enum_type(&self) -> String {
+        format!("{}{}", self.name, self.type_params(&[]))
+    }
+}
@@ -0,0 +1,188 @@
+//! 生成コードに埋め込む文字列リテラルと識別子
+//!
+//! メッセージ・タグ・パターン・バリアント名・リネームしたキーなど、Rust側の値を
+//! TypeScriptに埋め込むときは必ずここを通します。文字列はリテラルとしてエスケープし、
+//! プロパティ名は識別子でなければクォートし、宣言名はTypeScriptの予約語や
+//! 生成コードが参照するグローバルと衝突しないようにします。
+
+pub use gear_mesh_core::js_string;
+
+use crate::QuoteStyle;
+
+/// JavaScriptの予約語 (strictモードとTypeScriptの宣言で使えないものを含む)
+const RESERVED_WORDS: &[&str] = &[
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// 型エイリアスやインターフェースの名前にできない組み込み型
+const BUILTIN_TYPES: &[&str] = &[
+    "any",
+    "bigint",
+    "boolean",
+    "never",
+    "number",
+    "object",
+    "string",
+    "symbol",
+    "undefined",
+    "unknown",
+];
+
+/// 生成コードが参照するグローバルとヘルパー (同名の宣言があると参照が壊れる)
+const RESERVED_GLOBALS: &[&str] = &[
+    "Array",
+    "BigInt",
+    "Boolean",
+    "Brand",
+    "Date",
+    "Equal",
+    "Error",
+    "Expect",
+    "Extract",
+    "JSON",
+    "Map",
+    "Number",
+    "Object",
+    "Promise",
+    "RangeError",
+    "Record",
+    "ReturnType",
+    "Set",
+    "String",
+    "Symbol",
+];
+
+/// 引用符なしでプロパティ名やプロパティ参照に使える名前か
+pub fn is_plain_javascript_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+
+    if !(first.is_ascii_alphabetic() || first == '_' || first == '$') {
+        return false;
+    }
+
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// オブジェクト型・オブジェクトリテラルのキー (`name` / `"user-id"`)
+pub fn format_property_name(name: &str) -> String {
+    property_key(name, QuoteStyle::Mixed)
+}
+
+/// `quote` の引用符でクォートするキー
+pub fn property_key(name: &str, quote: QuoteStyle) -> String {
+    if is_plain_javascript_identifier(name) {
+        name.to_string()
+    } else {
+        js_string(name, quote)
+    }
+}
+
+/// プロパティ参照式 (`value.type` / `value["user-id"]`)
+pub fn member_access(object: &str, key: &str, quote: QuoteStyle) -> String {
+    if is_plain_javascript_identifier(key) {
+        format!("{object}.{key}")
+    } else {
+        format!("{object}[{}]", js_string(key, quote))
+    }
+}
+
+/// 型・値の宣言名に使えない名前か
+pub fn is_reserved_name(name: &str) -> bool {
+    RESERVED_WORDS.contains(&name)
+        || BUILTIN_TYPES.contains(&name)
+        || RESERVED_GLOBALS.contains(&name)
+}
+
+/// 宣言名 (予約語やグローバルと衝突する名前は末尾に `_` を付ける)
+pub fn declaration_name(name: &str) -> String {
+    if is_reserved_name(name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn property_names_are_quoted_as_javascript_strings() {
+        assert_eq!(format_property_name("delete"), "delete");
+        assert_eq!(format_property_name("it's \"bad\""), r#""it's \"bad\"""#);
+        // Rustの `{:?}` と違い、JavaScriptで読めるエスケープにする
+        assert_eq!(format_property_name("a\u{1}b"), r#""a\u0001b""#);
+        assert_eq!(format_property_name("e\u{301}"), "\"e\u{301}\"");
+        assert_eq!(property_key("user-id", QuoteStyle::Single), "'user-id'");
+    }
+
+    #[test]
+    fn member_access_quotes_non_identifiers() {
+        let access = |key| member_access("data", key, QuoteStyle::Mixed);
+        assert_eq!(access("default"), "data.default");
+        assert_eq!(access("user-id"), r#"data["user-id"]"#);
+        assert_eq!(access("a\"b"), r#"data["a\"b"]"#);
+        assert_eq!(
+            member_access("data", "user-id", QuoteStyle::Single),
+            "data['user-id']"
+        );
+    }
+
+    #[test]
+    fn reserved_declaration_names_are_mangled() {
+        assert_eq!(declaration_name("default"), "default_");
+        assert_eq!(declaration_name("string"), "string_");
+        assert_eq!(declaration_name("Record"), "Record_");
+        assert_eq!(declaration_name("Date"), "Date_");
+        assert_eq!(declaration_name("User"), "User");
+        assert_eq!(declaration_name("Records"), "Records");
+    }
+}
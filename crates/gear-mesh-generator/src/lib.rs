@@ -3,32 +3,46 @@
 //! This crate provides TypeScript code generation from Rust types
 //! and serves as the main entry point for the gear-mesh library.
 
+use std::collections::BTreeMap;
 use std::fmt;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use gear_mesh_core::custom_validator_name;
+
 mod branded;
+mod enums;
+mod escape;
+mod messages;
 mod module_organizer;
+mod printer;
+mod sourcemap;
+mod ts_ast;
 mod typescript;
 pub mod utils;
 mod validation_gen;
+mod validators;
 
 #[cfg(test)]
 mod tests;
 
-pub use branded::BrandedTypeGenerator;
+pub use branded::{BRAND_MODULE, BrandedTypeGenerator};
+pub use messages::{LOCALES_DIR, MESSAGES_MODULE, MessageCatalog};
 pub use module_organizer::{ModuleOrganizer, ModuleStrategy};
+pub use sourcemap::{SOURCE_MAP_FILE, SourceMap, SourceMapEntry};
 pub use typescript::TypeScriptGenerator;
-pub use validation_gen::ValidationGenerator;
+pub use validation_gen::{VALIDATION_MODULE, ValidationGenerator};
+pub use validators::VALIDATORS_MODULE;
 
 // ============================================================================
 // Facade: Re-export core types for convenient access
 // ============================================================================
 
 pub use gear_mesh_core::{
-    DocComment, EnumRepresentation, EnumType, EnumVariant, FieldInfo, GearMeshType, GenericParam,
-    NewtypeType, PrimitiveType, SerdeFieldAttrs, StructType, TypeAttributes, TypeKind, TypeRef,
-    TypeTransformer, ValidationRule, VariantContent,
+    BigIntPolicy, DocComment, EnumRepresentation, EnumType, EnumVariant, FieldAttributes,
+    FieldInfo, GearMeshType, GenericParam, NewtypeType, OptionStyle, PrimitiveType, QuoteStyle,
+    SerdeFieldAttrs, SourceLocation, StructType, TypeAttributes, TypeKind, TypeRef,
+    TypeTransformer, ValidationRule, VariantContent, ZodVersion,
 };
 
 // Re-export derive macro
@@ -49,23 +63,6 @@ pub trait GearMeshExport {
 // Generator Configuration
 // ============================================================================
 
-/// TypeScript mapping strategy for `Option<T>`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum OptionStyle {
-    /// Generate `T | null`.
-    Nullable,
-    /// Generate an optional property key with type `T`, and `T | undefined` in nested contexts.
-    Optional,
-    /// Generate an optional property key with type `T | null`, and `T | null | undefined` in nested contexts.
-    Both,
-}
-
-impl Default for OptionStyle {
-    fn default() -> Self {
-        Self::Nullable
-    }
-}
-
 /// TypeScript mapping strategy for `Result<T, E>`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ResultStyle {
@@ -83,25 +80,234 @@ impl Default for ResultStyle {
     }
 }
 
+/// Zod schema strategy for `f32` / `f64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatStyle {
+    /// Generate `z.number()`, which rejects `NaN` but accepts `Infinity`.
+    Number,
+    /// Generate `z.number().finite()`. serde_json writes non-finite floats as `null`, so this matches the JSON data.
+    /// Zod v4 rejects non-finite numbers by default, so this is a plain `z.number()` there.
+    Finite,
+    /// Generate `z.number().or(z.nan())` for in-memory values that may be `NaN`.
+    AllowNaN,
+}
+
+impl Default for FloatStyle {
+    fn default() -> Self {
+        Self::Number
+    }
+}
+
+/// How Branded Types carry their brand.
+///
+/// With Zod enabled, `Brand` is always Zod's own brand type (`z.BRAND` / `z.$brand`),
+/// so `XSchema.parse` returns exactly the declared Branded Type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrandStyle {
+    /// Generate `T & { readonly __brand: B }`.
+    Intersection,
+    /// Generate `T & { readonly [__brand]: B }` keyed by an unexported `unique symbol`, so plain objects cannot forge the brand.
+    UniqueSymbol,
+}
+
+impl Default for BrandStyle {
+    fn default() -> Self {
+        Self::Intersection
+    }
+}
+
+/// How the constructor of a Branded Type with validation rules reports invalid values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrandConstructorStyle {
+    /// Throw a `RangeError` with the rule's message.
+    Throw,
+    /// Return `{ ok: true; value: T } | { ok: false; error: string }`.
+    Result,
+}
+
+impl Default for BrandConstructorStyle {
+    fn default() -> Self {
+        Self::Throw
+    }
+}
+
+/// Where the exported TypeScript types come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeStyle {
+    /// Declare interfaces and type aliases next to the Zod schemas.
+    Declarations,
+    /// Emit only schemas and declare `type User = z.infer<typeof UserSchema>`.
+    Infer,
+    /// Emit only schemas and declare `UserInput = z.input<...>` and `User = z.output<...>`.
+    InputOutput,
+}
+
+impl Default for TypeStyle {
+    fn default() -> Self {
+        Self::Declarations
+    }
+}
+
+/// How unit-only enums are declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumStyle {
+    /// Generate a union of string literals.
+    Union,
+    /// Generate a TypeScript `enum` with string values.
+    Enum,
+    /// Generate an `as const` object and a union type of its values.
+    ConstObject,
+}
+
+impl Default for EnumStyle {
+    fn default() -> Self {
+        Self::Union
+    }
+}
+
+/// Where multi-line lists get a trailing comma, matching Prettier's `trailingComma`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingCommas {
+    /// Never add a trailing comma.
+    None,
+    /// Add trailing commas to objects, arrays, enums and imports.
+    Es5,
+    /// Also add trailing commas to function parameters and call arguments.
+    All,
+}
+
+impl Default for TrailingCommas {
+    fn default() -> Self {
+        Self::All
+    }
+}
+
+/// How unions of type alias members and `z.union([...])` members are laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnionBreak {
+    /// Put every member on its own line.
+    Always,
+    /// Keep the union on one line when it fits in `line_width` and has no documented members.
+    Auto,
+}
+
+impl Default for UnionBreak {
+    fn default() -> Self {
+        Self::Always
+    }
+}
+
+/// Formatting of the generated TypeScript.
+///
+/// The defaults reproduce the built-in layout; set these to match a Prettier config
+/// so the output needs no post-processing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    pub quote_style: QuoteStyle,
+    /// Whether statements and multi-line members end with `;`
+    pub semicolons: bool,
+    pub trailing_commas: TrailingCommas,
+    /// Maximum line width (unions, imports, function signatures, arrays, call arguments and method chains wrap to fit)
+    pub line_width: Option<usize>,
+    pub union_break: UnionBreak,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatOptions {
+    pub fn new() -> Self {
+        Self {
+            quote_style: QuoteStyle::Mixed,
+            semicolons: true,
+            trailing_commas: TrailingCommas::All,
+            line_width: None,
+            union_break: UnionBreak::Always,
+        }
+    }
+
+    pub fn with_quote_style(mut self, quote_style: QuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+
+    /// 文とプロパティの末尾の `;` を出力するか
+    pub fn with_semicolons(mut self, semicolons: bool) -> Self {
+        self.semicolons = semicolons;
+        self
+    }
+
+    pub fn with_trailing_commas(mut self, trailing_commas: TrailingCommas) -> Self {
+        self.trailing_commas = trailing_commas;
+        self
+    }
+
+    /// 行の幅 (Prettierの `printWidth`) を設定
+    pub fn with_line_width(mut self, line_width: usize) -> Self {
+        self.line_width = Some(line_width);
+        self
+    }
+
+    pub fn with_union_break(mut self, union_break: UnionBreak) -> Self {
+        self.union_break = union_break;
+        self
+    }
+}
+
 /// 生成設定
 #[derive(Clone)]
 pub struct GeneratorConfig {
     /// BigIntを自動的に使用するか
     pub use_bigint: bool,
+    /// 64bit整数の表現方法 (型・フィールドの指定が優先。未指定なら `use_bigint` に従う)
+    pub bigint_policy: Option<BigIntPolicy>,
     /// Branded Typeを生成するか
     pub generate_branded: bool,
+    /// Branded Typeのブランドの表現
+    pub brand_style: BrandStyle,
+    /// バリデーションルールを持つBranded Typeのコンストラクタの形式
+    pub brand_constructor_style: BrandConstructorStyle,
     /// バリデーション関数を生成するか
     pub generate_validation: bool,
     /// Zodスキーマを生成するか
     pub generate_zod: bool,
+    /// 型をインターフェースとして宣言するか、Zodスキーマから推論するか
+    pub type_style: TypeStyle,
+    /// 型とZodスキーマの一致を検査する型 (`Expect<Equal<...>>`) を生成するか
+    pub generate_type_assertions: bool,
     /// JSDocを生成するか
     pub generate_jsdoc: bool,
     /// 詳細なJSDocタグを追加するか
     pub enhanced_jsdoc: bool,
     /// `Option<T>` の出力スタイル
     pub option_style: OptionStyle,
+    /// 浮動小数点数のZodスキーマ
+    pub float_style: FloatStyle,
+    /// 出力するZodスキーマのバージョン
+    pub zod_version: ZodVersion,
     /// `Result<T, E>` の出力スタイル
     pub result_style: ResultStyle,
+    /// ユニットのみの列挙型の宣言の形式
+    pub enum_style: EnumStyle,
+    /// ユニットのみの列挙型に `XValues` を生成するか
+    pub generate_enum_values: bool,
+    /// データを持つ列挙型にバリアントごとの型ガードを生成するか
+    pub generate_enum_guards: bool,
+    /// データを持つ列挙型に `matchX` を生成するか
+    pub generate_enum_match: bool,
+    /// `#[deprecated]` の型・フィールド・バリアントを出力しないか
+    pub omit_deprecated: bool,
+    /// JSDocにRustでの定義位置 (`@see src/user.rs:42`) を出力するか
+    pub generate_source_links: bool,
+    /// `gear-mesh.sourcemap.json` を出力するか
+    pub generate_source_map: bool,
+    /// ドキュメントからZodスキーマの `.describe()` を生成するか
+    pub generate_schema_descriptions: bool,
+    /// 説明を `.meta({ ... })` として出力するか (Zod v4)
+    pub generate_schema_meta: bool,
     /// 出力モジュールの構成
     pub module_strategy: ModuleStrategy,
     /// カスタム型変換プラグイン
@@ -112,24 +318,55 @@ pub struct GeneratorConfig {
     pub cache_dir: PathBuf,
     /// インデント文字列
     pub indent: String,
+    /// 引用符・セミコロン・末尾のカンマ・行の幅などの書式
+    pub format: FormatOptions,
+    /// バリデーションメッセージのロケール (先頭が既定のロケール)
+    pub message_locales: Vec<String>,
+    /// カスタムバリデータ名とimport先の対応 (未指定のものは `validators.ts` から読み込む)
+    pub custom_validator_imports: BTreeMap<String, String>,
+    /// 参照されるカスタムバリデータのスタブ (`validators.ts`) を生成するか
+    pub generate_validator_stubs: bool,
 }
 
 impl fmt::Debug for GeneratorConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("GeneratorConfig")
             .field("use_bigint", &self.use_bigint)
+            .field("bigint_policy", &self.bigint_policy)
             .field("generate_branded", &self.generate_branded)
+            .field("brand_style", &self.brand_style)
+            .field("brand_constructor_style", &self.brand_constructor_style)
             .field("generate_validation", &self.generate_validation)
             .field("generate_zod", &self.generate_zod)
+            .field("type_style", &self.type_style)
+            .field("generate_type_assertions", &self.generate_type_assertions)
             .field("generate_jsdoc", &self.generate_jsdoc)
             .field("enhanced_jsdoc", &self.enhanced_jsdoc)
             .field("option_style", &self.option_style)
+            .field("float_style", &self.float_style)
+            .field("zod_version", &self.zod_version)
             .field("result_style", &self.result_style)
+            .field("enum_style", &self.enum_style)
+            .field("generate_enum_values", &self.generate_enum_values)
+            .field("generate_enum_guards", &self.generate_enum_guards)
+            .field("generate_enum_match", &self.generate_enum_match)
+            .field("omit_deprecated", &self.omit_deprecated)
+            .field("generate_source_links", &self.generate_source_links)
+            .field("generate_source_map", &self.generate_source_map)
+            .field(
+                "generate_schema_descriptions",
+                &self.generate_schema_descriptions,
+            )
+            .field("generate_schema_meta", &self.generate_schema_meta)
             .field("module_strategy", &self.module_strategy)
             .field("transformers", &self.transformers.len())
             .field("enable_cache", &self.enable_cache)
             .field("cache_dir", &self.cache_dir)
             .field("indent", &self.indent)
+            .field("format", &self.format)
+            .field("message_locales", &self.message_locales)
+            .field("custom_validator_imports", &self.custom_validator_imports)
+            .field("generate_validator_stubs", &self.generate_validator_stubs)
             .finish()
     }
 }
@@ -144,18 +381,38 @@ impl GeneratorConfig {
     pub fn new() -> Self {
         Self {
             use_bigint: true,
+            bigint_policy: None,
             generate_branded: true,
+            brand_style: BrandStyle::Intersection,
+            brand_constructor_style: BrandConstructorStyle::Throw,
             generate_validation: false,
             generate_zod: false,
+            type_style: TypeStyle::Declarations,
+            generate_type_assertions: false,
             generate_jsdoc: true,
             enhanced_jsdoc: false,
             option_style: OptionStyle::Nullable,
+            float_style: FloatStyle::Number,
+            zod_version: ZodVersion::V3,
             result_style: ResultStyle::OkOnly,
+            enum_style: EnumStyle::Union,
+            generate_enum_values: false,
+            generate_enum_guards: false,
+            generate_enum_match: false,
+            omit_deprecated: false,
+            generate_source_links: false,
+            generate_source_map: false,
+            generate_schema_descriptions: false,
+            generate_schema_meta: false,
             module_strategy: ModuleStrategy::SingleFile,
             transformers: Vec::new(),
             enable_cache: false,
             cache_dir: PathBuf::from(".gear-mesh-cache"),
             indent: "    ".to_string(),
+            format: FormatOptions::new(),
+            message_locales: vec!["en".to_string()],
+            custom_validator_imports: BTreeMap::new(),
+            generate_validator_stubs: false,
         }
     }
 
@@ -164,11 +421,27 @@ impl GeneratorConfig {
         self
     }
 
+    /// 64bit整数の表現方法を設定
+    pub fn with_bigint_policy(mut self, policy: BigIntPolicy) -> Self {
+        self.bigint_policy = Some(policy);
+        self
+    }
+
     pub fn with_branded(mut self, generate: bool) -> Self {
         self.generate_branded = generate;
         self
     }
 
+    pub fn with_brand_style(mut self, brand_style: BrandStyle) -> Self {
+        self.brand_style = brand_style;
+        self
+    }
+
+    pub fn with_brand_constructor_style(mut self, style: BrandConstructorStyle) -> Self {
+        self.brand_constructor_style = style;
+        self
+    }
+
     pub fn with_validation(mut self, generate: bool) -> Self {
         self.generate_validation = generate;
         self
@@ -179,6 +452,32 @@ impl GeneratorConfig {
         self
     }
 
+    /// 型の宣言方法を設定
+    ///
+    /// `TypeStyle::Infer` と `TypeStyle::InputOutput` は型をZodスキーマから推論するため、
+    /// Zodスキーマの生成も有効にします。
+    pub fn with_type_style(mut self, type_style: TypeStyle) -> Self {
+        self.type_style = type_style;
+        if type_style != TypeStyle::Declarations {
+            self.generate_zod = true;
+        }
+        self
+    }
+
+    /// 型ごとに `type _AssertUser = Expect<Equal<User, z.infer<typeof UserSchema>>>` を生成する
+    ///
+    /// インターフェースとZodスキーマが食い違うと `tsc` がエラーになります。
+    /// `with_zod(true)` のときだけ出力されます。
+    pub fn with_type_assertions(mut self, generate: bool) -> Self {
+        self.generate_type_assertions = generate;
+        self
+    }
+
+    /// 型をZodスキーマから推論するか
+    pub(crate) fn infers_types(&self) -> bool {
+        self.type_style != TypeStyle::Declarations
+    }
+
     pub fn with_jsdoc(mut self, generate: bool) -> Self {
         self.generate_jsdoc = generate;
         self
@@ -194,11 +493,77 @@ impl GeneratorConfig {
         self
     }
 
+    pub fn with_float_style(mut self, float_style: FloatStyle) -> Self {
+        self.float_style = float_style;
+        self
+    }
+
+    /// Zod v4の書き方 (`z.email()`, `z.int()`, `z.record(z.string(), V)` など) で出力する
+    pub fn with_zod_version(mut self, zod_version: ZodVersion) -> Self {
+        self.zod_version = zod_version;
+        self
+    }
+
     pub fn with_result_style(mut self, result_style: ResultStyle) -> Self {
         self.result_style = result_style;
         self
     }
 
+    pub fn with_enum_style(mut self, enum_style: EnumStyle) -> Self {
+        self.enum_style = enum_style;
+        self
+    }
+
+    /// ユニットのみの列挙型に `export const XValues = [...] as const` を生成
+    pub fn with_enum_values(mut self, generate: bool) -> Self {
+        self.generate_enum_values = generate;
+        self
+    }
+
+    /// データを持つ列挙型にバリアントごとの `isVariant` 型ガードを生成
+    pub fn with_enum_guards(mut self, generate: bool) -> Self {
+        self.generate_enum_guards = generate;
+        self
+    }
+
+    /// データを持つ列挙型に網羅的な `matchX(value, cases)` を生成
+    pub fn with_enum_match(mut self, generate: bool) -> Self {
+        self.generate_enum_match = generate;
+        self
+    }
+
+    /// `#[deprecated]` の型・フィールド・バリアントを除いた厳格な出力にする
+    pub fn with_omit_deprecated(mut self, omit: bool) -> Self {
+        self.omit_deprecated = omit;
+        self
+    }
+
+    /// 型・フィールドのJSDocに `@see crates/api/src/user.rs:42` を付ける
+    pub fn with_source_links(mut self, generate: bool) -> Self {
+        self.generate_source_links = generate;
+        self
+    }
+
+    /// 宣言とRustの定義の対応表 `gear-mesh.sourcemap.json` を出力する
+    pub fn with_source_map(mut self, generate: bool) -> Self {
+        self.generate_source_map = generate;
+        self
+    }
+
+    /// 型・フィールド・バリアントのZodスキーマにドキュメントのサマリーを `.describe()` で付ける
+    pub fn with_schema_descriptions(mut self, generate: bool) -> Self {
+        self.generate_schema_descriptions = generate;
+        self
+    }
+
+    /// 説明を `.meta({ description, examples, deprecated })` として出力する (Zod v4)
+    ///
+    /// `with_schema_descriptions(true)` も有効にする必要があります。
+    pub fn with_schema_meta(mut self, generate: bool) -> Self {
+        self.generate_schema_meta = generate;
+        self
+    }
+
     pub fn with_module_strategy(mut self, module_strategy: ModuleStrategy) -> Self {
         self.module_strategy = module_strategy;
         self
@@ -226,4 +591,48 @@ impl GeneratorConfig {
         self.cache_dir = cache_dir.into();
         self
     }
+
+    /// インデント文字列を設定 (既定は空白4つ)
+    pub fn with_indent(mut self, indent: impl Into<String>) -> Self {
+        self.indent = indent.into();
+        self
+    }
+
+    /// 出力の書式を設定
+    ///
+    /// Prettierの設定に合わせると、生成後に整形し直す必要がなくなります。
+    pub fn with_format(mut self, format: FormatOptions) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// メッセージカタログを生成するロケールを設定 (先頭が既定のロケール)
+    pub fn with_message_locales<I, S>(mut self, locales: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.message_locales = locales.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// カスタムバリデータのimport先を設定
+    ///
+    /// `name` は `custom = "..."` に書いた名前 (`validateUsername`・`validate_username` でも可)、`specifier` は
+    /// `"@app/validators"` のようなモジュール指定子です。`./` や `../` で始まる指定子は
+    /// 出力ディレクトリからの相対パスとして扱われます。
+    pub fn with_custom_validator_import(
+        mut self,
+        name: impl Into<String>,
+        specifier: impl Into<String>,
+    ) -> Self {
+        self.custom_validator_imports
+            .insert(custom_validator_name(&name.into()), specifier.into());
+        self
+    }
+
+    pub fn with_validator_stubs(mut self, generate: bool) -> Self {
+        self.generate_validator_stubs = generate;
+        self
+    }
 }
@@ -0,0 +1,231 @@
+//! バリデーションメッセージカタログ
+//!
+//! `message_key = "..."` で指定したキーを集め、ロケールごとの文言を実行時に
+//! 切り替える `gear-mesh.messages.ts` を生成します。文言は `locales/<locale>.json`
+//! で管理し、未登録のキーは既定の文言で補われます。
+
+use std::collections::BTreeMap;
+
+use gear_mesh_core::{GearMeshType, TypeKind, ValidationMessage, ValidationRule, VariantContent};
+
+use crate::GeneratorConfig;
+use crate::escape::js_string;
+use crate::printer::Printer;
+use crate::ts_ast::{Const, Expr, Function, Item, Param, Property, Stmt};
+
+/// 生成されるメッセージモジュール (出力ディレクトリからの相対パス)
+pub const MESSAGES_MODULE: &str = "gear-mesh.messages.ts";
+
+/// ロケールごとのJSONファイルを置くディレクトリ (出力ディレクトリからの相対パス)
+pub const LOCALES_DIR: &str = "locales";
+
+/// メッセージキーと既定の文言
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageCatalog {
+    entries: BTreeMap<String, String>,
+}
+
+impl MessageCatalog {
+    /// 型のバリデーションルールからメッセージキーを収集
+    ///
+    /// 同じキーが複数のルールで使われている場合は、最初に見つかった文言を使います。
+    pub fn collect(types: &[GearMeshType]) -> Self {
+        let mut catalog = Self::default();
+        for ty in types {
+            match &ty.kind {
+                TypeKind::Struct(s) => {
+                    for field in s.fields.iter().filter(|field| !field.attributes.skip) {
+                        catalog.add_rules(&field.validations);
+                    }
+                }
+                TypeKind::Enum(e) => {
+                    for variant in &e.variants {
+                        if let VariantContent::Struct(fields) = &variant.content {
+                            for field in fields.iter().filter(|field| !field.attributes.skip) {
+                                catalog.add_rules(&field.validations);
+                            }
+                        }
+                    }
+                }
+                TypeKind::Newtype(newtype) => catalog.add_rules(&newtype.validations),
+                _ => {}
+            }
+        }
+        catalog
+    }
+
+    fn add_rules(&mut self, rules: &[ValidationRule]) {
+        for rule in rules {
+            match rule {
+                ValidationRule::WithMessage {
+                    rule,
+                    message: ValidationMessage::Key { key, default },
+                } => {
+                    self.entries.entry(key.clone()).or_insert_with(|| {
+                        default.clone().unwrap_or_else(|| rule.default_message())
+                    });
+                    self.add_rules(std::slice::from_ref(rule));
+                }
+                ValidationRule::WithMessage { rule, .. }
+                | ValidationRule::Conditional { rule, .. } => {
+                    self.add_rules(std::slice::from_ref(rule))
+                }
+                ValidationRule::Each(rules) => self.add_rules(rules),
+                _ => {}
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// メッセージキーと既定の文言
+    pub fn entries(&self) -> &BTreeMap<String, String> {
+        &self.entries
+    }
+
+    /// ロケールの文言に未登録のキーを既定の文言で追加
+    ///
+    /// 既存の翻訳は変更しません。追加したキーがあれば `true` を返します。
+    pub fn merge_into(&self, translations: &mut BTreeMap<String, String>) -> bool {
+        let mut changed = false;
+        for (key, default) in &self.entries {
+            if !translations.contains_key(key) {
+                translations.insert(key.clone(), default.clone());
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// `gear-mesh.messages.ts` を生成
+    ///
+    /// `locales` は `(ロケール, 文言)` の組で、先頭が既定のロケールです。
+    pub fn render_module(
+        &self,
+        locales: &[(String, BTreeMap<String, String>)],
+        config: &GeneratorConfig,
+    ) -> String {
+        let quote = config.format.quote_style;
+        let default_locale = locales
+            .first()
+            .map(|(locale, _)| locale.as_str())
+            .unwrap_or("en");
+
+        let messages = locales
+            .iter()
+            .map(|(locale, translations)| {
+                Property::new(
+                    js_string(locale, quote),
+                    Expr::Object(
+                        translations
+                            .iter()
+                            .map(|(key, text)| {
+                                Property::new(js_string(key, quote), Expr::Str(text.clone()))
+                            })
+                            .collect(),
+                    ),
+                )
+            })
+            .collect();
+        let constant = |name: &str, ty: Option<&str>, value: Expr| {
+            Item::Const(Const {
+                export: false,
+                name: name.to_string(),
+                ty: ty.map(str::to_string),
+                value,
+            })
+        };
+        let function = |name: &str, params: Vec<Param>, return_type: &str, body: Vec<Stmt>| {
+            Item::Function(Function {
+                export: true,
+                name: name.to_string(),
+                type_params: String::new(),
+                params,
+                multiline: false,
+                return_type: return_type.to_string(),
+                body,
+            })
+        };
+        let params = Param {
+            default: Some("{}".to_string()),
+            ..Param::new("params", "Record<string, string>")
+        };
+
+        let items = [
+            Item::Comment("Auto-generated validation message catalog".to_string()),
+            Item::Comment(format!(
+                "Edit the translations in {LOCALES_DIR}/<locale>.json and regenerate."
+            )),
+            Item::Blank,
+            constant(
+                "messages",
+                Some("Record<string, Record<string, string>>"),
+                Expr::Object(messages),
+            ),
+            Item::Blank,
+            constant(
+                "defaultLocale",
+                None,
+                Expr::Str(default_locale.to_string()),
+            ),
+            Item::Statement(Stmt::expr("let currentLocale = defaultLocale")),
+            Item::Blank,
+            Item::Doc(
+                "/** Sets the locale used to resolve validation messages, e.g. `\"ja\"` or `\"en-US\"`. */"
+                    .to_string(),
+            ),
+            function(
+                "setGearMeshLocale",
+                vec![Param::new("locale", "string")],
+                "void",
+                vec![Stmt::expr("currentLocale = locale")],
+            ),
+            Item::Blank,
+            Item::Doc("/** Returns the locale used to resolve validation messages. */".to_string()),
+            function(
+                "getGearMeshLocale",
+                Vec::new(),
+                "string",
+                vec![Stmt::expr("return currentLocale")],
+            ),
+            Item::Blank,
+            Item::Doc(
+                "/** Resolves a message key for the current locale, falling back to its language and then the default locale. */"
+                    .to_string(),
+            ),
+            function(
+                "gearMeshMessage",
+                vec![Param::new("key", "string"), params],
+                "string",
+                vec![
+                    Stmt::Expr(Expr::Wrap(
+                        "const language = ".to_string(),
+                        Box::new(
+                            Expr::from("currentLocale")
+                                .method("split", vec![Expr::Str("-".to_string())]),
+                        ),
+                        "[0]".to_string(),
+                    )),
+                    Stmt::Expr(Expr::Wrap(
+                        "const template =".to_string(),
+                        Box::new(Expr::Indented(Box::new(
+                            "messages[currentLocale]?.[key] ?? messages[language]?.[key] ?? messages[defaultLocale]?.[key] ?? key".into(),
+                        ))),
+                        String::new(),
+                    )),
+                    Stmt::expr(
+                        "return template.replace(/\\{(\\w+)\\}/g, (placeholder, name: string) => params[name] ?? placeholder)",
+                    ),
+                ],
+            ),
+        ];
+        Printer::new(config).print(&items)
+    }
+}
+
+/// 型がメッセージキーを使うルールを持つか
+pub(crate) fn uses_message_keys(types: &[GearMeshType]) -> bool {
+    !MessageCatalog::collect(types).is_empty()
+}
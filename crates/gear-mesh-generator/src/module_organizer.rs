@@ -2,6 +2,8 @@ use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use gear_mesh_core::{GearMeshType, TypeKind, TypeRef, is_builtin_type, is_internal_type};
 
+use crate::branded::marker_positions;
+
 /// Output organization strategy for generated TypeScript files.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ModuleStrategy {
@@ -27,9 +29,10 @@ pub struct ModuleOrganizer {
 
 impl ModuleOrganizer {
     pub fn new(types: &[GearMeshType]) -> Self {
+        let markers = marker_positions(types);
         let dependency_graph = types
             .iter()
-            .map(|ty| (ty.name.clone(), extract_type_dependencies(ty)))
+            .map(|ty| (ty.name.clone(), extract_type_dependencies(ty, &markers)))
             .collect();
 
         Self { dependency_graph }
@@ -131,13 +134,16 @@ impl ModuleOrganizer {
     }
 }
 
-fn extract_type_dependencies(ty: &GearMeshType) -> BTreeSet<String> {
+pub(crate) fn extract_type_dependencies(
+    ty: &GearMeshType,
+    markers: &HashMap<String, Vec<bool>>,
+) -> BTreeSet<String> {
     let mut deps = BTreeSet::new();
 
     match &ty.kind {
         TypeKind::Struct(s) => {
             for field in &s.fields {
-                collect_type_refs(&field.ty, &mut deps);
+                collect_type_refs(&field.ty, markers, &mut deps);
             }
         }
         TypeKind::Enum(e) => {
@@ -145,32 +151,41 @@ fn extract_type_dependencies(ty: &GearMeshType) -> BTreeSet<String> {
                 match &variant.content {
                     gear_mesh_core::VariantContent::Tuple(types) => {
                         for ty_ref in types {
-                            collect_type_refs(ty_ref, &mut deps);
+                            collect_type_refs(ty_ref, markers, &mut deps);
                         }
                     }
                     gear_mesh_core::VariantContent::Struct(fields) => {
                         for field in fields {
-                            collect_type_refs(&field.ty, &mut deps);
+                            collect_type_refs(&field.ty, markers, &mut deps);
                         }
                     }
                     gear_mesh_core::VariantContent::Unit => {}
                 }
             }
         }
-        TypeKind::Newtype(n) => collect_type_refs(&n.inner, &mut deps),
+        TypeKind::Newtype(n) => collect_type_refs(&n.inner, markers, &mut deps),
         _ => {}
     }
 
     deps
 }
 
-fn collect_type_refs(ty_ref: &TypeRef, deps: &mut BTreeSet<String>) {
+fn collect_type_refs(
+    ty_ref: &TypeRef,
+    markers: &HashMap<String, Vec<bool>>,
+    deps: &mut BTreeSet<String>,
+) {
     if !is_builtin_type(&ty_ref.name) && !is_internal_type(&ty_ref.name) {
         deps.insert(ty_ref.name.clone());
     }
 
-    for generic in &ty_ref.generics {
-        collect_type_refs(generic, deps);
+    // ブランドのマーカーは文字列リテラルとして出力されるので依存にならない
+    let positions = markers.get(&ty_ref.name);
+    for (index, generic) in ty_ref.generics.iter().enumerate() {
+        if positions.is_some_and(|p| p.get(index) == Some(&true)) {
+            continue;
+        }
+        collect_type_refs(generic, markers, deps);
     }
 }
 
@@ -221,7 +236,7 @@ fn file_stem(type_name: &str) -> String {
     out
 }
 
-fn relative_import(from_module: &str, to_module: &str) -> String {
+pub(crate) fn relative_import(from_module: &str, to_module: &str) -> String {
     let from_parts = from_module.split('/').collect::<Vec<_>>();
     let to_parts = to_module.split('/').collect::<Vec<_>>();
 
@@ -291,6 +306,7 @@ mod tests {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 }],
             }),
             docs: None,
@@ -0,0 +1,728 @@
+//! `ts_ast` のモデルを `FormatOptions` に従ってTypeScriptのコードにする
+
+use crate::escape::js_string;
+use crate::ts_ast::{
+    Body, Const, Enum, Expr, Function, Import, Interface, Item, Member, MethodCall, Param,
+    Property, Stmt, TsType, TypeAlias, UnionMember,
+};
+use crate::{FormatOptions, GeneratorConfig, TrailingCommas, UnionBreak};
+
+/// 宣言のプリンタ
+pub(crate) struct Printer<'a> {
+    indent: &'a str,
+    options: &'a FormatOptions,
+}
+
+impl<'a> Printer<'a> {
+    pub fn new(config: &'a GeneratorConfig) -> Self {
+        Self {
+            indent: &config.indent,
+            options: &config.format,
+        }
+    }
+
+    /// 要素を1つずつ改行で終えて出力する
+    pub fn print(&self, items: &[Item]) -> String {
+        let mut out = String::new();
+        for item in items {
+            self.item(&mut out, item);
+            out.push('\n');
+        }
+        out
+    }
+
+    fn item(&self, out: &mut String, item: &Item) {
+        match item {
+            Item::Comment(text) if text.is_empty() => out.push_str("//"),
+            Item::Comment(text) => {
+                out.push_str("// ");
+                out.push_str(text);
+            }
+            Item::Doc(text) | Item::Raw(text) => out.push_str(text),
+            Item::Blank => {}
+            Item::Import(import) => self.import(out, import),
+            Item::Interface(interface) => self.interface(out, interface),
+            Item::TypeAlias(alias) => self.type_alias(out, alias),
+            Item::Enum(enum_decl) => self.enum_decl(out, enum_decl),
+            Item::Const(constant) => self.constant(out, constant),
+            Item::Function(function) => self.function(out, function),
+            Item::Statement(stmt) => {
+                self.stmt(out, stmt, 0);
+                out.pop();
+            }
+        }
+    }
+
+    fn import(&self, out: &mut String, import: &Import) {
+        let keyword = if import.type_only {
+            "import type "
+        } else {
+            "import "
+        };
+        let quote = self.options.quote_style.module_quote();
+        let from = format!(" from {quote}{}{quote}{}", import.module, self.semi());
+        let inline = format!("{keyword}{{ {} }}{from}", import.names.join(", "));
+        if !self.exceeds(inline.len()) {
+            out.push_str(&inline);
+            return;
+        }
+        out.push_str(keyword);
+        out.push_str("{\n");
+        for (index, name) in import.names.iter().enumerate() {
+            out.push_str(self.indent);
+            out.push_str(name);
+            out.push_str(self.comma(index + 1 == import.names.len(), TrailingCommas::Es5));
+            out.push('\n');
+        }
+        out.push('}');
+        out.push_str(&from);
+    }
+
+    fn interface(&self, out: &mut String, interface: &Interface) {
+        out.push_str(&format!(
+            "{}interface {}{} ",
+            export(interface.export),
+            interface.name,
+            interface.params
+        ));
+        self.block_type(out, &interface.members, 0);
+    }
+
+    fn type_alias(&self, out: &mut String, alias: &TypeAlias) {
+        out.push_str(&format!(
+            "{}type {}{} =",
+            export(alias.export),
+            alias.name,
+            alias.params
+        ));
+        match &alias.ty {
+            TsType::Union(members) => self.union(out, members, 0),
+            ty => {
+                out.push(' ');
+                self.ty(out, ty, 0);
+            }
+        }
+        out.push_str(self.semi());
+    }
+
+    fn enum_decl(&self, out: &mut String, enum_decl: &Enum) {
+        out.push_str(&format!(
+            "{}enum {} {{",
+            export(enum_decl.export),
+            enum_decl.name
+        ));
+        if enum_decl.members.is_empty() {
+            out.push('}');
+            return;
+        }
+        out.push('\n');
+        for (index, member) in enum_decl.members.iter().enumerate() {
+            self.doc(out, member.jsdoc.as_deref(), 1);
+            out.push_str(&self.pad(1));
+            out.push_str(&format!("{} = {}", member.name, member.value));
+            out.push_str(self.comma(index + 1 == enum_decl.members.len(), TrailingCommas::Es5));
+            out.push('\n');
+        }
+        out.push('}');
+    }
+
+    fn constant(&self, out: &mut String, constant: &Const) {
+        out.push_str(&format!(
+            "{}const {}",
+            export(constant.export),
+            constant.name
+        ));
+        if let Some(ty) = &constant.ty {
+            out.push_str(": ");
+            out.push_str(ty);
+        }
+        out.push_str(" = ");
+        self.expr(out, &constant.value, 0);
+        out.push_str(self.semi());
+    }
+
+    fn function(&self, out: &mut String, function: &Function) {
+        let head = format!(
+            "{}function {}{}(",
+            export(function.export),
+            function.name,
+            function.type_params
+        );
+        let tail = format!("): {} {{", function.return_type);
+        let params = function
+            .params
+            .iter()
+            .map(|param| {
+                let mut rendered = String::new();
+                self.param(&mut rendered, param, 0);
+                rendered
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let multiline = !function.params.is_empty()
+            && (function.multiline
+                || params.contains('\n')
+                || self.exceeds(head.len() + params.len() + tail.len()));
+
+        out.push_str(&head);
+        if multiline {
+            out.push('\n');
+            for (index, param) in function.params.iter().enumerate() {
+                out.push_str(&self.pad(1));
+                self.param(out, param, 1);
+                out.push_str(self.comma(index + 1 == function.params.len(), TrailingCommas::All));
+                out.push('\n');
+            }
+        } else {
+            out.push_str(&params);
+        }
+        out.push_str(&tail);
+        out.push('\n');
+        self.stmts(out, &function.body, 1);
+        out.push('}');
+    }
+
+    fn param(&self, out: &mut String, param: &Param, depth: usize) {
+        out.push_str(&param.name);
+        out.push_str(": ");
+        self.ty(out, &param.ty, depth);
+        if let Some(default) = &param.default {
+            out.push_str(" = ");
+            out.push_str(default);
+        }
+    }
+
+    fn ty(&self, out: &mut String, ty: &TsType, depth: usize) {
+        match ty {
+            TsType::Raw(raw) => out.push_str(raw),
+            TsType::Inline(members) if members.is_empty() => out.push_str("{}"),
+            TsType::Inline(members) => {
+                out.push_str("{ ");
+                for (index, member) in members.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str("; ");
+                    }
+                    self.member(out, member, depth);
+                }
+                out.push_str(" }");
+            }
+            TsType::Block(members) => self.block_type(out, members, depth),
+            TsType::Union(members) => {
+                let mut union = String::new();
+                self.union(&mut union, members, depth);
+                out.push_str(union.strip_prefix(' ').unwrap_or(&union));
+            }
+            TsType::Or(members) => {
+                for (index, member) in members.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(" | ");
+                    }
+                    self.ty(out, member, depth);
+                }
+            }
+        }
+    }
+
+    fn member(&self, out: &mut String, member: &Member, depth: usize) {
+        if member.readonly {
+            out.push_str("readonly ");
+        }
+        out.push_str(&member.name);
+        if member.optional {
+            out.push('?');
+        }
+        out.push_str(": ");
+        self.ty(out, &member.ty, depth);
+    }
+
+    /// 1行に1プロパティのオブジェクト型 (閉じ括弧は `depth` の位置)
+    fn block_type(&self, out: &mut String, members: &[Member], depth: usize) {
+        if members.is_empty() {
+            out.push_str("{}");
+            return;
+        }
+        out.push_str("{\n");
+        for member in members {
+            self.doc(out, member.jsdoc.as_deref(), depth + 1);
+            out.push_str(&self.pad(depth + 1));
+            self.member(out, member, depth + 1);
+            out.push_str(self.semi());
+            out.push('\n');
+        }
+        out.push_str(&self.pad(depth));
+        out.push('}');
+    }
+
+    /// `=` に続くユニオン (1行なら先頭に空白、複数行なら各メンバーを `| ` で始める)
+    fn union(&self, out: &mut String, members: &[UnionMember], depth: usize) {
+        if members.is_empty() {
+            out.push_str(" never");
+            return;
+        }
+        let inline = members
+            .iter()
+            .map(|member| {
+                let mut rendered = String::new();
+                self.ty(&mut rendered, &member.ty, depth);
+                rendered
+            })
+            .collect::<Vec<_>>()
+            .join(" | ");
+        let single_line =
+            !inline.contains('\n') && members.iter().all(|member| member.jsdoc.is_none());
+        let keep_inline = single_line
+            && match self.options.union_break {
+                UnionBreak::Always => members.len() == 1,
+                UnionBreak::Auto => !self.exceeds(column(out) + 1 + inline.len() + 1),
+            };
+        if keep_inline {
+            out.push(' ');
+            out.push_str(&inline);
+            return;
+        }
+        for member in members {
+            out.push('\n');
+            self.doc(out, member.jsdoc.as_deref(), depth + 1);
+            out.push_str(&self.pad(depth + 1));
+            out.push_str("| ");
+            self.ty(out, &member.ty, depth + 1);
+        }
+    }
+
+    fn expr(&self, out: &mut String, expr: &Expr, depth: usize) {
+        match expr {
+            Expr::Raw(raw) => out.push_str(raw),
+            Expr::Str(value) => out.push_str(&js_string(value, self.options.quote_style)),
+            Expr::Wrap(before, expr, after) => {
+                out.push_str(before);
+                self.expr(out, expr, depth);
+                out.push_str(after);
+            }
+            Expr::Object(properties) if properties.is_empty() => out.push_str("{}"),
+            Expr::Object(properties) => {
+                out.push_str("{\n");
+                for (index, property) in properties.iter().enumerate() {
+                    self.doc(out, property.jsdoc.as_deref(), depth + 1);
+                    out.push_str(&self.pad(depth + 1));
+                    self.property(out, property, depth + 1);
+                    out.push_str(self.comma(index + 1 == properties.len(), TrailingCommas::Es5));
+                    out.push('\n');
+                }
+                out.push_str(&self.pad(depth));
+                out.push('}');
+            }
+            Expr::InlineObject(properties) if properties.is_empty() => out.push_str("{}"),
+            Expr::InlineObject(properties) => {
+                let inline = self.render(out, |rendered| {
+                    rendered.push_str("{ ");
+                    for (index, property) in properties.iter().enumerate() {
+                        if index > 0 {
+                            rendered.push_str(", ");
+                        }
+                        self.property(rendered, property, depth);
+                    }
+                    rendered.push_str(" }");
+                });
+                if self.fits(out, &inline) {
+                    out.push_str(&inline);
+                } else {
+                    self.expr(out, &Expr::Object(properties.clone()), depth);
+                }
+            }
+            Expr::InlineArray(items) => {
+                let inline = self.render(out, |rendered| {
+                    rendered.push('[');
+                    self.args(rendered, items, depth);
+                    rendered.push(']');
+                });
+                if items.is_empty() || self.fits(out, &inline) {
+                    out.push_str(&inline);
+                    return;
+                }
+                out.push_str("[\n");
+                for (index, item) in items.iter().enumerate() {
+                    out.push_str(&self.pad(depth + 1));
+                    self.expr(out, item, depth + 1);
+                    out.push_str(self.comma(index + 1 == items.len(), TrailingCommas::Es5));
+                    out.push('\n');
+                }
+                out.push_str(&self.pad(depth));
+                out.push(']');
+            }
+            Expr::Array(items) if items.is_empty() => out.push_str("[]"),
+            Expr::Array(items) => {
+                if self.options.union_break == UnionBreak::Auto {
+                    let inline = items
+                        .iter()
+                        .map(|item| {
+                            let mut rendered = String::new();
+                            self.expr(&mut rendered, item, depth);
+                            rendered
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    // 閉じ括弧の `])` までが収まれば1行にする
+                    if !inline.contains('\n') && !self.exceeds(column(out) + inline.len() + 4) {
+                        out.push('[');
+                        out.push_str(&inline);
+                        out.push(']');
+                        return;
+                    }
+                }
+                out.push_str("[\n");
+                for (index, item) in items.iter().enumerate() {
+                    out.push_str(&self.pad(depth + 1));
+                    self.expr(out, item, depth + 1);
+                    out.push_str(self.comma(index + 1 == items.len(), TrailingCommas::Es5));
+                    out.push('\n');
+                }
+                out.push_str(&self.pad(depth));
+                out.push(']');
+            }
+            Expr::Call {
+                callee,
+                args,
+                multiline,
+            } => {
+                out.push_str(callee);
+                self.call_args(out, args, *multiline, depth);
+            }
+            Expr::Arrow { signature, body } => {
+                out.push_str(signature);
+                out.push_str(" =>");
+                match body.as_ref() {
+                    Body::Expr(expr @ Expr::Indented(_)) => self.expr(out, expr, depth),
+                    Body::Expr(expr) => {
+                        out.push(' ');
+                        self.expr(out, expr, depth);
+                    }
+                    Body::Block(stmts) => {
+                        out.push_str(" {\n");
+                        self.stmts(out, stmts, depth + 1);
+                        out.push_str(&self.pad(depth));
+                        out.push('}');
+                    }
+                }
+            }
+            Expr::Indented(expr) => {
+                out.truncate(out.trim_end_matches(' ').len());
+                out.push('\n');
+                out.push_str(&self.pad(depth + 1));
+                self.expr(out, expr, depth + 1);
+            }
+            Expr::Chain(receiver, calls) => {
+                let inline = self.render(out, |rendered| {
+                    self.expr(rendered, receiver, depth);
+                    for MethodCall { name, args } in calls {
+                        rendered.push('.');
+                        rendered.push_str(name);
+                        rendered.push('(');
+                        self.args(rendered, args, depth);
+                        rendered.push(')');
+                    }
+                });
+                if self.fits(out, &inline) {
+                    out.push_str(&inline);
+                    return;
+                }
+                // 収まらなければPrettierのように1行に1メソッドずつ並べる
+                let mut receiver = receiver.as_ref();
+                let mut chained = vec![calls];
+                while let Expr::Chain(inner, calls) = receiver {
+                    receiver = inner;
+                    chained.push(calls);
+                }
+                self.expr(out, receiver, depth);
+                for MethodCall { name, args } in chained.into_iter().rev().flatten() {
+                    out.push('\n');
+                    out.push_str(&self.pad(depth + 1));
+                    out.push('.');
+                    out.push_str(name);
+                    self.call_args(out, args, false, depth + 1);
+                }
+            }
+        }
+    }
+
+    /// `(a, b)` (`multiline` か行に収まらなければ1行に1引数)
+    fn call_args(&self, out: &mut String, args: &[Expr], multiline: bool, depth: usize) {
+        if !multiline || args.is_empty() {
+            let inline = self.render(out, |rendered| {
+                rendered.push('(');
+                self.args(rendered, args, depth);
+                rendered.push(')');
+            });
+            if args.is_empty() || self.fits(out, &inline) {
+                out.push_str(&inline);
+                return;
+            }
+        }
+        out.push_str("(\n");
+        for (index, arg) in args.iter().enumerate() {
+            out.push_str(&self.pad(depth + 1));
+            self.expr(out, arg, depth + 1);
+            out.push_str(self.comma(index + 1 == args.len(), TrailingCommas::All));
+            out.push('\n');
+        }
+        out.push_str(&self.pad(depth));
+        out.push(')');
+    }
+
+    /// `out` の続きとして書いた結果 (桁を判定できるよう書きかけの行から書く)
+    fn render(&self, out: &str, write: impl FnOnce(&mut String)) -> String {
+        let line = out.rsplit('\n').next().unwrap_or_default();
+        let mut rendered = line.to_string();
+        write(&mut rendered);
+        rendered.split_off(line.len())
+    }
+
+    /// `out` に続けた `rendered` の最初と最後の行が `line_width` に収まるか
+    ///
+    /// 間の行は内側の式がそれぞれ判定済みなので見ない。最後の行は続く `;` や `,` の分も数える。
+    fn fits(&self, out: &str, rendered: &str) -> bool {
+        let first = rendered.split('\n').next().unwrap_or_default();
+        let last = rendered.rsplit('\n').next().unwrap_or_default();
+        let last_width = if rendered.contains('\n') {
+            last.chars().count()
+        } else {
+            column(out) + last.chars().count()
+        };
+        !self.exceeds(column(out) + first.chars().count()) && !self.exceeds(last_width + 1)
+    }
+
+    fn property(&self, out: &mut String, property: &Property, depth: usize) {
+        out.push_str(&property.key);
+        out.push_str(": ");
+        self.expr(out, &property.value, depth);
+    }
+
+    /// 1行に並べる引数や要素 (`a, b`)
+    fn args(&self, out: &mut String, args: &[Expr], depth: usize) {
+        for (index, arg) in args.iter().enumerate() {
+            if index > 0 {
+                out.push_str(", ");
+            }
+            self.expr(out, arg, depth);
+        }
+    }
+
+    fn stmts(&self, out: &mut String, stmts: &[Stmt], depth: usize) {
+        for stmt in stmts {
+            self.stmt(out, stmt, depth);
+        }
+    }
+
+    /// 文を `depth` の位置から書き、改行で終える
+    fn stmt(&self, out: &mut String, stmt: &Stmt, depth: usize) {
+        out.push_str(&self.pad(depth));
+        match stmt {
+            Stmt::Expr(expr) => {
+                self.expr(out, expr, depth);
+                out.push_str(self.semi());
+            }
+            Stmt::Block { head, body } => {
+                out.push_str(head);
+                out.push_str(" {\n");
+                self.stmts(out, body, depth + 1);
+                out.push_str(&self.pad(depth));
+                out.push('}');
+            }
+            Stmt::Case { label, body } => {
+                out.push_str(&format!("case {label}:\n"));
+                self.stmts(out, body, depth + 1);
+                return;
+            }
+        }
+        out.push('\n');
+    }
+
+    /// JSDocの各行を `depth` の位置に書く
+    fn doc(&self, out: &mut String, jsdoc: Option<&str>, depth: usize) {
+        for line in jsdoc.into_iter().flat_map(str::lines) {
+            out.push_str(&self.pad(depth));
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+
+    fn pad(&self, depth: usize) -> String {
+        self.indent.repeat(depth)
+    }
+
+    fn semi(&self) -> &'static str {
+        if self.options.semicolons { ";" } else { "" }
+    }
+
+    /// 複数行のリストの要素の後のカンマ (`minimum` 以上の設定で最後の要素にも付ける)
+    fn comma(&self, last: bool, minimum: TrailingCommas) -> &'static str {
+        let trailing = match self.options.trailing_commas {
+            TrailingCommas::None => false,
+            TrailingCommas::Es5 => minimum == TrailingCommas::Es5,
+            TrailingCommas::All => true,
+        };
+        if !last || trailing { "," } else { "" }
+    }
+
+    fn exceeds(&self, width: usize) -> bool {
+        self.options.line_width.is_some_and(|max| width > max)
+    }
+}
+
+fn export(export: bool) -> &'static str {
+    if export { "export " } else { "" }
+}
+
+/// 書き出し中の行の桁
+fn column(out: &str) -> usize {
+    let line = out.rsplit('\n').next().unwrap_or_default();
+    line.chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::QuoteStyle;
+
+    fn print_with(format: FormatOptions, items: &[Item]) -> String {
+        let config = GeneratorConfig::new().with_indent("  ").with_format(format);
+        Printer::new(&config).print(items)
+    }
+
+    #[test]
+    fn union_breaks_by_line_width() {
+        let alias = |members: &[&str]| {
+            Item::TypeAlias(TypeAlias {
+                export: true,
+                name: "Status".to_string(),
+                params: String::new(),
+                ty: TsType::Union(
+                    members
+                        .iter()
+                        .map(|member| UnionMember {
+                            jsdoc: None,
+                            ty: (*member).into(),
+                        })
+                        .collect(),
+                ),
+            })
+        };
+        let format = FormatOptions::new()
+            .with_union_break(UnionBreak::Auto)
+            .with_line_width(40);
+        assert_eq!(
+            print_with(format.clone(), &[alias(&["\"a\"", "\"b\""])]),
+            "export type Status = \"a\" | \"b\";\n"
+        );
+        assert_eq!(
+            print_with(
+                format,
+                &[alias(&["\"active\"", "\"inactive\"", "\"pending\""])]
+            ),
+            "export type Status =\n  | \"active\"\n  | \"inactive\"\n  | \"pending\";\n"
+        );
+    }
+
+    #[test]
+    fn trailing_commas_and_semicolons_follow_options() {
+        let object = Item::Const(Const {
+            export: false,
+            name: "a".to_string(),
+            ty: None,
+            value: Expr::call(
+                "f",
+                vec![Expr::Object(vec![
+                    Property::new("x", "1"),
+                    Property::new("y", "2"),
+                ])],
+            ),
+        });
+        assert_eq!(
+            print_with(FormatOptions::new(), std::slice::from_ref(&object)),
+            "const a = f({\n  x: 1,\n  y: 2,\n});\n"
+        );
+        assert_eq!(
+            print_with(
+                FormatOptions::new()
+                    .with_semicolons(false)
+                    .with_trailing_commas(TrailingCommas::None),
+                &[object]
+            ),
+            "const a = f({\n  x: 1,\n  y: 2\n})\n"
+        );
+    }
+
+    #[test]
+    fn string_literals_follow_the_quote_style() {
+        let items = [
+            Item::import("import { z } from 'zod';"),
+            Item::Const(Const {
+                export: false,
+                name: "a".to_string(),
+                ty: None,
+                value: Expr::call(
+                    "f",
+                    vec![Expr::Str("x".to_string()), Expr::Str("it's".to_string())],
+                ),
+            }),
+        ];
+        let print =
+            |quote_style| print_with(FormatOptions::new().with_quote_style(quote_style), &items);
+        assert_eq!(
+            print(QuoteStyle::Mixed),
+            "import { z } from 'zod';\nconst a = f(\"x\", \"it's\");\n"
+        );
+        assert_eq!(
+            print(QuoteStyle::Single),
+            "import { z } from 'zod';\nconst a = f('x', \"it's\");\n"
+        );
+        assert_eq!(
+            print(QuoteStyle::Double),
+            "import { z } from \"zod\";\nconst a = f(\"x\", \"it's\");\n"
+        );
+    }
+
+    #[test]
+    fn long_imports_are_wrapped() {
+        let import = Item::import("import { alpha, beta } from './gear-mesh.validation';");
+        assert_eq!(
+            print_with(FormatOptions::new().with_line_width(30), &[import]),
+            "import {\n  alpha,\n  beta,\n} from './gear-mesh.validation';\n"
+        );
+    }
+
+    #[test]
+    fn long_chains_break_one_call_per_line() {
+        let schema = |value: Expr| {
+            Item::Const(Const {
+                export: false,
+                name: "A".to_string(),
+                ty: None,
+                value,
+            })
+        };
+        // 正規表現や文字列の中の括弧・カンマでは分けない
+        let chain = Expr::from("z.string()")
+            .suffixed(".regex(/^[(,/]+$/).min(1, { message: \"a, b)\" })")
+            .method("brand<'A'>", vec![]);
+        assert_eq!(
+            print_with(FormatOptions::new(), &[schema(chain.clone())]),
+            "const A = z.string().regex(/^[(,/]+$/).min(1, { message: \"a, b)\" }).brand<'A'>();\n"
+        );
+        assert_eq!(
+            print_with(FormatOptions::new().with_line_width(30), &[schema(chain)]),
+            "const A = z.string()\n  .regex(/^[(,/]+$/)\n  .min(\n    1,\n    { message: \"a, b)\" },\n  )\n  .brand<'A'>();\n"
+        );
+        let call = Expr::call(
+            "z.enum",
+            vec![Expr::InlineArray(
+                ["Alpha", "Beta", "Gamma"]
+                    .map(|tag| Expr::Str(tag.to_string()))
+                    .to_vec(),
+            )],
+        );
+        assert_eq!(
+            print_with(FormatOptions::new().with_line_width(30), &[schema(call)]),
+            "const A = z.enum([\n  \"Alpha\",\n  \"Beta\",\n  \"Gamma\",\n]);\n"
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Rustの定義への対応表
+//!
+//! 生成されたTypeScriptの宣言から元のRustの型・フィールドを辿れるよう、
+//! `gear-mesh.sourcemap.json` を生成します。
+
+use std::collections::BTreeMap;
+
+use gear_mesh_core::{GearMeshType, SourceLocation, TypeKind};
+
+use crate::QuoteStyle;
+use crate::escape::js_string;
+use crate::utils::resolve_field_name;
+
+/// 生成される対応表 (出力ディレクトリからの相対パス)
+pub const SOURCE_MAP_FILE: &str = "gear-mesh.sourcemap.json";
+
+/// 宣言ごとのRustでの定義位置
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    entries: BTreeMap<String, SourceMapEntry>,
+}
+
+/// 1つの宣言の対応
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceMapEntry {
+    /// 宣言を出力したファイル (出力ディレクトリからの相対パス)
+    pub output: String,
+    /// Rustでの定義位置
+    pub location: SourceLocation,
+    /// プロパティ名とフィールドの行番号
+    pub fields: BTreeMap<String, u32>,
+}
+
+impl SourceMap {
+    /// 定義位置が記録された型を集める
+    ///
+    /// `output` は型を出力したファイルを返します。
+    pub fn collect(types: &[GearMeshType], output: impl Fn(&GearMeshType) -> String) -> Self {
+        let mut map = Self::default();
+        for ty in types {
+            let Some(location) = &ty.attributes.source else {
+                continue;
+            };
+            let fields = match &ty.kind {
+                TypeKind::Struct(s) => s
+                    .fields
+                    .iter()
+                    .filter(|field| !field.attributes.skip)
+                    .filter_map(|field| {
+                        let name = resolve_field_name(field, ty.attributes.serde.rename_all);
+                        Some((name, field.attributes.line?))
+                    })
+                    .collect(),
+                _ => BTreeMap::new(),
+            };
+            map.entries.insert(
+                ty.name.clone(),
+                SourceMapEntry {
+                    output: output(ty),
+                    location: location.clone(),
+                    fields,
+                },
+            );
+        }
+        map
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 宣言名ごとの対応
+    pub fn entries(&self) -> &BTreeMap<String, SourceMapEntry> {
+        &self.entries
+    }
+
+    /// `gear-mesh.sourcemap.json` の内容
+    pub fn render_json(&self) -> String {
+        // `Mixed` の文字列リテラルは常に二重引用符なのでJSONとしても読める
+        let js_string = |value: &str| js_string(value, QuoteStyle::Mixed);
+        let mut output = String::from("{\n  \"version\": 1,\n  \"declarations\": {");
+        for (i, (name, entry)) in self.entries.iter().enumerate() {
+            let location = &entry.location;
+            output.push_str(if i == 0 { "\n" } else { ",\n" });
+            output.push_str(&format!("    {}: {{\n", js_string(name)));
+            output.push_str(&format!(
+                "      \"output\": {},\n",
+                js_string(&entry.output)
+            ));
+            output.push_str(&format!(
+                "      \"rust\": {},\n",
+                js_string(&location.path())
+            ));
+            output.push_str(&format!("      \"file\": {},\n", js_string(&location.file)));
+            output.push_str(&format!("      \"line\": {}", location.line));
+            if !entry.fields.is_empty() {
+                let fields = entry
+                    .fields
+                    .iter()
+                    .map(|(field, line)| format!("        {}: {line}", js_string(field)))
+                    .collect::<Vec<_>>();
+                output.push_str(&format!(
+                    ",\n      \"fields\": {{\n{}\n      }}",
+                    fields.join(",\n")
+                ));
+            }
+            output.push_str("\n    }");
+        }
+        if !self.entries.is_empty() {
+            output.push_str("\n  ");
+        }
+        output.push_str("}\n}\n");
+        output
+    }
+}
@@ -3,13 +3,18 @@
 use std::{fs, path::PathBuf};
 
 use gear_mesh_core::{
-    DocComment, EnumRepresentation, EnumType, EnumVariant, FieldInfo, GearMeshType, NewtypeType,
-    RenameRule, SerdeTypeAttrs, StructType, TypeAttributes, TypeKind, TypeRef, ValidationRule,
-    VariantContent,
+    BigIntPolicy, Condition, ConditionValue, CrossFieldRule, DocComment, EnumRepresentation,
+    EnumType, EnumVariant, FieldAttributes, FieldInfo, GearMeshType, GenericParam, IpVersion,
+    NewtypeType, RenameRule, SerdeTypeAttrs, StructType, TypeAttributes, TypeKind, TypeRef,
+    ValidationMessage, ValidationRule, VariantContent, ZodVersion,
 };
 use pretty_assertions::assert_eq;
 
-use crate::{GeneratorConfig, OptionStyle, ResultStyle, TypeScriptGenerator};
+use crate::{
+    BrandedTypeGenerator, EnumStyle, FloatStyle, FormatOptions, GeneratorConfig, MessageCatalog,
+    OptionStyle, QuoteStyle, ResultStyle, TrailingCommas, TypeScriptGenerator, TypeStyle,
+    UnionBreak, ValidationGenerator,
+};
 
 #[test]
 fn test_generate_enum_with_data() {
@@ -55,6 +60,7 @@ fn test_generate_nested_types() {
                 validations: vec![],
                 optional: false,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -72,6 +78,7 @@ fn test_generate_nested_types() {
                 validations: vec![],
                 optional: false,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -99,6 +106,7 @@ fn test_generate_with_jsdoc_disabled() {
                 validations: vec![],
                 optional: false,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: Some(DocComment::summary("User information")),
@@ -130,6 +138,7 @@ fn test_tuple_type_generation() {
                 validations: vec![],
                 optional: false,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -155,6 +164,7 @@ fn test_option_type_generation() {
                 validations: vec![],
                 optional: true,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -180,6 +190,7 @@ fn test_option_type_generation_with_optional_style() {
                 validations: vec![],
                 optional: true,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -212,6 +223,7 @@ fn test_nested_option_type_generation_with_optional_style_includes_undefined() {
                 validations: vec![],
                 optional: false,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -244,6 +256,7 @@ fn test_nested_option_type_generation_with_both_style_includes_null_and_undefine
                 validations: vec![],
                 optional: false,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -276,6 +289,7 @@ fn test_result_type_generation_with_tagged_union_style() {
                 validations: vec![],
                 optional: false,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -306,6 +320,7 @@ fn test_result_type_generation_with_success_error_style() {
                 validations: vec![],
                 optional: false,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -347,6 +362,8 @@ fn test_branded_type_without_flag() {
         name: "UserId".to_string(),
         kind: TypeKind::Newtype(NewtypeType {
             inner: TypeRef::new("i32"),
+            validations: vec![],
+            markers: vec![],
         }),
         docs: None,
         generics: vec![],
@@ -376,6 +393,7 @@ fn test_zod_schema_generation_for_usize() {
                 validations: vec![],
                 optional: false,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -407,6 +425,7 @@ fn test_zod_schema_generation_no_bigint() {
                 validations: vec![],
                 optional: false,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -443,6 +462,7 @@ fn test_zod_schema_generation_with_validation_on_bigint() {
                 }],
                 optional: false,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -475,6 +495,7 @@ fn test_zod_vec_generation() {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 },
                 FieldInfo {
                     name: "scores".to_string(),
@@ -483,6 +504,7 @@ fn test_zod_vec_generation() {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 },
             ],
         }),
@@ -503,10 +525,10 @@ fn test_zod_vec_generation() {
         output
     );
 
-    // Vec<i32> -> z.array(z.number())
+    // Vec<i32> -> z.array(z.number().int()...)
     assert!(
-        output.contains("scores: z.array(z.number())"),
-        "Vec<i32> should be z.array(z.number()), generated: {}",
+        output.contains("scores: z.array(z.number().int().min(-2147483648).max(2147483647))"),
+        "Vec<i32> should be an array of bounded integers, generated: {}",
         output
     );
 }
@@ -523,6 +545,7 @@ fn test_zod_option_generation_with_optional_style() {
                 validations: vec![],
                 optional: true,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -552,6 +575,7 @@ fn test_zod_option_generation_with_nullable_style_keeps_key_required() {
                 validations: vec![],
                 optional: true,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -582,6 +606,7 @@ fn test_zod_generation_for_internal_types() {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 },
                 FieldInfo {
                     name: "tuple".to_string(),
@@ -593,6 +618,7 @@ fn test_zod_generation_for_internal_types() {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 },
                 FieldInfo {
                     name: "unit".to_string(),
@@ -601,6 +627,7 @@ fn test_zod_generation_for_internal_types() {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 },
             ],
         }),
@@ -613,7 +640,9 @@ fn test_zod_generation_for_internal_types() {
     let output = generator.generate(&[ty]);
 
     assert!(output.contains("array: z.array(z.string())"));
-    assert!(output.contains("tuple: z.tuple([z.number(), z.string()])"));
+    assert!(output.contains(
+        "tuple: z.tuple([z.number().int().min(-2147483648).max(2147483647), z.string()])"
+    ));
     assert!(output.contains("unit: z.null()"));
 }
 
@@ -630,6 +659,7 @@ fn test_pointer_wrapper_type_generation() {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 },
                 FieldInfo {
                     name: "shared".to_string(),
@@ -638,6 +668,7 @@ fn test_pointer_wrapper_type_generation() {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 },
             ],
         }),
@@ -666,6 +697,7 @@ fn test_pointer_wrapper_zod_generation() {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 },
                 FieldInfo {
                     name: "shared".to_string(),
@@ -674,6 +706,7 @@ fn test_pointer_wrapper_zod_generation() {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 },
             ],
         }),
@@ -704,6 +737,7 @@ fn test_zod_result_generation_with_tagged_union_style() {
                 validations: vec![],
                 optional: false,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -738,6 +772,7 @@ fn test_typescript_generation_respects_serde_rename_with_quoted_key() {
                     rename: Some("display-name".to_string()),
                     ..Default::default()
                 },
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -766,6 +801,7 @@ fn test_zod_generation_respects_serde_rename_with_quoted_key() {
                     rename: Some("display-name".to_string()),
                     ..Default::default()
                 },
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -792,6 +828,7 @@ fn test_typescript_generation_respects_serde_rename_all_on_struct_fields() {
                 validations: vec![],
                 optional: false,
                 serde_attrs: Default::default(),
+                attributes: Default::default(),
             }],
         }),
         docs: None,
@@ -824,6 +861,7 @@ fn test_zod_generation_respects_serde_rename_all_with_field_override() {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 },
                 FieldInfo {
                     name: "account_id".to_string(),
@@ -835,6 +873,7 @@ fn test_zod_generation_respects_serde_rename_all_with_field_override() {
                         rename: Some("account-id".to_string()),
                         ..Default::default()
                     },
+                    attributes: Default::default(),
                 },
             ],
         }),
@@ -858,6 +897,45 @@ fn test_zod_generation_respects_serde_rename_all_with_field_override() {
     assert!(output.contains("\"account-id\": z.number()"));
 }
 
+#[test]
+fn test_doc_links_point_to_generated_types() {
+    let user = GearMeshType {
+        name: "User".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![FieldInfo {
+                name: "orders".to_string(),
+                ty: TypeRef::with_generics("Vec", vec![TypeRef::new("Order")]),
+                docs: Some(DocComment::parse("Every [`Order`] placed, see [`Cart`]")),
+                validations: vec![],
+                optional: false,
+                serde_attrs: Default::default(),
+                attributes: Default::default(),
+            }],
+        }),
+        docs: Some(DocComment {
+            hidden: true,
+            ..DocComment::summary("An account")
+        }),
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+    let order = GearMeshType {
+        name: "Order".to_string(),
+        kind: TypeKind::Struct(StructType { fields: vec![] }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes {
+            rename: Some("PurchaseOrder".to_string()),
+            ..Default::default()
+        },
+    };
+
+    let output = TypeScriptGenerator::new(GeneratorConfig::new()).generate(&[user, order]);
+
+    assert!(output.contains("/** Every {@link PurchaseOrder} placed, see `Cart` */"));
+    assert!(output.contains("/**\n * An account\n *\n * @internal\n */"));
+}
+
 #[test]
 fn test_typescript_generation_respects_serde_rename_all_on_enum_variants() {
     let ty = GearMeshType {
@@ -891,7 +969,7 @@ fn test_typescript_generation_respects_serde_rename_all_on_enum_variants() {
     let mut generator = TypeScriptGenerator::new(GeneratorConfig::new());
     let output = generator.generate(&[ty]);
 
-    assert!(output.contains("\"not_found\" | \"server_error\""));
+    assert!(output.contains("    | \"not_found\"\n    | \"server_error\";"));
 }
 
 #[test]
@@ -907,6 +985,7 @@ fn test_snapshot_simple_struct_output() {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 },
                 FieldInfo {
                     name: "name".to_string(),
@@ -915,6 +994,7 @@ fn test_snapshot_simple_struct_output() {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 },
             ],
         }),
@@ -960,148 +1040,2007 @@ fn test_snapshot_enum_output() {
 }
 
 #[test]
-fn test_snapshot_branded_type_output() {
-    let ty = GearMeshType {
-        name: "UserId".to_string(),
-        kind: TypeKind::Newtype(NewtypeType {
-            inner: TypeRef::new("i32"),
+fn test_snapshot_enum_companions() {
+    let variant = |name: &str, content: VariantContent| EnumVariant {
+        name: name.to_string(),
+        content,
+        docs: None,
+    };
+    let user_role = GearMeshType {
+        name: "UserRole".to_string(),
+        kind: TypeKind::Enum(EnumType {
+            variants: vec![
+                variant("Admin", VariantContent::Unit),
+                variant("Member", VariantContent::Unit),
+            ],
+            representation: EnumRepresentation::External,
         }),
         docs: None,
         generics: vec![],
         attributes: TypeAttributes {
-            branded: true,
+            serde: SerdeTypeAttrs {
+                rename_all: Some(RenameRule::Lowercase),
+                ..Default::default()
+            },
             ..Default::default()
         },
     };
+    let api_event = |name: &str, representation: EnumRepresentation| GearMeshType {
+        name: name.to_string(),
+        kind: TypeKind::Enum(EnumType {
+            variants: vec![
+                variant(
+                    "UserCreated",
+                    VariantContent::Struct(vec![FieldInfo {
+                        name: "user_id".to_string(),
+                        ty: TypeRef::new("i32"),
+                        docs: None,
+                        validations: vec![],
+                        optional: false,
+                        serde_attrs: Default::default(),
+                        attributes: Default::default(),
+                    }]),
+                ),
+                variant(
+                    "Renamed",
+                    VariantContent::Tuple(vec![TypeRef::new("Profile")]),
+                ),
+                variant("Ping", VariantContent::Unit),
+            ],
+            representation,
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+    let types = [
+        user_role.clone(),
+        api_event("ApiEvent", EnumRepresentation::External),
+        api_event(
+            "TaggedEvent",
+            EnumRepresentation::Internal {
+                tag: "type".to_string(),
+            },
+        ),
+        api_event(
+            "AdjacentEvent",
+            EnumRepresentation::Adjacent {
+                tag: "t".to_string(),
+                content: "c".to_string(),
+            },
+        ),
+        api_event("UntaggedEvent", EnumRepresentation::Untagged),
+    ];
+
+    let config = GeneratorConfig::new()
+        .with_enum_values(true)
+        .with_enum_guards(true)
+        .with_enum_match(true);
+    let mut output = TypeScriptGenerator::new(config.clone()).generate(&types[..2]);
+    // 同じバリアント名を持つ列挙型を同じモジュールに出力しても、ガードの名前は重ならない
+    output.push_str("\n// Internal / Adjacent / Untagged\n");
+    output.push_str(&TypeScriptGenerator::new(config.clone()).generate(&types[2..]));
+    for name in ["ApiEvent", "TaggedEvent", "AdjacentEvent"] {
+        let guard = format!("export function is{name}UserCreated(");
+        assert_eq!(output.matches(&guard).count(), 1, "{guard}");
+    }
+    for style in [crate::EnumStyle::Enum, crate::EnumStyle::ConstObject] {
+        output.push_str(&format!("\n// {style:?}\n"));
+        output.push_str(
+            &TypeScriptGenerator::new(config.clone().with_enum_style(style))
+                .generate(std::slice::from_ref(&user_role)),
+        );
+    }
 
-    let mut generator = TypeScriptGenerator::new(GeneratorConfig::new());
-    let output = generator.generate(&[ty]);
+    assert_snapshot("enum_companions.snap", &output);
+}
 
-    assert_snapshot("branded_type.snap", &output);
+#[test]
+fn test_snapshot_documented_enum() {
+    let field = |name: &str, ty: TypeRef, docs: &str, validations: Vec<ValidationRule>| FieldInfo {
+        name: name.to_string(),
+        ty,
+        docs: Some(DocComment::summary(docs)),
+        validations,
+        optional: false,
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    let variants = vec![
+        EnumVariant {
+            name: "Pending".to_string(),
+            content: VariantContent::Unit,
+            docs: Some(DocComment::summary("Waiting for payment")),
+        },
+        EnumVariant {
+            name: "Shipped".to_string(),
+            content: VariantContent::Struct(vec![
+                field(
+                    "tracking_number",
+                    TypeRef::new("String"),
+                    "Carrier tracking number",
+                    vec![ValidationRule::Length {
+                        min: Some(1),
+                        max: Some(32),
+                    }],
+                ),
+                field("shipped_at", TypeRef::new("i64"), "Unix time", vec![]),
+            ]),
+            docs: Some(DocComment::summary("Handed over to the carrier")),
+        },
+        EnumVariant {
+            name: "Cancelled".to_string(),
+            content: VariantContent::Struct(vec![FieldInfo {
+                docs: None,
+                ..field("reason", TypeRef::new("String"), "", vec![])
+            }]),
+            docs: None,
+        },
+    ];
+    let ty = |representation: EnumRepresentation| GearMeshType {
+        name: "OrderStatus".to_string(),
+        kind: TypeKind::Enum(EnumType {
+            variants: variants.clone(),
+            representation,
+        }),
+        docs: Some(DocComment::summary("Order state")),
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+
+    let config = GeneratorConfig::new()
+        .with_bigint(false)
+        .with_zod(true)
+        .with_schema_descriptions(true);
+    let mut output = TypeScriptGenerator::new(config.clone().with_enhanced_jsdoc(true))
+        .generate(&[ty(EnumRepresentation::External)]);
+    output.push_str("\n// --- internally tagged ---\n\n");
+    output.push_str(&TypeScriptGenerator::new(config).generate(&[ty(
+        EnumRepresentation::Internal {
+            tag: "status".to_string(),
+        },
+    )]));
+
+    assert_snapshot("documented_enum.snap", &output);
 }
 
 #[test]
-fn test_snapshot_generic_type_output() {
+fn test_snapshot_schema_descriptions() {
+    let field = |name: &str, ty: TypeRef, docs: Option<DocComment>| FieldInfo {
+        name: name.to_string(),
+        optional: ty.name == "Option",
+        ty,
+        docs,
+        validations: vec![],
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
     let ty = GearMeshType {
-        name: "Paginated".to_string(),
+        name: "Product".to_string(),
         kind: TypeKind::Struct(StructType {
             fields: vec![
-                FieldInfo {
-                    name: "items".to_string(),
-                    ty: TypeRef::with_generics("Vec", vec![TypeRef::new("T")]),
-                    docs: None,
-                    validations: vec![],
-                    optional: false,
-                    serde_attrs: Default::default(),
-                },
-                FieldInfo {
-                    name: "next_cursor".to_string(),
-                    ty: TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
-                    docs: None,
-                    validations: vec![],
-                    optional: true,
-                    serde_attrs: Default::default(),
-                },
+                field(
+                    "sku",
+                    TypeRef::new("String"),
+                    Some(DocComment::parse(
+                        "Stock keeping unit, see [`Catalog`]\n\n# Examples\n\n```json\n\"SKU-001\"\n```",
+                    )),
+                ),
+                field(
+                    "legacy_code",
+                    TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+                    Some(DocComment {
+                        deprecated: Some(Default::default()),
+                        ..DocComment::summary("Code from the old system")
+                    }),
+                ),
+                field("price", TypeRef::new("u32"), None),
             ],
         }),
-        docs: None,
-        generics: vec![gear_mesh_core::GenericParam {
-            name: "T".to_string(),
-            bounds: vec![],
-        }],
+        docs: Some(DocComment::summary("A product in the catalog")),
+        generics: vec![],
         attributes: TypeAttributes::default(),
     };
 
-    let mut generator = TypeScriptGenerator::new(GeneratorConfig::new());
-    let output = generator.generate(&[ty]);
+    let config = GeneratorConfig::new()
+        .with_zod(true)
+        .with_jsdoc(false)
+        .with_schema_descriptions(true);
+    let mut output = TypeScriptGenerator::new(config.clone()).generate(std::slice::from_ref(&ty));
+    output.push_str("\n// --- with .meta() ---\n\n");
+    output.push_str(&TypeScriptGenerator::new(config.with_schema_meta(true)).generate(&[ty]));
 
-    assert_snapshot("generic_type.snap", &output);
+    assert_snapshot("schema_descriptions.snap", &output);
 }
 
 #[test]
-fn test_snapshot_validation_and_zod_output() {
-    let ty = GearMeshType {
-        name: "UserInput".to_string(),
+fn test_snapshot_zod_versions() {
+    let field = |name: &str, ty: TypeRef, validations: Vec<ValidationRule>| FieldInfo {
+        name: name.to_string(),
+        optional: ty.name == "Option",
+        ty,
+        docs: None,
+        validations,
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    let string = || TypeRef::new("String");
+    let account = GearMeshType {
+        name: "Account".to_string(),
         kind: TypeKind::Struct(StructType {
             fields: vec![
-                FieldInfo {
-                    name: "name".to_string(),
-                    ty: TypeRef::new("String"),
-                    docs: Some(DocComment::summary("Display name")),
-                    validations: vec![ValidationRule::Length {
-                        min: Some(1),
-                        max: Some(20),
+                field(
+                    "email",
+                    string(),
+                    vec![
+                        ValidationRule::WithMessage {
+                            rule: Box::new(ValidationRule::Email),
+                            message: ValidationMessage::Text("Invalid email".to_string()),
+                        },
+                        ValidationRule::Length {
+                            min: None,
+                            max: Some(254),
+                        },
+                    ],
+                ),
+                field(
+                    "website",
+                    TypeRef::with_generics("Option", vec![string()]),
+                    vec![ValidationRule::Url],
+                ),
+                field("id", string(), vec![ValidationRule::Uuid]),
+                field(
+                    "server",
+                    string(),
+                    vec![ValidationRule::Ip(Some(IpVersion::V4))],
+                ),
+                field("client", string(), vec![ValidationRule::Ip(None)]),
+                field(
+                    "age",
+                    TypeRef::new("u8"),
+                    vec![ValidationRule::Range {
+                        min: Some(18.0),
+                        max: None,
                     }],
-                    optional: false,
-                    serde_attrs: Default::default(),
-                },
-                FieldInfo {
-                    name: "email".to_string(),
-                    ty: TypeRef::new("String"),
-                    docs: Some(DocComment::summary("Email address")),
-                    validations: vec![ValidationRule::Email],
-                    optional: false,
-                    serde_attrs: Default::default(),
-                },
-                FieldInfo {
-                    name: "website".to_string(),
-                    ty: TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
-                    docs: Some(DocComment::summary("Optional website")),
-                    validations: vec![ValidationRule::Url],
-                    optional: true,
-                    serde_attrs: Default::default(),
-                },
+                ),
+                field("visits", TypeRef::new("u64"), vec![]),
+                field("score", TypeRef::new("f64"), vec![]),
+                field(
+                    "labels",
+                    TypeRef::with_generics("HashMap", vec![string(), string()]),
+                    vec![],
+                ),
+                field(
+                    "outcome",
+                    TypeRef::with_generics("Result", vec![string(), string()]),
+                    vec![],
+                ),
+                field("password", string(), vec![]),
+                field(
+                    "confirm",
+                    string(),
+                    vec![ValidationRule::CrossField {
+                        fields: vec!["password".to_string(), "confirm".to_string()],
+                        rule: CrossFieldRule::Match,
+                        message: Some("Passwords do not match".to_string()),
+                        path: None,
+                    }],
+                ),
+                field(
+                    "vat_id",
+                    TypeRef::with_generics("Option", vec![string()]),
+                    vec![ValidationRule::Conditional {
+                        condition: Condition::Present("company".to_string()),
+                        rule: Box::new(ValidationRule::WithMessage {
+                            rule: Box::new(ValidationRule::Required),
+                            message: ValidationMessage::Key {
+                                key: "account.vat_id.required".to_string(),
+                                default: None,
+                            },
+                        }),
+                    }],
+                ),
+                field(
+                    "company",
+                    TypeRef::with_generics("Option", vec![string()]),
+                    vec![],
+                ),
             ],
         }),
-        docs: Some(DocComment::summary("User input payload")),
+        docs: None,
         generics: vec![],
         attributes: TypeAttributes::default(),
     };
+    let user_id = GearMeshType {
+        name: "UserId".to_string(),
+        kind: TypeKind::Newtype(NewtypeType {
+            inner: TypeRef::new("i32"),
+            validations: vec![],
+            markers: vec![],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes {
+            branded: true,
+            ..Default::default()
+        },
+    };
+    let types = [account, user_id];
 
-    let mut generator = TypeScriptGenerator::new(GeneratorConfig::new().with_zod(true));
+    for (version, snapshot) in [
+        (ZodVersion::V3, "zod_v3.snap"),
+        (ZodVersion::V4, "zod_v4.snap"),
+    ] {
+        let config = GeneratorConfig::new()
+            .with_zod(true)
+            .with_jsdoc(false)
+            .with_result_style(ResultStyle::SuccessError)
+            .with_float_style(FloatStyle::Finite)
+            .with_zod_version(version);
+        let output = TypeScriptGenerator::new(config).generate(&types);
+        assert_snapshot(snapshot, &output);
+    }
+}
+
+#[test]
+fn test_snapshot_branded_type_output() {
+    let ty = GearMeshType {
+        name: "UserId".to_string(),
+        kind: TypeKind::Newtype(NewtypeType {
+            inner: TypeRef::new("i32"),
+            validations: vec![],
+            markers: vec![],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes {
+            branded: true,
+            ..Default::default()
+        },
+    };
+
+    let mut generator = TypeScriptGenerator::new(GeneratorConfig::new());
     let output = generator.generate(&[ty]);
 
-    assert_snapshot("validation_and_zod.snap", &output);
+    assert_snapshot("branded_type.snap", &output);
 }
 
 #[test]
-fn test_snapshot_result_tagged_union_output() {
-    let ty = GearMeshType {
-        name: "ApiResponse".to_string(),
+fn test_snapshot_validated_branded_types() {
+    let branded = |name: &str, inner: &str, validations: Vec<ValidationRule>| GearMeshType {
+        name: name.to_string(),
+        kind: TypeKind::Newtype(NewtypeType {
+            inner: TypeRef::new(inner),
+            validations,
+            markers: vec![],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes {
+            branded: true,
+            ..Default::default()
+        },
+    };
+    let email = branded(
+        "Email",
+        "String",
+        vec![
+            ValidationRule::Email,
+            ValidationRule::WithMessage {
+                rule: Box::new(ValidationRule::Length {
+                    min: None,
+                    max: Some(254),
+                }),
+                message: ValidationMessage::Text("At most {max} characters".to_string()),
+            },
+        ],
+    );
+    let handle = branded(
+        "Handle",
+        "String",
+        vec![ValidationRule::Custom {
+            name: "Handle".to_string(),
+            message: None,
+        }],
+    );
+    let contact = GearMeshType {
+        name: "Contact".to_string(),
         kind: TypeKind::Struct(StructType {
-            fields: vec![
-                FieldInfo {
-                    name: "result".to_string(),
-                    ty: TypeRef::with_generics(
-                        "Result",
-                        vec![TypeRef::new("String"), TypeRef::new("ApiError")],
-                    ),
-                    docs: None,
-                    validations: vec![],
-                    optional: false,
-                    serde_attrs: Default::default(),
-                },
-                FieldInfo {
-                    name: "retry_after".to_string(),
-                    ty: TypeRef::with_generics("Option", vec![TypeRef::new("i32")]),
-                    docs: None,
-                    validations: vec![],
-                    optional: true,
-                    serde_attrs: Default::default(),
-                },
-            ],
+            fields: vec![FieldInfo {
+                name: "email".to_string(),
+                ty: TypeRef::new("Email"),
+                docs: None,
+                validations: vec![],
+                optional: false,
+                serde_attrs: Default::default(),
+                attributes: Default::default(),
+            }],
         }),
         docs: None,
         generics: vec![],
         attributes: TypeAttributes::default(),
     };
 
-    let mut generator = TypeScriptGenerator::new(
-        GeneratorConfig::new().with_result_style(ResultStyle::TaggedUnion),
+    let config = GeneratorConfig::new()
+        .with_zod(true)
+        .with_brand_style(crate::BrandStyle::UniqueSymbol);
+    let mut generator = TypeScriptGenerator::new(config.clone());
+    let mut output = generator.generate(&[email.clone(), handle, contact]);
+    output.push_str("\n// Result-style constructor\n");
+    output.push_str(
+        &crate::BrandedTypeGenerator::new(
+            config
+                .clone()
+                .with_brand_constructor_style(crate::BrandConstructorStyle::Result),
+        )
+        .generate(&email, "string")
+        .unwrap(),
     );
-    let output = generator.generate(&[ty]);
+    output.push_str(&format!("\n// {}\n", crate::BRAND_MODULE));
+    output.push_str(&crate::BrandedTypeGenerator::new(config).generate_helpers());
 
-    assert_snapshot("result_tagged_union.snap", &output);
+    assert_snapshot("validated_branded_types.snap", &output);
+}
+
+#[test]
+fn test_snapshot_generic_branded_ids() {
+    let id = GearMeshType {
+        name: "Id".to_string(),
+        kind: TypeKind::Newtype(NewtypeType {
+            inner: TypeRef::new("i64"),
+            validations: vec![ValidationRule::Range {
+                min: Some(1.0),
+                max: None,
+            }],
+            markers: vec!["T".to_string()],
+        }),
+        docs: None,
+        generics: vec![GenericParam {
+            name: "T".to_string(),
+            bounds: vec![],
+        }],
+        attributes: TypeAttributes {
+            branded: true,
+            ..Default::default()
+        },
+    };
+    let id_field = |name: &str, marker: &str| FieldInfo {
+        name: name.to_string(),
+        ty: TypeRef::with_generics("Id", vec![TypeRef::new(marker)]),
+        docs: None,
+        validations: vec![],
+        optional: false,
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    let entity = |name: &str, fields: Vec<FieldInfo>| GearMeshType {
+        name: name.to_string(),
+        kind: TypeKind::Struct(StructType { fields }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+    let user = entity("User", vec![id_field("id", "User")]);
+    let order = entity(
+        "Order",
+        vec![id_field("id", "Order"), id_field("user_id", "User")],
+    );
+    let types = [id, user, order];
+
+    let config = GeneratorConfig::new().with_bigint(false).with_zod(true);
+    let output = TypeScriptGenerator::new(config).generate(&types);
+    assert_snapshot("generic_branded_ids.snap", &output);
+
+    // マーカーは文字列リテラルになるので `Order` は `User` をimportしない
+    let organizer = crate::ModuleOrganizer::new(&types);
+    let modules = organizer.organize(&types, &crate::ModuleStrategy::PerType);
+    let index = organizer.build_type_index(&modules);
+    let imports = organizer.generate_imports("models/order.ts", &types[2..], &index, false);
+    assert_eq!(imports, vec!["import type { Id } from './id';".to_string()]);
+}
+
+#[test]
+fn test_snapshot_inferred_types() {
+    let field = |name: &str, ty: TypeRef, docs: Option<&str>| FieldInfo {
+        name: name.to_string(),
+        optional: ty.name == "Option",
+        ty,
+        docs: docs.map(DocComment::summary),
+        validations: vec![],
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    let id = GearMeshType {
+        name: "Id".to_string(),
+        kind: TypeKind::Newtype(NewtypeType {
+            inner: TypeRef::new("u32"),
+            validations: vec![],
+            markers: vec!["T".to_string()],
+        }),
+        docs: None,
+        generics: vec![GenericParam {
+            name: "T".to_string(),
+            bounds: vec![],
+        }],
+        attributes: TypeAttributes {
+            branded: true,
+            ..Default::default()
+        },
+    };
+    let role = GearMeshType {
+        name: "Role".to_string(),
+        kind: TypeKind::Enum(EnumType {
+            variants: ["Admin", "Member"]
+                .into_iter()
+                .map(|name| EnumVariant {
+                    name: name.to_string(),
+                    content: VariantContent::Unit,
+                    docs: None,
+                })
+                .collect(),
+            representation: EnumRepresentation::External,
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+    let user = GearMeshType {
+        name: "User".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field(
+                    "id",
+                    TypeRef::with_generics("Id", vec![TypeRef::new("User")]),
+                    None,
+                ),
+                field("name", TypeRef::new("String"), Some("Display name")),
+                field("role", TypeRef::new("Role"), None),
+            ],
+        }),
+        docs: Some(DocComment::summary("A registered user")),
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+    let page = GearMeshType {
+        name: "Page".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field(
+                    "items",
+                    TypeRef::with_generics("Vec", vec![TypeRef::new("T")]),
+                    None,
+                ),
+                field(
+                    "next_cursor",
+                    TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+                    None,
+                ),
+            ],
+        }),
+        docs: None,
+        generics: vec![GenericParam {
+            name: "T".to_string(),
+            bounds: vec![],
+        }],
+        attributes: TypeAttributes::default(),
+    };
+    let user_page = GearMeshType {
+        name: "UserList".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![field(
+                "page",
+                TypeRef::with_generics("Page", vec![TypeRef::new("User")]),
+                None,
+            )],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+    let types = [id, role, user, page, user_page];
+
+    let config = GeneratorConfig::new()
+        .with_enum_style(EnumStyle::Enum)
+        .with_enum_values(true);
+    let mut output =
+        TypeScriptGenerator::new(config.clone().with_type_style(TypeStyle::Infer)).generate(&types);
+    output.push_str("\n// --- z.input / z.output ---\n\n");
+    output.push_str(
+        &TypeScriptGenerator::new(
+            config
+                .with_type_style(TypeStyle::InputOutput)
+                .with_zod_version(ZodVersion::V4),
+        )
+        .generate(&types),
+    );
+
+    assert_snapshot("inferred_types.snap", &output);
+}
+
+#[test]
+fn test_snapshot_output_formatting() {
+    let field = |name: &str, ty: TypeRef, validations: Vec<ValidationRule>| FieldInfo {
+        name: name.to_string(),
+        optional: ty.name == "Option",
+        ty,
+        docs: None,
+        validations,
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    let unit = |name: &str| EnumVariant {
+        name: name.to_string(),
+        content: VariantContent::Unit,
+        docs: None,
+    };
+    let status = GearMeshType {
+        name: "Status".to_string(),
+        kind: TypeKind::Enum(EnumType {
+            variants: vec![unit("Active"), unit("Suspended")],
+            representation: EnumRepresentation::External,
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+    let permission = GearMeshType {
+        name: "Permission".to_string(),
+        kind: TypeKind::Enum(EnumType {
+            variants: vec![
+                unit("ReadOnlyAccess"),
+                unit("ReadWriteAccess"),
+                unit("Administrator"),
+                unit("BillingManager"),
+            ],
+            representation: EnumRepresentation::External,
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+    let event = GearMeshType {
+        name: "Event".to_string(),
+        kind: TypeKind::Enum(EnumType {
+            variants: vec![
+                EnumVariant {
+                    name: "Renamed".to_string(),
+                    content: VariantContent::Tuple(vec![TypeRef::new("String")]),
+                    docs: Some(DocComment::parse("The user's display name changed")),
+                },
+                unit("Deleted"),
+            ],
+            representation: EnumRepresentation::Internal {
+                tag: "type".to_string(),
+            },
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+    let user_id = GearMeshType {
+        name: "UserId".to_string(),
+        kind: TypeKind::Newtype(NewtypeType {
+            inner: TypeRef::new("String"),
+            validations: vec![ValidationRule::WithMessage {
+                rule: Box::new(ValidationRule::NonEmpty),
+                message: ValidationMessage::Text("User ID can't be empty".to_string()),
+            }],
+            markers: vec![],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes {
+            branded: true,
+            ..Default::default()
+        },
+    };
+    let user = GearMeshType {
+        name: "User".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field("id", TypeRef::new("UserId"), vec![]),
+                field(
+                    "handle",
+                    TypeRef::new("String"),
+                    vec![ValidationRule::Pattern("^[^'\"]+$".to_string())],
+                ),
+                // 行に収まらないメソッドチェーンは1行に1メソッドずつ折り返す
+                field(
+                    "email",
+                    TypeRef::new("String"),
+                    vec![
+                        ValidationRule::Email,
+                        ValidationRule::WithMessage {
+                            rule: Box::new(ValidationRule::Length {
+                                min: Some(6),
+                                max: Some(254),
+                            }),
+                            message: ValidationMessage::Text(
+                                "Email must be 6 to 254 characters long".to_string(),
+                            ),
+                        },
+                        ValidationRule::Custom {
+                            name: "CompanyEmail".to_string(),
+                            message: Some("Use your company email address".to_string()),
+                        },
+                    ],
+                ),
+                field("status", TypeRef::new("Status"), vec![]),
+                field(
+                    "permissions",
+                    TypeRef::with_generics("Vec", vec![TypeRef::new("Permission")]),
+                    vec![],
+                ),
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes {
+            validate: true,
+            ..Default::default()
+        },
+    };
+    let types = [status, permission, event, user_id, user];
+
+    let config = GeneratorConfig::new()
+        .with_zod(true)
+        .with_enum_match(true)
+        .with_indent("  ")
+        .with_format(
+            FormatOptions::new()
+                .with_quote_style(QuoteStyle::Single)
+                .with_semicolons(false)
+                .with_trailing_commas(TrailingCommas::None)
+                .with_line_width(80)
+                .with_union_break(UnionBreak::Auto),
+        );
+    let output = TypeScriptGenerator::new(config.clone()).generate(&types);
+    let helpers = BrandedTypeGenerator::new(config).generate_helpers();
+
+    let long_lines = output
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("/**") && line.chars().count() > 80)
+        .collect::<Vec<_>>();
+    assert!(long_lines.is_empty(), "{long_lines:#?}");
+    assert_snapshot(
+        "output_formatting.snap",
+        &format!("{output}\n// ---- gear-mesh.brand.ts ----\n\n{helpers}"),
+    );
+}
+
+#[test]
+fn test_snapshot_escaped_names_and_literals() {
+    let field = |name: &str, ty: TypeRef, validations: Vec<ValidationRule>| FieldInfo {
+        name: name.to_string(),
+        optional: false,
+        ty,
+        docs: None,
+        validations,
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    // 生成コードが参照するグローバルと同じ名前の型
+    let date = GearMeshType {
+        name: "Date".to_string(),
+        kind: TypeKind::Enum(EnumType {
+            variants: vec![
+                EnumVariant {
+                    name: "Now".to_string(),
+                    content: VariantContent::Unit,
+                    docs: None,
+                },
+                EnumVariant {
+                    name: "At".to_string(),
+                    content: VariantContent::Tuple(vec![TypeRef::new("String")]),
+                    docs: None,
+                },
+            ],
+            representation: EnumRepresentation::Adjacent {
+                tag: "@type".to_string(),
+                content: "value\"s".to_string(),
+            },
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+    let record = GearMeshType {
+        name: "Record".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field("password", TypeRef::new("String"), vec![]),
+                field(
+                    "password_confirm",
+                    TypeRef::new("String"),
+                    vec![ValidationRule::CrossField {
+                        fields: vec!["password".to_string(), "password_confirm".to_string()],
+                        rule: CrossFieldRule::Match,
+                        message: Some("Passwords don't \"match\"".to_string()),
+                        path: None,
+                    }],
+                ),
+                field("updated", TypeRef::new("Date"), vec![]),
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes {
+            serde: SerdeTypeAttrs {
+                rename_all: Some(RenameRule::CamelCase),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    };
+    // TypeScriptの予約語と同じ名前の型
+    let mut quoted = field(
+        "label",
+        TypeRef::new("String"),
+        vec![
+            ValidationRule::WithMessage {
+                rule: Box::new(ValidationRule::NonEmpty),
+                message: ValidationMessage::Text("it's \"bad\"\n\u{2028}".to_string()),
+            },
+            ValidationRule::Pattern(r"^a/b\d+$".to_string()),
+        ],
+    );
+    quoted.serde_attrs.rename = Some("it's \"label\"".to_string());
+    let default = GearMeshType {
+        name: "default".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field("delete", TypeRef::new("bool"), vec![]),
+                quoted,
+                field(
+                    "records",
+                    TypeRef::with_generics("Vec", vec![TypeRef::new("Record")]),
+                    vec![],
+                ),
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+    let types = [date, record, default];
+
+    let config = GeneratorConfig::new()
+        .with_zod(true)
+        .with_enum_guards(true)
+        .with_enum_match(true);
+    let output = TypeScriptGenerator::new(config).generate(&types);
+
+    assert!(!output.contains("type Date "));
+    assert!(!output.contains("interface Record "));
+    assert!(!output.contains("interface default "));
+    assert_snapshot("escaped_names_and_literals.snap", &output);
+}
+
+#[test]
+fn test_snapshot_type_assertions() {
+    let field = |name: &str, ty: TypeRef| FieldInfo {
+        name: name.to_string(),
+        optional: ty.name == "Option",
+        ty,
+        docs: None,
+        validations: vec![],
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    let entity = |name: &str, fields: Vec<FieldInfo>, generics: &[&str]| GearMeshType {
+        name: name.to_string(),
+        kind: TypeKind::Struct(StructType { fields }),
+        docs: None,
+        generics: generics
+            .iter()
+            .map(|name| GenericParam {
+                name: name.to_string(),
+                bounds: vec![],
+            })
+            .collect(),
+        attributes: TypeAttributes::default(),
+    };
+    let user_id = GearMeshType {
+        name: "UserId".to_string(),
+        kind: TypeKind::Newtype(NewtypeType {
+            inner: TypeRef::new("u32"),
+            validations: vec![],
+            markers: vec![],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes {
+            branded: true,
+            ..Default::default()
+        },
+    };
+    let status = GearMeshType {
+        name: "Status".to_string(),
+        kind: TypeKind::Enum(EnumType {
+            variants: vec![
+                EnumVariant {
+                    name: "Active".to_string(),
+                    content: VariantContent::Unit,
+                    docs: None,
+                },
+                EnumVariant {
+                    name: "Suspended".to_string(),
+                    content: VariantContent::Struct(vec![field("reason", TypeRef::new("String"))]),
+                    docs: None,
+                },
+            ],
+            representation: EnumRepresentation::Internal {
+                tag: "type".to_string(),
+            },
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+    let profile = entity(
+        "Profile",
+        vec![
+            field("name", TypeRef::new("String")),
+            field(
+                "bio",
+                TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+            ),
+            field("status", TypeRef::new("Status")),
+        ],
+        &[],
+    );
+    let role = GearMeshType {
+        name: "Role".to_string(),
+        kind: TypeKind::Enum(EnumType {
+            variants: ["Admin", "Member"]
+                .map(|name| EnumVariant {
+                    name: name.to_string(),
+                    content: VariantContent::Unit,
+                    docs: None,
+                })
+                .to_vec(),
+            representation: EnumRepresentation::External,
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+    // ブランドとTS enumを参照する型も検査する
+    let account = entity(
+        "Account",
+        vec![
+            field("id", TypeRef::new("UserId")),
+            field("role", TypeRef::new("Role")),
+            field("profile", TypeRef::new("Profile")),
+        ],
+        &[],
+    );
+    let page = entity(
+        "Page",
+        vec![field(
+            "items",
+            TypeRef::with_generics("Vec", vec![TypeRef::new("T")]),
+        )],
+        &["T"],
+    );
+    let types = [user_id, role, status, profile, account, page];
+
+    let config = GeneratorConfig::new()
+        .with_zod(true)
+        .with_jsdoc(false)
+        .with_enum_style(EnumStyle::Enum)
+        .with_type_assertions(true);
+    let mut output = TypeScriptGenerator::new(config.clone()).generate(&types);
+    for name in ["UserId", "Role", "Account"] {
+        assert!(output.contains(&format!("export type _Assert{name} = ")));
+    }
+    output.push_str("\n// --- Zod v4 ---\n\n");
+    output.push_str(
+        &TypeScriptGenerator::new(config.with_zod_version(ZodVersion::V4)).generate(&types),
+    );
+    assert_snapshot("type_assertions.snap", &output);
+}
+
+#[test]
+fn test_snapshot_generic_type_output() {
+    let ty = GearMeshType {
+        name: "Paginated".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                FieldInfo {
+                    name: "items".to_string(),
+                    ty: TypeRef::with_generics("Vec", vec![TypeRef::new("T")]),
+                    docs: None,
+                    validations: vec![],
+                    optional: false,
+                    serde_attrs: Default::default(),
+                    attributes: Default::default(),
+                },
+                FieldInfo {
+                    name: "next_cursor".to_string(),
+                    ty: TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+                    docs: None,
+                    validations: vec![],
+                    optional: true,
+                    serde_attrs: Default::default(),
+                    attributes: Default::default(),
+                },
+            ],
+        }),
+        docs: None,
+        generics: vec![gear_mesh_core::GenericParam {
+            name: "T".to_string(),
+            bounds: vec![],
+        }],
+        attributes: TypeAttributes::default(),
+    };
+
+    let mut generator = TypeScriptGenerator::new(GeneratorConfig::new());
+    let output = generator.generate(&[ty]);
+
+    assert_snapshot("generic_type.snap", &output);
+}
+
+#[test]
+fn test_snapshot_validation_and_zod_output() {
+    let ty = GearMeshType {
+        name: "UserInput".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                FieldInfo {
+                    name: "name".to_string(),
+                    ty: TypeRef::new("String"),
+                    docs: Some(DocComment::summary("Display name")),
+                    validations: vec![ValidationRule::Length {
+                        min: Some(1),
+                        max: Some(20),
+                    }],
+                    optional: false,
+                    serde_attrs: Default::default(),
+                    attributes: Default::default(),
+                },
+                FieldInfo {
+                    name: "email".to_string(),
+                    ty: TypeRef::new("String"),
+                    docs: Some(DocComment::summary("Email address")),
+                    validations: vec![ValidationRule::Email],
+                    optional: false,
+                    serde_attrs: Default::default(),
+                    attributes: Default::default(),
+                },
+                FieldInfo {
+                    name: "website".to_string(),
+                    ty: TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+                    docs: Some(DocComment::summary("Optional website")),
+                    validations: vec![ValidationRule::Url],
+                    optional: true,
+                    serde_attrs: Default::default(),
+                    attributes: Default::default(),
+                },
+            ],
+        }),
+        docs: Some(DocComment::summary("User input payload")),
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+
+    let mut generator = TypeScriptGenerator::new(GeneratorConfig::new().with_zod(true));
+    let output = generator.generate(&[ty]);
+
+    assert_snapshot("validation_and_zod.snap", &output);
+}
+
+#[test]
+fn test_snapshot_collection_validation_output() {
+    let field = |name: &str, ty: TypeRef, docs: &str, validations: Vec<ValidationRule>| FieldInfo {
+        name: name.to_string(),
+        optional: ty.name == "Option",
+        ty,
+        docs: Some(DocComment::summary(docs)),
+        validations,
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    let ty = GearMeshType {
+        name: "Inventory".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field(
+                    "tags",
+                    TypeRef::with_generics("Vec", vec![TypeRef::new("String")]),
+                    "Unique tags",
+                    vec![
+                        ValidationRule::Length {
+                            min: Some(1),
+                            max: Some(5),
+                        },
+                        ValidationRule::Unique,
+                        ValidationRule::Each(vec![
+                            ValidationRule::Length {
+                                min: Some(2),
+                                max: None,
+                            },
+                            ValidationRule::Alphanumeric,
+                        ]),
+                    ],
+                ),
+                field(
+                    "owners",
+                    TypeRef::with_generics(
+                        "Option",
+                        vec![TypeRef::with_generics(
+                            "Vec",
+                            vec![TypeRef::with_generics(
+                                "Option",
+                                vec![TypeRef::new("String")],
+                            )],
+                        )],
+                    ),
+                    "Owner IDs",
+                    vec![ValidationRule::Each(vec![ValidationRule::Uuid])],
+                ),
+                field(
+                    "labels",
+                    TypeRef::with_generics(
+                        "HashMap",
+                        vec![TypeRef::new("String"), TypeRef::new("String")],
+                    ),
+                    "Per-locale labels",
+                    vec![
+                        ValidationRule::NonEmpty,
+                        ValidationRule::Contains("en".to_string()),
+                        ValidationRule::Each(vec![ValidationRule::StartsWith("#".to_string())]),
+                    ],
+                ),
+                field(
+                    "sku",
+                    TypeRef::new("String"),
+                    "Stock keeping unit",
+                    vec![
+                        ValidationRule::Length {
+                            min: Some(8),
+                            max: Some(8),
+                        },
+                        ValidationRule::Ascii,
+                        ValidationRule::EndsWith("-X".to_string()),
+                    ],
+                ),
+                field(
+                    "host",
+                    TypeRef::new("String"),
+                    "Warehouse host",
+                    vec![ValidationRule::Ip(None)],
+                ),
+                field(
+                    "pack_size",
+                    TypeRef::new("u32"),
+                    "Units per pack",
+                    vec![ValidationRule::MultipleOf(6.0)],
+                ),
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+
+    let mut generator = TypeScriptGenerator::new(
+        GeneratorConfig::new()
+            .with_zod(true)
+            .with_enhanced_jsdoc(true),
+    );
+    let output = generator.generate(&[ty]);
+
+    assert_snapshot("collection_validation.snap", &output);
+}
+
+#[test]
+fn test_snapshot_refinements_follow_native_checks() {
+    let field = |name: &str, ty: TypeRef, validations: Vec<ValidationRule>| FieldInfo {
+        name: name.to_string(),
+        optional: false,
+        ty,
+        docs: None,
+        validations,
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    // Zod v3の `.refine` は `ZodEffects` を返すため、宣言順に関係なく最後に置く
+    let ty = GearMeshType {
+        name: "Listing".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field(
+                    "tags",
+                    TypeRef::with_generics("Vec", vec![TypeRef::new("String")]),
+                    vec![
+                        ValidationRule::Unique,
+                        ValidationRule::Length {
+                            min: Some(1),
+                            max: None,
+                        },
+                    ],
+                ),
+                field(
+                    "title",
+                    TypeRef::new("String"),
+                    vec![
+                        ValidationRule::WithMessage {
+                            rule: Box::new(ValidationRule::NonEmpty),
+                            message: ValidationMessage::Key {
+                                key: "listing.title.empty".to_string(),
+                                default: None,
+                            },
+                        },
+                        ValidationRule::Length {
+                            min: None,
+                            max: Some(80),
+                        },
+                    ],
+                ),
+                field(
+                    "slug",
+                    TypeRef::new("String"),
+                    vec![
+                        ValidationRule::Custom {
+                            name: "Slug".to_string(),
+                            message: None,
+                        },
+                        ValidationRule::Length {
+                            min: Some(3),
+                            max: None,
+                        },
+                        ValidationRule::Pattern("^[a-z-]+$".to_string()),
+                    ],
+                ),
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+
+    let output = ValidationGenerator::new(GeneratorConfig::new().with_zod(true))
+        .generate_zod_schema(&ty)
+        .unwrap();
+
+    assert_snapshot("refinement_order.snap", &output);
+}
+
+#[test]
+fn test_zod_structured_conditional_uses_super_refine() {
+    let ty = GearMeshType {
+        name: "Billing".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                FieldInfo {
+                    name: "account_kind".to_string(),
+                    ty: TypeRef::new("String"),
+                    docs: None,
+                    validations: vec![],
+                    optional: false,
+                    serde_attrs: Default::default(),
+                    attributes: Default::default(),
+                },
+                FieldInfo {
+                    name: "vat_id".to_string(),
+                    ty: TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+                    docs: None,
+                    validations: vec![
+                        ValidationRule::Conditional {
+                            condition: Condition::Equals {
+                                field: "account_kind".to_string(),
+                                value: ConditionValue::String("company".to_string()),
+                            },
+                            rule: Box::new(ValidationRule::Required),
+                        },
+                        ValidationRule::Conditional {
+                            condition: Condition::Present("vat_id".to_string()),
+                            rule: Box::new(ValidationRule::Length {
+                                min: Some(8),
+                                max: None,
+                            }),
+                        },
+                    ],
+                    optional: true,
+                    serde_attrs: Default::default(),
+                    attributes: Default::default(),
+                },
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes {
+            serde: SerdeTypeAttrs {
+                rename_all: Some(RenameRule::CamelCase),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    };
+
+    let mut generator = TypeScriptGenerator::new(GeneratorConfig::new().with_zod(true));
+    let output = generator.generate(&[ty]);
+
+    assert!(output.contains(
+        r#".superRefine((data, ctx) => { if ((data.accountKind === "company") && !(data.vatId !== undefined && data.vatId !== null)) { ctx.addIssue({ code: "custom", message: "vatId is required", path: ["vatId"] }); } })"#
+    ), "generated: {output}");
+    assert!(output.contains(
+        r#".superRefine((data, ctx) => { if ((data.vatId !== undefined && data.vatId !== null && data.vatId !== "") && !(data.vatId === undefined || data.vatId === null || (data.vatId.length >= 8))) { ctx.addIssue({ code: "custom", message: "vatId failed validation", path: ["vatId"] }); } })"#
+    ), "generated: {output}");
+}
+
+#[test]
+fn test_zod_schema_literals_follow_the_quote_style() {
+    let field = |name: &str, ty: TypeRef, validations: Vec<ValidationRule>| FieldInfo {
+        name: name.to_string(),
+        optional: false,
+        ty,
+        docs: None,
+        validations,
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    let ty = GearMeshType {
+        name: "Contact".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field(
+                    "email",
+                    TypeRef::new("String"),
+                    vec![ValidationRule::CrossField {
+                        fields: vec!["email".to_string(), "phone".to_string()],
+                        rule: CrossFieldRule::AtLeastOne,
+                        message: None,
+                        path: None,
+                    }],
+                ),
+                field(
+                    "phone",
+                    TypeRef::new("String"),
+                    vec![ValidationRule::Conditional {
+                        condition: Condition::Equals {
+                            field: "email".to_string(),
+                            value: ConditionValue::String("none".to_string()),
+                        },
+                        rule: Box::new(ValidationRule::Required),
+                    }],
+                ),
+                field(
+                    "last_sync",
+                    TypeRef::with_generics(
+                        "Result",
+                        vec![TypeRef::new("String"), TypeRef::new("String")],
+                    ),
+                    vec![],
+                ),
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: Default::default(),
+    };
+
+    let config = GeneratorConfig::new()
+        .with_zod(true)
+        .with_zod_version(ZodVersion::V4)
+        .with_result_style(ResultStyle::SuccessError)
+        .with_format(FormatOptions::new().with_quote_style(QuoteStyle::Single));
+    let output = TypeScriptGenerator::new(config).generate(&[ty]);
+
+    assert!(!output.contains('"'), "generated: {output}");
+    assert!(
+        output.contains("data.email !== undefined && data.email !== null && data.email !== ''"),
+        "generated: {output}"
+    );
+    assert!(
+        output.contains("z.discriminatedUnion('success', ["),
+        "generated: {output}"
+    );
+    assert!(
+        output.contains("{ error: 'phone is required', path: ['phone'] }"),
+        "generated: {output}"
+    );
+}
+
+#[test]
+fn test_snapshot_result_tagged_union_output() {
+    let ty = GearMeshType {
+        name: "ApiResponse".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                FieldInfo {
+                    name: "result".to_string(),
+                    ty: TypeRef::with_generics(
+                        "Result",
+                        vec![TypeRef::new("String"), TypeRef::new("ApiError")],
+                    ),
+                    docs: None,
+                    validations: vec![],
+                    optional: false,
+                    serde_attrs: Default::default(),
+                    attributes: Default::default(),
+                },
+                FieldInfo {
+                    name: "retry_after".to_string(),
+                    ty: TypeRef::with_generics("Option", vec![TypeRef::new("i32")]),
+                    docs: None,
+                    validations: vec![],
+                    optional: true,
+                    serde_attrs: Default::default(),
+                    attributes: Default::default(),
+                },
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+
+    let mut generator = TypeScriptGenerator::new(
+        GeneratorConfig::new().with_result_style(ResultStyle::TaggedUnion),
+    );
+    let output = generator.generate(&[ty]);
+
+    assert_snapshot("result_tagged_union.snap", &output);
+}
+
+#[test]
+fn test_snapshot_validation_messages_output() {
+    let keyed = |rule: ValidationRule, key: &str| ValidationRule::WithMessage {
+        rule: Box::new(rule),
+        message: ValidationMessage::Key {
+            key: key.to_string(),
+            default: None,
+        },
+    };
+    let field = |name: &str, ty: TypeRef, validations: Vec<ValidationRule>| FieldInfo {
+        name: name.to_string(),
+        optional: ty.name == "Option",
+        ty,
+        docs: None,
+        validations,
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    let ty = GearMeshType {
+        name: "SignUp".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field(
+                    "name",
+                    TypeRef::new("String"),
+                    vec![
+                        ValidationRule::WithMessage {
+                            rule: Box::new(ValidationRule::Length {
+                                min: Some(3),
+                                max: Some(20),
+                            }),
+                            message: ValidationMessage::Key {
+                                key: "signup.name.length".to_string(),
+                                default: Some("Name must be {min} to {max} characters".to_string()),
+                            },
+                        },
+                        ValidationRule::WithMessage {
+                            rule: Box::new(ValidationRule::Pattern("^[a-z]+$".to_string())),
+                            message: ValidationMessage::Text(
+                                "Use lowercase \"a-z\" only".to_string(),
+                            ),
+                        },
+                    ],
+                ),
+                field(
+                    "email",
+                    TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+                    vec![keyed(ValidationRule::Email, "signup.email.invalid")],
+                ),
+                field(
+                    "tags",
+                    TypeRef::with_generics("Vec", vec![TypeRef::new("String")]),
+                    vec![ValidationRule::Each(vec![keyed(
+                        ValidationRule::NonEmpty,
+                        "signup.tags.empty",
+                    )])],
+                ),
+                field(
+                    "vat_id",
+                    TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+                    vec![ValidationRule::Conditional {
+                        condition: Condition::Present("company".to_string()),
+                        rule: Box::new(keyed(ValidationRule::Required, "signup.vat_id.required")),
+                    }],
+                ),
+                field(
+                    "company",
+                    TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+                    vec![],
+                ),
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+
+    let config = GeneratorConfig::new()
+        .with_zod(true)
+        .with_message_locales(["en", "ja"]);
+    let mut generator = TypeScriptGenerator::new(config.clone());
+    let output = generator.generate(std::slice::from_ref(&ty));
+
+    let catalog = MessageCatalog::collect(std::slice::from_ref(&ty));
+    let mut ja = [(
+        "signup.email.invalid".to_string(),
+        "メールアドレスの形式が正しくありません".to_string(),
+    )]
+    .into_iter()
+    .collect();
+    assert!(catalog.merge_into(&mut ja));
+    assert_eq!(
+        ja["signup.email.invalid"],
+        "メールアドレスの形式が正しくありません"
+    );
+    let mut en = Default::default();
+    catalog.merge_into(&mut en);
+    let module = catalog.render_module(&[("en".to_string(), en), ("ja".to_string(), ja)], &config);
+
+    assert_snapshot(
+        "validation_messages.snap",
+        &format!("{output}\n// ---- gear-mesh.messages.ts ----\n\n{module}"),
+    );
+}
+
+#[test]
+fn test_snapshot_custom_validator_imports_and_stubs() {
+    let custom = |name: &str| ValidationRule::Custom {
+        name: name.to_string(),
+        message: None,
+    };
+    let field = |name: &str, ty: TypeRef, validations: Vec<ValidationRule>| FieldInfo {
+        name: name.to_string(),
+        optional: ty.name == "Option",
+        ty,
+        docs: None,
+        validations,
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    let account = GearMeshType {
+        name: "Account".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field("username", TypeRef::new("String"), vec![custom("Username")]),
+                field("slug", TypeRef::new("String"), vec![custom("Slug")]),
+                field(
+                    "tags",
+                    TypeRef::with_generics("Vec", vec![TypeRef::new("String")]),
+                    vec![ValidationRule::Each(vec![custom("Tag")])],
+                ),
+                field(
+                    "vat_id",
+                    TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+                    vec![ValidationRule::Conditional {
+                        condition: Condition::Present("company".to_string()),
+                        rule: Box::new(custom("VatId")),
+                    }],
+                ),
+                field(
+                    "company",
+                    TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+                    vec![],
+                ),
+                field("age", TypeRef::new("u32"), vec![custom("Tag")]),
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+
+    let config = GeneratorConfig::new()
+        .with_zod(true)
+        .with_jsdoc(false)
+        .with_custom_validator_import("validateUsername", "./lib/validators")
+        .with_custom_validator_import("Slug", "@app/validators");
+    let mut generator = TypeScriptGenerator::new(config.clone()).with_module("models/account.ts");
+    let output = generator.generate(std::slice::from_ref(&account));
+    assert!(output.contains("import { validateUsername } from '../lib/validators';"));
+    assert!(output.contains("import { validateSlug } from '@app/validators';"));
+    assert!(output.contains("import { validateTag, validateVatId } from '../validators';"));
+
+    let stubs = generator
+        .generate_validator_stubs(std::slice::from_ref(&account), None)
+        .unwrap();
+    assert!(!stubs.contains("validateUsername"));
+    assert!(!stubs.contains("validateSlug"));
+
+    let existing = "export function validateTag(value: string | number): boolean {\n    return value !== \"\";\n}\n";
+    let appended = generator
+        .generate_validator_stubs(std::slice::from_ref(&account), Some(existing))
+        .unwrap();
+    assert!(appended.starts_with(existing));
+    assert_eq!(appended.matches("function validateTag").count(), 1);
+    assert!(
+        generator
+            .generate_validator_stubs(std::slice::from_ref(&account), Some(&appended))
+            .is_none()
+    );
+
+    assert_snapshot(
+        "custom_validators.snap",
+        &format!("{output}\n// ---- validators.ts ----\n\n{stubs}"),
+    );
+}
+
+#[test]
+fn test_snapshot_type_level_validators() {
+    let field = |name: &str, ty: TypeRef, validations: Vec<ValidationRule>| FieldInfo {
+        name: name.to_string(),
+        optional: ty.name == "Option",
+        ty,
+        docs: None,
+        validations,
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    let user = GearMeshType {
+        name: "User".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field(
+                    "name",
+                    TypeRef::new("String"),
+                    vec![ValidationRule::Length {
+                        min: Some(1),
+                        max: Some(20),
+                    }],
+                ),
+                field("email", TypeRef::new("String"), vec![ValidationRule::Email]),
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes {
+            validate: true,
+            ..Default::default()
+        },
+    };
+    let tag = GearMeshType {
+        name: "Tag".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![field("label", TypeRef::new("String"), vec![])],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+
+    let config = GeneratorConfig::new().with_zod(true).with_jsdoc(false);
+    let mut generator = TypeScriptGenerator::new(config.clone());
+    let lean = generator.generate(std::slice::from_ref(&tag));
+    assert!(!lean.contains("validateTag"));
+    assert!(!lean.contains("gear-mesh.validation"));
+
+    let output = generator.generate(&[user, tag]);
+    assert!(!output.contains("parseTag"));
+    let module = crate::ValidationGenerator::new(config).generate_validation_module();
+
+    assert_snapshot(
+        "type_validators.snap",
+        &format!("{output}\n// ---- gear-mesh.validation.ts ----\n\n{module}"),
+    );
+}
+
+#[test]
+fn test_snapshot_bigint_policies() {
+    let field = |name: &str, ty: TypeRef, validations: Vec<ValidationRule>| FieldInfo {
+        name: name.to_string(),
+        optional: ty.name == "Option",
+        ty,
+        docs: None,
+        validations,
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    let with_policy = |mut field: FieldInfo, policy: BigIntPolicy| {
+        field.attributes.bigint = Some(policy);
+        field
+    };
+    let range = ValidationRule::Range {
+        min: Some(1.0),
+        max: Some(1000.0),
+    };
+    let order = GearMeshType {
+        name: "Order".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field("id", TypeRef::new("u64"), vec![]),
+                field("quantity", TypeRef::new("i64"), vec![range.clone()]),
+                with_policy(
+                    field("total", TypeRef::new("u64"), vec![range.clone()]),
+                    BigIntPolicy::String,
+                ),
+                with_policy(
+                    field(
+                        "ledger_ids",
+                        TypeRef::with_generics("Vec", vec![TypeRef::new("i128")]),
+                        vec![],
+                    ),
+                    BigIntPolicy::BigInt,
+                ),
+                with_policy(
+                    field(
+                        "version",
+                        TypeRef::with_generics("Option", vec![TypeRef::new("u32")]),
+                        vec![ValidationRule::MultipleOf(2.0)],
+                    ),
+                    BigIntPolicy::String,
+                ),
+                field("count", TypeRef::new("u32"), vec![]),
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes {
+            bigint: Some(BigIntPolicy::Number),
+            ..Default::default()
+        },
+    };
+    let mut legacy = order.clone();
+    legacy.name = "LegacyOrder".to_string();
+    legacy.attributes = TypeAttributes::default();
+
+    let mut generator =
+        TypeScriptGenerator::new(GeneratorConfig::new().with_zod(true).with_jsdoc(false));
+    let output = generator.generate(&[order, legacy]);
+
+    assert_snapshot("bigint_policies.snap", &output);
+}
+
+#[test]
+fn test_snapshot_field_and_type_overrides() {
+    let field = |name: &str, ty: TypeRef, attributes: FieldAttributes| FieldInfo {
+        name: name.to_string(),
+        optional: ty.name == "Option",
+        ty,
+        docs: None,
+        validations: vec![],
+        serde_attrs: Default::default(),
+        attributes,
+    };
+    let account = GearMeshType {
+        name: "Account".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field(
+                    "id",
+                    TypeRef::new("Uuid"),
+                    FieldAttributes {
+                        ts_type: Some("string".to_string()),
+                        zod: Some("z.string().uuid()".to_string()),
+                        readonly: true,
+                        ..Default::default()
+                    },
+                ),
+                field(
+                    "nickname",
+                    TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+                    FieldAttributes::default(),
+                ),
+                field(
+                    "display_name",
+                    TypeRef::new("String"),
+                    FieldAttributes {
+                        rename: Some("label".to_string()),
+                        nullable: true,
+                        optional: true,
+                        ..Default::default()
+                    },
+                ),
+                field(
+                    "password_hash",
+                    TypeRef::new("String"),
+                    FieldAttributes {
+                        skip: true,
+                        ..Default::default()
+                    },
+                ),
+                field(
+                    "settings",
+                    TypeRef::new("Settings"),
+                    FieldAttributes::default(),
+                ),
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes {
+            rename: Some("AccountDto".to_string()),
+            option_style: Some(OptionStyle::Optional),
+            ..Default::default()
+        },
+    };
+    let settings = GearMeshType {
+        name: "Settings".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![field(
+                "theme",
+                TypeRef::new("String"),
+                FieldAttributes::default(),
+            )],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes {
+            export: false,
+            ..Default::default()
+        },
+    };
+    let team = GearMeshType {
+        name: "Team".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![field(
+                "members",
+                TypeRef::with_generics("Vec", vec![TypeRef::new("Account")]),
+                FieldAttributes::default(),
+            )],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+
+    let mut generator = TypeScriptGenerator::new(GeneratorConfig::new().with_zod(true));
+    let output = generator.generate(&[account, settings, team]);
+
+    assert_snapshot("field_overrides.snap", &output);
+}
+
+#[test]
+fn test_snapshot_literal_and_template_fields() {
+    let field = |name: &str, ty: TypeRef, attributes: FieldAttributes| FieldInfo {
+        name: name.to_string(),
+        optional: ty.name == "Option",
+        ty,
+        docs: None,
+        validations: vec![],
+        serde_attrs: Default::default(),
+        attributes,
+    };
+    let envelope = GearMeshType {
+        name: "Envelope".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field(
+                    "version",
+                    TypeRef::new("String"),
+                    FieldAttributes {
+                        literal: Some(ConditionValue::String("v2".to_string())),
+                        ..Default::default()
+                    },
+                ),
+                field(
+                    "revision",
+                    TypeRef::new("u8"),
+                    FieldAttributes {
+                        literal: Some(ConditionValue::Number(3.0)),
+                        ..Default::default()
+                    },
+                ),
+                field(
+                    "ok",
+                    TypeRef::new("bool"),
+                    FieldAttributes {
+                        literal: Some(ConditionValue::Bool(true)),
+                        ..Default::default()
+                    },
+                ),
+                field(
+                    "user_id",
+                    TypeRef::new("String"),
+                    FieldAttributes {
+                        template: Some("usr_${string}".to_string()),
+                        ..Default::default()
+                    },
+                ),
+                field(
+                    "parent_id",
+                    TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+                    FieldAttributes {
+                        template: Some("usr_${string}".to_string()),
+                        ..Default::default()
+                    },
+                ),
+                field(
+                    "page",
+                    TypeRef::new("String"),
+                    FieldAttributes {
+                        template: Some("page-${number}.json".to_string()),
+                        ..Default::default()
+                    },
+                ),
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+
+    let config = GeneratorConfig::new().with_zod(true);
+    let output = TypeScriptGenerator::new(config).generate(&[envelope]);
+
+    assert_snapshot("literal_fields.snap", &output);
+}
+
+#[test]
+fn test_snapshot_number_schemas() {
+    let field = |name: &str, ty: &str, validations: Vec<ValidationRule>| FieldInfo {
+        name: name.to_string(),
+        ty: TypeRef::new(ty),
+        docs: None,
+        validations,
+        optional: false,
+        serde_attrs: Default::default(),
+        attributes: Default::default(),
+    };
+    let ty = GearMeshType {
+        name: "Measurements".to_string(),
+        kind: TypeKind::Struct(StructType {
+            fields: vec![
+                field("level", "u8", vec![]),
+                field(
+                    "rating",
+                    "u8",
+                    vec![ValidationRule::Range {
+                        min: Some(1.0),
+                        max: Some(5.0),
+                    }],
+                ),
+                field(
+                    "offset",
+                    "i16",
+                    vec![ValidationRule::ExclusiveRange {
+                        min: Some(-100.0),
+                        max: None,
+                    }],
+                ),
+                field("count", "u64", vec![]),
+                field(
+                    "delta",
+                    "i64",
+                    vec![ValidationRule::Range {
+                        min: Some(-1000.0),
+                        max: Some(1000.0),
+                    }],
+                ),
+                field(
+                    "ratio",
+                    "f64",
+                    vec![ValidationRule::Range {
+                        min: Some(0.0),
+                        max: None,
+                    }],
+                ),
+            ],
+        }),
+        docs: None,
+        generics: vec![],
+        attributes: TypeAttributes::default(),
+    };
+
+    let mut output = String::new();
+    for style in [FloatStyle::Finite, FloatStyle::Number, FloatStyle::AllowNaN] {
+        let config = GeneratorConfig::new()
+            .with_zod(true)
+            .with_bigint(false)
+            .with_float_style(style);
+        let schema = crate::ValidationGenerator::new(config)
+            .generate_zod_schema(&ty)
+            .unwrap();
+        output.push_str(&format!("// {style:?}\n{schema}\n"));
+    }
+
+    assert_snapshot("number_schemas.snap", &output);
 }
 
 fn assert_snapshot(name: &str, actual: &str) {
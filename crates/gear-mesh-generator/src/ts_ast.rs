@@ -0,0 +1,497 @@
+//! 出力するTypeScriptの宣言のモデル
+//!
+//! 各ジェネレータは宣言をこのモデルで組み立て、インデント・セミコロン・末尾のカンマ・
+//! 改行などの書式は `Printer` が `FormatOptions` に従って決めます。
+//! 型や式の中身は文字列のまま持ち、書式に関わる構造だけをモデルにしています。
+//! 文字列リテラルは `Expr::Str` で持つか、式に埋め込む場合は設定の引用符を渡した
+//! `js_string` で組み立てます。
+
+/// トップレベルの要素
+#[derive(Debug, Clone)]
+pub(crate) enum Item {
+    /// `// ...`
+    Comment(String),
+    /// 描画済みのJSDoc (インデントなし)
+    Doc(String),
+    /// 空行
+    Blank,
+    /// そのまま出力する行 (解釈できないimport文など)
+    Raw(String),
+    Import(Import),
+    Interface(Interface),
+    TypeAlias(TypeAlias),
+    Enum(Enum),
+    Const(Const),
+    Function(Function),
+    Statement(Stmt),
+}
+
+impl Item {
+    /// import文を解釈する (`import { a, b } from '...'` の形でなければそのまま出力する)
+    pub fn import(line: &str) -> Self {
+        Import::parse(line).map_or_else(|| Self::Raw(line.to_string()), Self::Import)
+    }
+}
+
+/// `import { a, b } from 'module';`
+#[derive(Debug, Clone)]
+pub(crate) struct Import {
+    pub type_only: bool,
+    pub names: Vec<String>,
+    pub module: String,
+}
+
+impl Import {
+    fn parse(line: &str) -> Option<Self> {
+        let rest = line.trim().strip_prefix("import ")?;
+        let (type_only, rest) = match rest.strip_prefix("type ") {
+            Some(rest) => (true, rest),
+            None => (false, rest),
+        };
+        let (names, rest) = rest.strip_prefix('{')?.split_once('}')?;
+        let module = rest
+            .trim()
+            .strip_prefix("from ")?
+            .trim_end_matches(';')
+            .trim();
+        let quote = module
+            .chars()
+            .next()
+            .filter(|ch| matches!(ch, '\'' | '"'))?;
+        let module = module.strip_prefix(quote)?.strip_suffix(quote)?;
+        if module.contains(['\'', '"', '\\']) {
+            return None;
+        }
+        let names = names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            return None;
+        }
+        Some(Self {
+            type_only,
+            names,
+            module: module.to_string(),
+        })
+    }
+}
+
+/// `interface X<T> { ... }`
+#[derive(Debug, Clone)]
+pub(crate) struct Interface {
+    pub export: bool,
+    pub name: String,
+    /// `<T>` (なければ空)
+    pub params: String,
+    pub members: Vec<Member>,
+}
+
+/// `type X<T> = ...;`
+#[derive(Debug, Clone)]
+pub(crate) struct TypeAlias {
+    pub export: bool,
+    pub name: String,
+    /// `<T>` (なければ空)
+    pub params: String,
+    pub ty: TsType,
+}
+
+/// TS enum (`enum X { A = "a" }`)
+#[derive(Debug, Clone)]
+pub(crate) struct Enum {
+    pub export: bool,
+    pub name: String,
+    pub members: Vec<EnumMember>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct EnumMember {
+    pub jsdoc: Option<String>,
+    pub name: String,
+    pub value: String,
+}
+
+/// `const x: T = ...;`
+#[derive(Debug, Clone)]
+pub(crate) struct Const {
+    pub export: bool,
+    pub name: String,
+    pub ty: Option<String>,
+    pub value: Expr,
+}
+
+/// `function f<T>(a: A): R { ... }`
+#[derive(Debug, Clone)]
+pub(crate) struct Function {
+    pub export: bool,
+    pub name: String,
+    /// `<T>` (なければ空)
+    pub type_params: String,
+    pub params: Vec<Param>,
+    /// 引数を1行に1つずつ書くか (`line_width` を超える場合も複数行になる)
+    pub multiline: bool,
+    pub return_type: String,
+    pub body: Vec<Stmt>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Param {
+    pub name: String,
+    pub ty: TsType,
+    pub default: Option<String>,
+}
+
+impl Param {
+    pub fn new(name: impl Into<String>, ty: impl Into<TsType>) -> Self {
+        Self {
+            name: name.into(),
+            ty: ty.into(),
+            default: None,
+        }
+    }
+}
+
+/// インターフェース・オブジェクト型のプロパティ
+#[derive(Debug, Clone)]
+pub(crate) struct Member {
+    /// 描画済みのJSDoc (インデントなし)
+    pub jsdoc: Option<String>,
+    pub readonly: bool,
+    /// 出力するプロパティ名 (必要ならクォート済み)
+    pub name: String,
+    pub optional: bool,
+    pub ty: TsType,
+}
+
+impl Member {
+    pub fn new(name: impl Into<String>, ty: impl Into<TsType>) -> Self {
+        Self {
+            jsdoc: None,
+            readonly: false,
+            name: name.into(),
+            optional: false,
+            ty: ty.into(),
+        }
+    }
+}
+
+/// 型の式
+#[derive(Debug, Clone)]
+pub(crate) enum TsType {
+    Raw(String),
+    /// 1行のオブジェクト型 (`{ a: A; b: B }`)
+    Inline(Vec<Member>),
+    /// 1行に1プロパティのオブジェクト型
+    Block(Vec<Member>),
+    /// `| A | B` (各メンバーの前にJSDocを置ける)
+    Union(Vec<UnionMember>),
+    /// 1行のユニオン (`A | B`)
+    Or(Vec<TsType>),
+}
+
+impl From<String> for TsType {
+    fn from(ty: String) -> Self {
+        Self::Raw(ty)
+    }
+}
+
+impl From<&str> for TsType {
+    fn from(ty: &str) -> Self {
+        Self::Raw(ty.to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct UnionMember {
+    pub jsdoc: Option<String>,
+    pub ty: TsType,
+}
+
+/// 値の式
+#[derive(Debug, Clone)]
+pub(crate) enum Expr {
+    Raw(String),
+    /// 文字列リテラル (引用符は `FormatOptions::quote_style` に従う)
+    Str(String),
+    /// 前後に文字列を付けた式 (`(` ... `)`, ... `as const` など)
+    Wrap(String, Box<Expr>, String),
+    /// 1行に1プロパティのオブジェクト
+    Object(Vec<Property>),
+    /// 1行のオブジェクト (`{ a: 1, b: 2 }`)
+    InlineObject(Vec<Property>),
+    /// 1行に1要素の配列
+    Array(Vec<Expr>),
+    /// 1行の配列 (`[a, b]`)
+    InlineArray(Vec<Expr>),
+    /// 関数呼び出し (`multiline` なら引数を1行に1つずつ書く)
+    Call {
+        callee: String,
+        args: Vec<Expr>,
+        multiline: bool,
+    },
+    /// アロー関数 (`signature` は `<T>(a: A): R` の部分)
+    Arrow {
+        signature: String,
+        body: Box<Body>,
+    },
+    /// 次の行に1段深くして続ける式
+    Indented(Box<Expr>),
+    /// メソッドチェーン (`z.string().min(1).max(10)`)
+    Chain(Box<Expr>, Vec<MethodCall>),
+}
+
+impl Expr {
+    pub fn call(callee: impl Into<String>, args: Vec<Expr>) -> Self {
+        Self::Call {
+            callee: callee.into(),
+            args,
+            multiline: false,
+        }
+    }
+
+    pub fn arrow(signature: impl Into<String>, body: Body) -> Self {
+        Self::Arrow {
+            signature: signature.into(),
+            body: Box::new(body),
+        }
+    }
+
+    /// メソッド呼び出しを1つ続ける
+    pub fn method(self, name: impl Into<String>, args: Vec<Expr>) -> Self {
+        self.chain([MethodCall::new(name, args)])
+    }
+
+    /// メソッド呼び出しを続ける (続けるものがなければそのまま)
+    pub fn chain(self, calls: impl IntoIterator<Item = MethodCall>) -> Self {
+        let mut calls = calls.into_iter().peekable();
+        if calls.peek().is_none() {
+            return self;
+        }
+        match self {
+            Self::Chain(receiver, mut chained) => {
+                chained.extend(calls);
+                Self::Chain(receiver, chained)
+            }
+            expr => Self::Chain(Box::new(expr), calls.collect()),
+        }
+    }
+
+    /// 最後が引数のない `.name()` の呼び出しか
+    pub fn ends_with_call(&self, name: &str) -> bool {
+        match self {
+            Self::Raw(raw) => raw.ends_with(&format!(".{name}()")),
+            Self::Wrap(_, expr, after) if after.is_empty() => expr.ends_with_call(name),
+            Self::Wrap(_, _, after) => after.ends_with(&format!(".{name}()")),
+            Self::Chain(_, calls) => calls
+                .last()
+                .is_some_and(|call| call.name == name && call.args.is_empty()),
+            _ => false,
+        }
+    }
+
+    /// 最後の `.name()` の呼び出しを取り除く (なければそのまま)
+    pub fn without_call(self, name: &str) -> Self {
+        if !self.ends_with_call(name) {
+            return self;
+        }
+        match self {
+            Self::Raw(raw) => Self::Raw(raw[..raw.len() - name.len() - 3].to_string()),
+            Self::Wrap(before, expr, after) if after.is_empty() => {
+                Self::Wrap(before, Box::new(expr.without_call(name)), after)
+            }
+            Self::Wrap(before, expr, after) => Self::Wrap(
+                before,
+                expr,
+                after[..after.len() - name.len() - 3].to_string(),
+            ),
+            Self::Chain(receiver, mut calls) => {
+                calls.pop();
+                receiver.chain(calls)
+            }
+            expr => expr,
+        }
+    }
+
+    /// 式の後ろにメソッドチェーンなどを付ける
+    pub fn suffixed(self, suffix: impl Into<String>) -> Self {
+        let suffix = suffix.into();
+        if suffix.is_empty() {
+            return self;
+        }
+        // メソッドチェーンならプリンタが行の幅に合わせて折り返せるようにする
+        if let Some(calls) = MethodCall::parse_chain(&suffix) {
+            return self.chain(calls);
+        }
+        match self {
+            Self::Raw(raw) => Self::Raw(raw + &suffix),
+            Self::Wrap(before, expr, after) => Self::Wrap(before, expr, after + &suffix),
+            expr => Self::Wrap(String::new(), Box::new(expr), suffix),
+        }
+    }
+}
+
+impl From<String> for Expr {
+    fn from(expr: String) -> Self {
+        Self::Raw(expr)
+    }
+}
+
+impl From<&str> for Expr {
+    fn from(expr: &str) -> Self {
+        Self::Raw(expr.to_string())
+    }
+}
+
+/// メソッドチェーンの呼び出し1つ (`.name(args)`)
+#[derive(Debug, Clone)]
+pub(crate) struct MethodCall {
+    pub name: String,
+    pub args: Vec<Expr>,
+}
+
+impl MethodCall {
+    pub fn new(name: impl Into<String>, args: Vec<Expr>) -> Self {
+        Self {
+            name: name.into(),
+            args,
+        }
+    }
+
+    /// `.min(1).max(10)` のような文字列のメソッドチェーンを呼び出しに分ける
+    ///
+    /// 引数は最上位のカンマで分けた `Expr::Raw` になります。チェーンとして読めなければ `None`。
+    fn parse_chain(code: &str) -> Option<Vec<Self>> {
+        let mut calls = Vec::new();
+        let mut rest = code;
+        while !rest.is_empty() {
+            let (name, after) = rest.strip_prefix('.')?.split_once('(')?;
+            if name.is_empty() || name.contains(|ch: char| ch.is_whitespace() || ch == ')') {
+                return None;
+            }
+            let (args, after) = split_args(after)?;
+            calls.push(Self::new(name, args));
+            rest = after;
+        }
+        Some(calls)
+    }
+}
+
+/// `(` の後ろから対応する `)` までの引数と、その後ろの残り
+///
+/// 文字列・テンプレート・正規表現リテラルの中の括弧やカンマは数えない。
+fn split_args(code: &str) -> Option<(Vec<Expr>, &str)> {
+    let mut args = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut previous = '(';
+    let mut chars = code.char_indices();
+    while let Some((index, ch)) = chars.next() {
+        match ch {
+            '\'' | '"' | '`' => skip_literal(&mut chars, ch)?,
+            '/' if "(,=:[!&|?{};>".contains(previous) => skip_regex(&mut chars)?,
+            '(' | '[' | '{' => depth += 1,
+            ')' if depth == 0 => {
+                let arg = code[start..index].trim();
+                if !arg.is_empty() {
+                    args.push(Expr::Raw(arg.to_string()));
+                } else if !args.is_empty() {
+                    return None;
+                }
+                return Some((args, &code[index + 1..]));
+            }
+            ')' | ']' | '}' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                args.push(Expr::Raw(code[start..index].trim().to_string()));
+                start = index + 1;
+            }
+            _ => {}
+        }
+        if !ch.is_whitespace() {
+            previous = ch;
+        }
+    }
+    None
+}
+
+/// 閉じる `quote` まで読み飛ばす
+fn skip_literal(chars: &mut std::str::CharIndices, quote: char) -> Option<()> {
+    while let Some((_, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next()?;
+            }
+            ch if ch == quote => return Some(()),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 正規表現リテラルの閉じる `/` まで読み飛ばす (`[...]` の中の `/` は区切りではない)
+fn skip_regex(chars: &mut std::str::CharIndices) -> Option<()> {
+    let mut class = false;
+    while let Some((_, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next()?;
+            }
+            '[' => class = true,
+            ']' => class = false,
+            '/' if !class => return Some(()),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Property {
+    pub jsdoc: Option<String>,
+    /// 出力するキー (必要ならクォート済み)
+    pub key: String,
+    pub value: Expr,
+}
+
+impl Property {
+    pub fn new(key: impl Into<String>, value: impl Into<Expr>) -> Self {
+        Self {
+            jsdoc: None,
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// アロー関数の本体
+#[derive(Debug, Clone)]
+pub(crate) enum Body {
+    Expr(Expr),
+    Block(Vec<Stmt>),
+}
+
+/// 文
+#[derive(Debug, Clone)]
+pub(crate) enum Stmt {
+    /// 式文 (`return x`, `throw ...` など。セミコロンはプリンタが付ける)
+    Expr(Expr),
+    /// `head { ... }` (`if (...)`, `switch (...)`)
+    Block { head: String, body: Vec<Stmt> },
+    /// `case label:` と、1段深い本体
+    Case { label: String, body: Vec<Stmt> },
+}
+
+impl Stmt {
+    pub fn expr(expr: impl Into<Expr>) -> Self {
+        Self::Expr(expr.into())
+    }
+
+    pub fn block(head: impl Into<String>, body: Vec<Stmt>) -> Self {
+        Self::Block {
+            head: head.into(),
+            body,
+        }
+    }
+}
@@ -1,29 +1,76 @@
 //! TypeScriptコード生成の主要ロジック
 
-use std::collections::BTreeSet;
+use std::cell::Cell;
+use std::collections::{BTreeSet, HashMap};
 
 use gear_mesh_core::{
-    EnumRepresentation, EnumType, FieldInfo, GearMeshType, NewtypeType, RenameRule, StructType,
-    TypeAttributes, TypeKind, TypeRef, ValidationRule, VariantContent, to_typescript_primitive,
+    BigIntPolicy, DocComment, EnumRepresentation, EnumType, FieldInfo, GearMeshType, IpVersion,
+    NewtypeType, RenameRule, StructType, TypeAttributes, TypeKind, TypeRef, ValidationRule,
+    ValueKind, VariantContent, jsdoc_block, to_typescript_primitive,
 };
 
-use crate::utils::{apply_rename_all, format_property_name, resolve_field_name};
-use crate::{GeneratorConfig, OptionStyle, ResultStyle};
+use crate::branded::{brand_type_params, marker_literal, marker_positions};
+use crate::enums::{EnumCompanions, VariantShape};
+use crate::escape::{js_string, property_key};
+use crate::module_organizer::relative_import;
+use crate::printer::Printer;
+use crate::ts_ast::{
+    Expr, Function, Interface, Item, Member, Param, Stmt, TsType, TypeAlias, UnionMember,
+};
+use crate::utils::{apply_rename_all, apply_type_renames, resolve_field_name, strip_deprecated};
+use crate::validation_gen::{schema_type_params, uses_type_validation};
+use crate::validators::{
+    collect_custom_validators, mentions_identifier, validator_function, validator_imports,
+};
+use crate::{
+    BrandedTypeGenerator, GeneratorConfig, OptionStyle, QuoteStyle, ResultStyle, TypeStyle,
+    ZodVersion,
+};
 
 /// TypeScript生成器
 pub struct TypeScriptGenerator {
     config: GeneratorConfig,
+    module: String,
+    /// 生成中の型・フィールドに適用される整数の表現方法
+    bigint_policy: Cell<Option<BigIntPolicy>>,
+    /// ジェネリックなブランドの型引数のうちマーカーである位置
+    brand_markers: HashMap<String, Vec<bool>>,
+    /// docコメントのリンク先になる型 (Rustの名前 → 出力される名前)
+    doc_links: HashMap<String, String>,
     pub output: String,
 }
 
 impl TypeScriptGenerator {
     pub fn new(config: GeneratorConfig) -> Self {
         Self {
+            bigint_policy: Cell::new(config.bigint_policy),
             config,
+            module: String::new(),
+            brand_markers: HashMap::new(),
+            doc_links: HashMap::new(),
             output: String::new(),
         }
     }
 
+    /// 出力先のモジュール (出力ディレクトリからの相対パス) を設定
+    ///
+    /// 生成コードが参照するメッセージカタログやカスタムバリデータのimportパスに使われます。
+    pub fn with_module(mut self, module: impl Into<String>) -> Self {
+        self.module = module.into();
+        self
+    }
+
+    /// 生成対象以外の型を参照のために登録
+    ///
+    /// ファイルを分けて生成するとき、他のモジュールで定義された `Id<T>` のような
+    /// ジェネリックなブランドへの参照を `Id<"User">` と出力するために使います。
+    pub fn with_type_context(mut self, types: &[GearMeshType]) -> Self {
+        let renamed = apply_type_renames(types);
+        self.brand_markers.extend(marker_positions(&renamed));
+        self.doc_links.extend(doc_link_targets(types));
+        self
+    }
+
     /// 複数の型からTypeScriptコードを生成
     pub fn generate(&mut self, types: &[GearMeshType]) -> String {
         self.generate_with_imports(types, &[])
@@ -35,60 +82,190 @@ impl TypeScriptGenerator {
         extra_imports: &[String],
     ) -> String {
         self.output.clear();
+        let types = &if self.config.omit_deprecated {
+            strip_deprecated(types)
+        } else {
+            types.to_vec()
+        };
+        self.doc_links.extend(doc_link_targets(types));
+        let types = &apply_type_renames(types);
+        self.brand_markers.extend(marker_positions(types));
 
         self.render_prelude(types, extra_imports);
 
-        // Branded Type用のヘルパーを追加
-        if self.config.generate_branded && types.iter().any(|t| t.attributes.branded) {
-            self.output.push_str("// Branded Type helper\n");
-            self.output
-                .push_str("type Brand<T, B> = T & { readonly __brand: B };\n\n");
-        }
-
         // 各型を生成
         for ty in types {
             self.generate_type(ty);
             self.output.push('\n');
         }
 
-        // Zodスキーマを生成
+        // Zodスキーマを生成 (型を推論する場合は各型の宣言として出力済み)
         if self.config.generate_zod {
-            self.output.push_str("// Zod Schemas\n\n");
-            let validator = crate::ValidationGenerator::new(self.config.clone());
-            for ty in types {
-                if let Some(schema) = validator.generate_zod_schema(ty) {
-                    self.output.push_str(&schema);
+            let validator = self.validation_generator();
+            if !self.config.infers_types() {
+                let mut items = vec![Item::Comment("Zod Schemas".to_string()), Item::Blank];
+                for ty in types {
+                    if let Some(schema) = validator.schema_item(ty) {
+                        items.extend([schema, Item::Blank]);
+                    }
+                }
+                self.emit(&items);
+            }
+
+            if self.config.generate_type_assertions && !self.config.infers_types() {
+                let assertions = self.type_assertions(types);
+                if !assertions.is_empty() {
+                    self.emit(&assertions);
                     self.output.push('\n');
                 }
             }
+
+            let functions = types
+                .iter()
+                .filter_map(|ty| validator.validator_function_items(ty, field_error_name(types)))
+                .collect::<Vec<_>>();
+            if !functions.is_empty() {
+                let mut items = vec![Item::Comment("Validators".to_string()), Item::Blank];
+                for function in functions {
+                    items.extend(function);
+                    items.push(Item::Blank);
+                }
+                self.emit(&items);
+            }
         }
 
         self.output.clone()
     }
 
+    /// カスタムバリデータのスタブモジュール (`validators.ts`) を生成
+    ///
+    /// `existing` に既存のファイル内容を渡すと、まだ宣言されていないバリデータだけを
+    /// 末尾に追加します。既存の実装は変更しません。追加するものがなければ `None` を返します。
+    pub fn generate_validator_stubs(
+        &self,
+        types: &[GearMeshType],
+        existing: Option<&str>,
+    ) -> Option<String> {
+        let mut stubs = Vec::new();
+        for (name, uses) in collect_custom_validators(types) {
+            if self.config.custom_validator_imports.contains_key(&name) {
+                continue;
+            }
+            let function = validator_function(&name);
+            if existing.is_some_and(|source| mentions_identifier(source, &function)) {
+                continue;
+            }
+
+            let mut value_types = Vec::new();
+            let mut owners = Vec::new();
+            let mut nullable = false;
+            for validator_use in &uses {
+                let ty = self.type_ref_to_typescript(validator_use.value);
+                if !value_types.contains(&ty) {
+                    value_types.push(ty);
+                }
+                if !owners.contains(&validator_use.owner) {
+                    owners.push(validator_use.owner.clone());
+                }
+                nullable |= validator_use.nullable;
+            }
+            let mut value_type: Vec<TsType> = value_types.into_iter().map(TsType::from).collect();
+            if nullable {
+                value_type.extend(["null".into(), "undefined".into()]);
+            }
+
+            let mut lines = vec![format!("Custom validator `{name}`."), String::new()];
+            lines.extend(owners.iter().map(|owner| format!("Used by `{owner}`.")));
+            let error = Expr::call(
+                "new Error",
+                vec![Expr::Str(format!("{function} is not implemented"))],
+            );
+            stubs.push([
+                Item::Doc(jsdoc_block("", &lines)),
+                Item::Function(Function {
+                    export: true,
+                    name: function.clone(),
+                    type_params: String::new(),
+                    params: vec![Param::new("_value", TsType::Or(value_type))],
+                    multiline: false,
+                    return_type: "boolean".to_string(),
+                    body: vec![Stmt::Expr(Expr::Wrap(
+                        "throw ".to_string(),
+                        Box::new(error),
+                        String::new(),
+                    ))],
+                }),
+            ]);
+        }
+
+        if stubs.is_empty() {
+            return None;
+        }
+        let mut items = match existing {
+            Some(source) => vec![Item::Raw(source.trim_end().to_string())],
+            None => vec![
+                Item::Comment("Custom validators referenced by the generated schemas.".to_string()),
+                Item::Comment(
+                    "Implement each function; gear-mesh only appends stubs for new validators."
+                        .to_string(),
+                ),
+            ],
+        };
+        for stub in stubs {
+            items.push(Item::Blank);
+            items.extend(stub);
+        }
+        Some(Printer::new(&self.config).print(&items))
+    }
+
     /// 単一の型を生成
     pub fn generate_type(&mut self, ty: &GearMeshType) {
+        let mut items = Vec::new();
         // JSDoc生成
+        let source = self.source_link(ty.attributes.source.as_ref().map(|s| (&s.file, s.line)));
         if self.config.generate_jsdoc
-            && let Some(ref docs) = ty.docs
+            && let Some(docs) = ty
+                .docs
+                .clone()
+                .or_else(|| source.as_ref().map(|_| DocComment::empty()))
         {
-            if self.config.enhanced_jsdoc {
-                self.output
-                    .push_str(&render_type_jsdoc(docs, &ty.name, self.config.generate_zod));
+            let docs = &self.link_docs(&docs);
+            let mut jsdoc = if self.config.enhanced_jsdoc {
+                let schema_ref = self.config.generate_zod && !self.config.infers_types();
+                render_type_jsdoc(docs, &ty.name, schema_ref)
             } else {
-                self.output.push_str(&docs.to_jsdoc());
+                docs.to_jsdoc()
+            };
+            if let Some(source) = &source {
+                jsdoc = append_jsdoc_tag(&jsdoc, "", source);
             }
-            self.output.push('\n');
+            items.push(Item::Doc(jsdoc));
         }
 
+        let previous = self
+            .bigint_policy
+            .replace(ty.attributes.bigint_policy().or(self.config.bigint_policy));
+        let previous_style = ty
+            .attributes
+            .option_style
+            .map(|style| std::mem::replace(&mut self.config.option_style, style));
+        let infer = self.config.infers_types();
+        if infer {
+            items.extend(self.generate_inferred_type(ty));
+        }
         match &ty.kind {
-            TypeKind::Struct(s) => self.generate_struct(&ty.name, s, &ty.generics, &ty.attributes),
-            TypeKind::Enum(e) => self.generate_enum(&ty.name, e, &ty.generics, &ty.attributes),
+            TypeKind::Struct(_) if infer => {}
+            TypeKind::Struct(s) => {
+                items.push(self.generate_struct(&ty.name, s, &ty.generics, &ty.attributes))
+            }
+            TypeKind::Enum(e) => {
+                items.extend(self.generate_enum(&ty.name, e, &ty.generics, &ty.attributes))
+            }
             TypeKind::Newtype(n) => {
-                if ty.attributes.branded {
-                    self.generate_branded_type(&ty.name, n);
-                } else {
-                    self.generate_type_alias(&ty.name, n);
+                if ty.attributes.branded && self.config.generate_branded {
+                    items.extend(self.generate_branded_type(ty, n));
+                } else if !infer {
+                    items.push(self.generate_type_alias(ty, n));
                 }
             }
             TypeKind::Primitive(_) | TypeKind::Tuple(_) | TypeKind::Array(_) => {
@@ -96,195 +273,490 @@ impl TypeScriptGenerator {
             }
             _ => {}
         }
+        self.bigint_policy.set(previous);
+        if let Some(style) = previous_style {
+            self.config.option_style = style;
+        }
+        self.emit(&items);
+    }
+
+    /// 宣言を書式に従って出力に追加
+    fn emit(&mut self, items: &[Item]) {
+        let printed = Printer::new(&self.config).print(items);
+        self.output.push_str(&printed);
+    }
+
+    /// Zodスキーマと、そこから推論した型を生成
+    ///
+    /// 続けて `generate_type` が列挙型やBranded Typeのコンパニオンだけを出力します。
+    fn generate_inferred_type(&self, ty: &GearMeshType) -> Vec<Item> {
+        let Some(schema) = self.validation_generator().schema_item(ty) else {
+            return Vec::new();
+        };
+
+        let export = ty.attributes.export;
+        let name = &ty.name;
+        // ジェネリックな型は型引数を渡したファクトリの戻り値から推論する
+        let (params, target) = match &ty.kind {
+            TypeKind::Newtype(newtype) if newtype.is_generic_brand() => (
+                brand_type_params(ty, newtype),
+                format!(
+                    "ReturnType<typeof {name}Schema<{}>>",
+                    newtype.markers.join(", ")
+                ),
+            ),
+            _ => {
+                let params = schema_type_params(ty);
+                let target = self.schema_type_target(name, &params);
+                if params.is_empty() {
+                    (String::new(), target)
+                } else {
+                    (format!("<{}>", params.join(", ")), target)
+                }
+            }
+        };
+        let alias = |name: String, ty: String| {
+            Item::TypeAlias(TypeAlias {
+                export,
+                name,
+                params: params.clone(),
+                ty: ty.into(),
+            })
+        };
+        match self.config.type_style {
+            TypeStyle::InputOutput => vec![
+                schema,
+                alias(format!("{name}Input"), format!("z.input<{target}>")),
+                alias(name.clone(), format!("z.output<{target}>")),
+            ],
+            _ => vec![schema, alias(name.clone(), format!("z.infer<{target}>"))],
+        }
+    }
+
+    /// `z.infer` に渡すスキーマの型 (ジェネリックな型は `args` を型引数にしたファクトリの戻り値)
+    fn schema_type_target(&self, name: &str, args: &[String]) -> String {
+        if args.is_empty() {
+            return format!("typeof {name}Schema");
+        }
+        let schema_types = args
+            .iter()
+            .map(|arg| match self.config.zod_version {
+                ZodVersion::V3 => format!("z.ZodType<{arg}>"),
+                ZodVersion::V4 => format!("z.ZodType<{arg}, {arg}>"),
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "ReturnType<typeof {name}Schema<{}>>",
+            schema_types.join(", ")
+        )
+    }
+
+    /// インターフェースとZodスキーマの型が一致することを `tsc` で検査する型
+    ///
+    /// Branded TypeはZodのブランド型、TS enumはenumそのものから推論されるので、
+    /// どちらも宣言した型と一致します。
+    fn type_assertions(&self, types: &[GearMeshType]) -> Vec<Item> {
+        let assertions = types
+            .iter()
+            .filter(|ty| {
+                matches!(
+                    ty.kind,
+                    TypeKind::Struct(_) | TypeKind::Enum(_) | TypeKind::Newtype(_)
+                )
+            })
+            .map(|ty| {
+                let args = vec!["unknown".to_string(); schema_type_params(ty).len()];
+                let declared = if args.is_empty() {
+                    ty.name.clone()
+                } else {
+                    format!("{}<{}>", ty.name, args.join(", "))
+                };
+                Item::TypeAlias(TypeAlias {
+                    export: ty.attributes.export,
+                    name: format!("_Assert{}", ty.name),
+                    params: String::new(),
+                    ty: format!(
+                        "Expect<Equal<{declared}, z.infer<{}>>>",
+                        self.schema_type_target(&ty.name, &args)
+                    )
+                    .into(),
+                })
+            })
+            .collect::<Vec<_>>();
+        if assertions.is_empty() {
+            return Vec::new();
+        }
+        let helper = |name: &str, params: &str, ty: &str| {
+            Item::TypeAlias(TypeAlias {
+                export: false,
+                name: name.to_string(),
+                params: params.to_string(),
+                ty: ty.into(),
+            })
+        };
+        let mut items = vec![
+            Item::Comment("Type Assertions".to_string()),
+            Item::Blank,
+            helper(
+                "Equal",
+                "<A, B>",
+                "(<T>() => T extends A ? 1 : 2) extends (<T>() => T extends B ? 1 : 2) ? true : false",
+            ),
+            helper("Expect", "<T extends true>", "T"),
+            Item::Blank,
+        ];
+        items.extend(assertions);
+        items
+    }
+
+    fn validation_generator(&self) -> crate::ValidationGenerator {
+        crate::ValidationGenerator::new(self.config.clone())
+            .with_brand_markers(self.brand_markers.clone())
+            .with_doc_links(self.doc_links.clone())
     }
 
     /// 構造体を生成
     fn generate_struct(
-        &mut self,
+        &self,
         name: &str,
         struct_type: &StructType,
         generics: &[gear_mesh_core::GenericParam],
         attrs: &TypeAttributes,
-    ) {
-        let generic_str = if generics.is_empty() {
-            String::new()
-        } else {
-            format!(
-                "<{}>",
-                generics
-                    .iter()
-                    .map(|g| g.name.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            )
-        };
-
-        self.output
-            .push_str(&format!("export interface {}{} {{\n", name, generic_str));
-
-        for field in &struct_type.fields {
-            self.generate_field(field, attrs.serde.rename_all);
-        }
-
-        self.output.push_str("}\n");
+    ) -> Item {
+        let source_file = attrs.source.as_ref().map(|source| source.file.as_str());
+        Item::Interface(Interface {
+            export: attrs.export,
+            name: name.to_string(),
+            params: generic_params(generics),
+            members: struct_type
+                .fields
+                .iter()
+                .filter(|f| !f.attributes.skip)
+                .map(|field| self.generate_field(field, attrs.serde.rename_all, source_file))
+                .collect(),
+        })
     }
 
     /// フィールドを生成
-    fn generate_field(&mut self, field: &FieldInfo, rename_all: Option<RenameRule>) {
-        let indent = &self.config.indent;
-
+    fn generate_field(
+        &self,
+        field: &FieldInfo,
+        rename_all: Option<RenameRule>,
+        source_file: Option<&str>,
+    ) -> Member {
         // フィールドのJSDoc
-        let field_name = format_property_name(&resolve_field_name(field, rename_all));
-        let optional = if self.is_optional_field(field) {
-            "?"
+        let field_name = property_key(&resolve_field_name(field, rename_all), self.quote());
+        let optional = self.is_optional_field(field);
+        let ts_type = self.with_field_policy(field, |this| this.field_type_to_typescript(field));
+
+        let source = self.source_link(source_file.zip(field.attributes.line));
+        let jsdoc = if self.config.generate_jsdoc
+            && let Some(docs) = field
+                .docs
+                .clone()
+                .or_else(|| source.as_ref().map(|_| DocComment::empty()))
+        {
+            let mut jsdoc = self.field_jsdoc(field, &docs, &ts_type, optional);
+            if let Some(source) = &source {
+                jsdoc = append_jsdoc_tag(&jsdoc, "", source);
+            }
+            Some(jsdoc).filter(|jsdoc| !jsdoc.trim().is_empty())
         } else {
-            ""
+            None
         };
-        let ts_type = self.field_type_to_typescript(field);
 
-        if self.config.generate_jsdoc
-            && let Some(ref docs) = field.docs
-        {
-            if self.config.enhanced_jsdoc {
-                self.output.push_str(&render_field_jsdoc(
-                    indent,
-                    docs,
-                    &ts_type,
-                    optional == "?",
-                    &field.validations,
-                ));
-                self.output.push('\n');
-            } else {
-                self.output
-                    .push_str(&format!("{}{}\n", indent, docs.to_inline_jsdoc()));
-            }
+        Member {
+            jsdoc,
+            readonly: field.attributes.readonly,
+            name: field_name,
+            optional,
+            ty: ts_type.into(),
         }
-
-        self.output.push_str(&format!(
-            "{}{}{}: {};\n",
-            indent, field_name, optional, ts_type
-        ));
     }
 
     /// 列挙型を生成
     fn generate_enum(
-        &mut self,
+        &self,
         name: &str,
         enum_type: &EnumType,
         generics: &[gear_mesh_core::GenericParam],
         attrs: &TypeAttributes,
-    ) {
-        let generic_str = if generics.is_empty() {
-            String::new()
-        } else {
-            format!(
-                "<{}>",
-                generics
-                    .iter()
-                    .map(|g| g.name.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            )
-        };
-
-        // ユニオン型として生成
-        let variants: Vec<String> = enum_type
+    ) -> Vec<Item> {
+        let variants: Vec<VariantShape> = enum_type
             .variants
             .iter()
-            .map(|v| self.generate_variant(name, v, &enum_type.representation, attrs))
+            .map(|v| self.generate_variant(v, &enum_type.representation, attrs))
             .collect();
-
-        self.output.push_str(&format!(
-            "export type {}{} = {};\n",
+        let companions = EnumCompanions {
+            config: &self.config,
             name,
-            generic_str,
-            variants.join(" | ")
-        ));
+            generics,
+            repr: &enum_type.representation,
+            export: attrs.export,
+            variants: &variants,
+        };
+
+        // ユニオン型として生成 (`enum_style` の指定があればTS enumなど)
+        // 型をスキーマから推論する場合はコンパニオンだけを出力する
+        let mut items = if self.config.infers_types() {
+            Vec::new()
+        } else {
+            companions.declaration().unwrap_or_else(|| {
+                // 1行に1メンバー。バリアントのドキュメントはメンバーの前に置く
+                vec![Item::TypeAlias(TypeAlias {
+                    export: attrs.export,
+                    name: name.to_string(),
+                    params: generic_params(generics),
+                    ty: TsType::Union(
+                        variants
+                            .iter()
+                            .map(|variant| UnionMember {
+                                jsdoc: variant.jsdoc.clone(),
+                                ty: variant.declaration.clone(),
+                            })
+                            .collect(),
+                    ),
+                })]
+            })
+        };
+        items.extend(companions.values());
+        items.extend(companions.guards().into_iter().flatten());
+        items.extend(companions.match_helper());
+        items
     }
 
     /// 列挙型バリアントを生成
     fn generate_variant(
         &self,
-        _enum_name: &str,
         variant: &gear_mesh_core::EnumVariant,
         repr: &EnumRepresentation,
         attrs: &TypeAttributes,
-    ) -> String {
+    ) -> VariantShape {
         let variant_name = apply_rename_all(&variant.name, attrs.serde.rename_all);
-        match (&variant.content, repr) {
-            (VariantContent::Unit, EnumRepresentation::External) => {
-                format!("\"{}\"", variant_name)
-            }
-            (VariantContent::Unit, EnumRepresentation::Internal { tag }) => {
-                format!("{{ {}: \"{}\" }}", tag, variant_name)
-            }
-            (VariantContent::Tuple(types), EnumRepresentation::External) => {
-                if types.len() == 1 {
-                    let inner = self.type_ref_to_typescript(&types[0]);
-                    format!("{{ \"{}\": {} }}", variant_name, inner)
-                } else {
-                    let inner: Vec<_> = types
-                        .iter()
-                        .map(|t| self.type_ref_to_typescript(t))
-                        .collect();
-                    format!("{{ \"{}\": [{}] }}", variant_name, inner.join(", "))
-                }
+        let fields = |fields: &[FieldInfo]| {
+            fields
+                .iter()
+                .filter_map(|f| self.variant_field(f))
+                .collect::<Vec<_>>()
+                .join("; ")
+        };
+        // バリアントが運ぶ値 (外部タグ・隣接タグでの中身)
+        let payload = match &variant.content {
+            VariantContent::Unit => None,
+            VariantContent::Tuple(types) if types.len() == 1 => {
+                Some(self.type_ref_to_typescript(&types[0]))
             }
-            (VariantContent::Struct(fields), EnumRepresentation::External) => {
-                let field_strs: Vec<_> = fields
+            VariantContent::Tuple(types) => {
+                let inner: Vec<_> = types
                     .iter()
-                    .map(|f| {
-                        let ts_type = self.type_ref_to_typescript(&f.ty);
-                        let field_name = format_property_name(&resolve_field_name(f, None));
-                        format!("{}: {}", field_name, ts_type)
-                    })
+                    .map(|t| self.type_ref_to_typescript(t))
                     .collect();
-                format!(
-                    "{{ \"{}\": {{ {} }} }}",
-                    variant_name,
-                    field_strs.join("; ")
-                )
+                Some(format!("[{}]", inner.join(", ")))
             }
-            (VariantContent::Struct(fields), EnumRepresentation::Internal { tag }) => {
-                let field_strs: Vec<_> = fields
-                    .iter()
-                    .map(|f| {
-                        let ts_type = self.type_ref_to_typescript(&f.ty);
-                        let field_name = format_property_name(&resolve_field_name(f, None));
-                        format!("{}: {}", field_name, ts_type)
-                    })
-                    .collect();
+            VariantContent::Struct(struct_fields) => {
+                Some(format!("{{ {} }}", fields(struct_fields)))
+            }
+        };
+        let quote = self.quote();
+        let key = |name: &str| property_key(name, quote);
+        let tag_value = js_string(&variant_name, quote);
+
+        let ts_type = match (&variant.content, repr, &payload) {
+            (_, EnumRepresentation::External, None) => tag_value.clone(),
+            (_, EnumRepresentation::External, Some(payload)) => {
+                format!("{{ {}: {} }}", tag_value, payload)
+            }
+            (_, EnumRepresentation::Internal { tag }, None)
+            | (_, EnumRepresentation::Adjacent { tag, .. }, None) => {
+                format!("{{ {}: {} }}", key(tag), tag_value)
+            }
+            (VariantContent::Struct(struct_fields), EnumRepresentation::Internal { tag }, _) => {
                 format!(
-                    "{{ {}: \"{}\"; {} }}",
-                    tag,
-                    variant_name,
-                    field_strs.join("; ")
+                    "{{ {}: {}; {} }}",
+                    key(tag),
+                    tag_value,
+                    fields(struct_fields)
                 )
             }
-            _ => format!("\"{}\"", variant_name),
+            // serdeの内部タグはnewtypeバリアントの中身にタグを追加する
+            (VariantContent::Tuple(types), EnumRepresentation::Internal { tag }, Some(payload))
+                if types.len() == 1 =>
+            {
+                format!("{{ {}: {} }} & {}", key(tag), tag_value, payload)
+            }
+            (_, EnumRepresentation::Adjacent { tag, content }, Some(payload)) => format!(
+                "{{ {}: {}; {}: {} }}",
+                key(tag),
+                tag_value,
+                key(content),
+                payload
+            ),
+            (_, EnumRepresentation::Untagged, None) => "null".to_string(),
+            (_, EnumRepresentation::Untagged, Some(payload)) => payload.clone(),
+            // 内部タグでは複数要素のタプルバリアントを表せない
+            _ => tag_value.clone(),
+        };
+        // 内部タグではタグを含むオブジェクト全体をハンドラに渡す
+        let payload = match repr {
+            EnumRepresentation::Internal { .. } => payload.map(|_| ts_type.clone()),
+            _ => payload,
+        };
+
+        // フィールドにドキュメントがある構造体バリアントは複数行で宣言する
+        let declaration = match &variant.content {
+            VariantContent::Struct(struct_fields) => {
+                let tag_member = |tag: &str| Member::new(key(tag), tag_value.clone());
+                match repr {
+                    EnumRepresentation::External => self
+                        .struct_variant_block(struct_fields, None)
+                        .map(|block| TsType::Inline(vec![Member::new(tag_value.clone(), block)])),
+                    EnumRepresentation::Internal { tag } => {
+                        self.struct_variant_block(struct_fields, Some(tag_member(tag)))
+                    }
+                    EnumRepresentation::Adjacent { tag, content } => {
+                        self.struct_variant_block(struct_fields, None).map(|block| {
+                            TsType::Inline(vec![tag_member(tag), Member::new(key(content), block)])
+                        })
+                    }
+                    EnumRepresentation::Untagged => self.struct_variant_block(struct_fields, None),
+                }
+            }
+            _ => None,
+        }
+        .unwrap_or_else(|| ts_type.clone().into());
+        let jsdoc = variant
+            .docs
+            .as_ref()
+            .filter(|_| self.config.generate_jsdoc)
+            .map(|docs| self.link_docs(docs).to_inline_jsdoc())
+            .filter(|jsdoc| !jsdoc.is_empty());
+
+        VariantShape {
+            name: variant.name.clone(),
+            tag: variant_name,
+            ts_type,
+            declaration,
+            jsdoc,
+            payload,
         }
     }
 
-    /// Branded Typeを生成
-    fn generate_branded_type(&mut self, name: &str, newtype: &NewtypeType) {
-        let inner_type = self.type_ref_to_typescript(&newtype.inner);
-        self.output.push_str(&format!(
-            "export type {} = Brand<{}, \"{}\">;\n",
-            name, inner_type, name
-        ));
+    /// 構造体バリアントのフィールド (`skip` されたフィールドは `None`)
+    fn variant_field(&self, field: &FieldInfo) -> Option<String> {
+        if field.attributes.skip {
+            return None;
+        }
+        Some(format!(
+            "{}{}{}: {}",
+            if field.attributes.readonly {
+                "readonly "
+            } else {
+                ""
+            },
+            property_key(&resolve_field_name(field, None), self.quote()),
+            if field.attributes.optional { "?" } else { "" },
+            self.variant_field_type(field)
+        ))
+    }
 
-        // ヘルパー関数を生成
-        self.output.push_str(&format!(
-            "export const {} = (value: {}): {} => value as {};\n",
-            name, inner_type, name, name
-        ));
+    fn variant_field_type(&self, field: &FieldInfo) -> String {
+        let ts_type = match &field.attributes.ts_type {
+            Some(ts_type) => ts_type.clone(),
+            None => self.with_field_policy(field, |this| this.type_ref_to_typescript(&field.ty)),
+        };
+        if field.attributes.nullable {
+            with_null(ts_type)
+        } else {
+            ts_type
+        }
     }
 
-    /// 通常のtype aliasを生成
-    fn generate_type_alias(&mut self, name: &str, newtype: &NewtypeType) {
+    /// ドキュメント付きのフィールドを持つ構造体バリアントの複数行のオブジェクト型
+    ///
+    /// `leading` は内部タグのように先頭に置くプロパティです。ドキュメントがなければ `None`。
+    fn struct_variant_block(
+        &self,
+        fields: &[FieldInfo],
+        leading: Option<Member>,
+    ) -> Option<TsType> {
+        let fields = fields
+            .iter()
+            .filter(|field| !field.attributes.skip)
+            .collect::<Vec<_>>();
+        if !self.config.generate_jsdoc || fields.iter().all(|field| field.docs.is_none()) {
+            return None;
+        }
+
+        let mut members = Vec::from_iter(leading);
+        for field in fields {
+            let ts_type = self.variant_field_type(field);
+            members.push(Member {
+                jsdoc: field
+                    .docs
+                    .as_ref()
+                    .map(|docs| self.field_jsdoc(field, docs, &ts_type, field.attributes.optional))
+                    .filter(|jsdoc| !jsdoc.is_empty()),
+                readonly: field.attributes.readonly,
+                name: property_key(&resolve_field_name(field, None), self.quote()),
+                optional: field.attributes.optional,
+                ty: ts_type.into(),
+            });
+        }
+        Some(TsType::Block(members))
+    }
+
+    /// フィールドのJSDoc (`enhanced_jsdoc` なら型やバリデーションのタグ付き)
+    fn field_jsdoc(
+        &self,
+        field: &FieldInfo,
+        docs: &DocComment,
+        ts_type: &str,
+        optional: bool,
+    ) -> String {
+        let docs = &self.link_docs(docs);
+        if self.config.enhanced_jsdoc {
+            render_field_jsdoc(docs, ts_type, optional, &field.validations, &field.ty)
+        } else {
+            docs.to_inline_jsdoc()
+        }
+    }
+
+    /// `with_source_links` が有効なら `@see src/user.rs:42`
+    fn source_link(&self, location: Option<(impl AsRef<str>, u32)>) -> Option<String> {
+        let (file, line) = location.filter(|_| self.config.generate_source_links)?;
+        Some(format!("@see {}:{line}", file.as_ref()))
+    }
+
+    /// intra-docリンクを生成対象の型への `{@link X}` に置き換える
+    fn link_docs(&self, docs: &DocComment) -> DocComment {
+        docs.with_links(|name| self.doc_links.get(name).cloned())
+    }
+
+    /// Branded Typeを生成 (型・コンストラクタ・型ガード)
+    fn generate_branded_type(&self, ty: &GearMeshType, newtype: &NewtypeType) -> Vec<Item> {
         let inner_type = self.type_ref_to_typescript(&newtype.inner);
-        self.output
-            .push_str(&format!("export type {} = {};\n", name, inner_type));
+        BrandedTypeGenerator::new(self.config.clone())
+            .items(ty, &inner_type)
+            .unwrap_or_default()
+    }
+
+    /// 通常のtype aliasを生成
+    fn generate_type_alias(&self, ty: &GearMeshType, newtype: &NewtypeType) -> Item {
+        Item::TypeAlias(TypeAlias {
+            export: ty.attributes.export,
+            name: ty.name.clone(),
+            params: brand_type_params(ty, newtype),
+            ty: self.type_ref_to_typescript(&newtype.inner).into(),
+        })
+    }
+
+    /// フィールドに指定された整数の表現方法を適用して変換
+    fn with_field_policy<R>(&self, field: &FieldInfo, f: impl FnOnce(&Self) -> R) -> R {
+        let policy = field.attributes.bigint.or(self.bigint_policy.get());
+        let previous = self.bigint_policy.replace(policy);
+        let result = f(self);
+        self.bigint_policy.set(previous);
+        result
     }
 
     /// TypeRefからTypeScript型文字列へ変換
@@ -293,6 +765,19 @@ impl TypeScriptGenerator {
             return transformed;
         }
 
+        if let Some(policy) = self
+            .bigint_policy
+            .get()
+            .filter(|policy| policy.applies_to(&type_ref.name))
+        {
+            return match policy {
+                BigIntPolicy::BigInt => "bigint",
+                BigIntPolicy::Number => "number",
+                BigIntPolicy::String => "string",
+            }
+            .to_string();
+        }
+
         if let Some(primitive) =
             to_typescript_primitive(type_ref.name.as_str(), self.config.use_bigint)
         {
@@ -365,10 +850,18 @@ impl TypeScriptGenerator {
                 if type_ref.generics.is_empty() {
                     type_ref.name.clone()
                 } else {
+                    let markers = self.brand_markers.get(&type_ref.name);
                     let generics: Vec<_> = type_ref
                         .generics
                         .iter()
-                        .map(|t| self.type_ref_to_typescript(t))
+                        .enumerate()
+                        .map(|(index, t)| {
+                            if markers.is_some_and(|m| m.get(index) == Some(&true)) {
+                                marker_literal(t, self.quote())
+                            } else {
+                                self.type_ref_to_typescript(t)
+                            }
+                        })
                         .collect();
                     format!("{}<{}>", type_ref.name, generics.join(", "))
                 }
@@ -376,6 +869,10 @@ impl TypeScriptGenerator {
         }
     }
 
+    fn quote(&self) -> QuoteStyle {
+        self.config.format.quote_style
+    }
+
     fn wrap_option_type(&self, inner: String) -> String {
         match self.config.option_style {
             OptionStyle::Nullable => format!("{} | null", inner),
@@ -385,6 +882,9 @@ impl TypeScriptGenerator {
     }
 
     fn is_optional_field(&self, field: &FieldInfo) -> bool {
+        if field.attributes.optional {
+            return true;
+        }
         if field.ty.name != "Option" || !field.optional {
             return field.optional;
         }
@@ -396,15 +896,28 @@ impl TypeScriptGenerator {
     }
 
     fn field_type_to_typescript(&self, field: &FieldInfo) -> String {
-        if field.ty.name != "Option" || field.ty.generics.is_empty() {
-            return self.type_ref_to_typescript(&field.ty);
-        }
+        let fixed = match (&field.attributes.literal, &field.attributes.template) {
+            (Some(literal), _) => Some(literal.to_typescript(self.quote())),
+            (None, Some(template)) => Some(format!("`{template}`")),
+            (None, None) => None,
+        };
+        let ts_type = if let Some(ts_type) = &field.attributes.ts_type {
+            ts_type.clone()
+        } else if field.ty.name != "Option" || field.ty.generics.is_empty() {
+            fixed.unwrap_or_else(|| self.type_ref_to_typescript(&field.ty))
+        } else {
+            let inner = fixed.unwrap_or_else(|| self.type_ref_to_typescript(&field.ty.generics[0]));
+            match self.config.option_style {
+                OptionStyle::Nullable => format!("{} | null", inner),
+                OptionStyle::Optional => inner,
+                OptionStyle::Both => format!("{} | null", inner),
+            }
+        };
 
-        let inner = self.type_ref_to_typescript(&field.ty.generics[0]);
-        match self.config.option_style {
-            OptionStyle::Nullable => format!("{} | null", inner),
-            OptionStyle::Optional => inner,
-            OptionStyle::Both => format!("{} | null", inner),
+        if field.attributes.nullable {
+            with_null(ts_type)
+        } else {
+            ts_type
         }
     }
 
@@ -433,8 +946,46 @@ impl TypeScriptGenerator {
     fn render_prelude(&mut self, types: &[GearMeshType], extra_imports: &[String]) {
         let mut imports = BTreeSet::new();
 
+        if self.config.generate_branded
+            && !self.config.infers_types()
+            && types.iter().any(is_branded_newtype)
+        {
+            imports.insert(format!(
+                "import type {{ Brand }} from '{}';",
+                relative_import(&self.module, crate::BRAND_MODULE)
+            ));
+        }
+
+        // ルールを検査するコード (Zodスキーマと、Branded Typeのコンストラクタ)
+        let validated = types
+            .iter()
+            .filter(|ty| {
+                self.config.generate_zod || (self.config.generate_branded && is_branded_newtype(ty))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        if crate::messages::uses_message_keys(&validated) {
+            imports.insert(format!(
+                "import {{ gearMeshMessage }} from '{}';",
+                relative_import(&self.module, crate::MESSAGES_MODULE)
+            ));
+        }
+        imports.extend(validator_imports(
+            collect_custom_validators(&validated).keys(),
+            &self.module,
+            &self.config,
+        ));
+
         if self.config.generate_zod {
             imports.insert("import { z } from 'zod';".to_string());
+            if uses_type_validation(types) {
+                let module = relative_import(&self.module, crate::VALIDATION_MODULE);
+                imports.insert(match field_error_name(types) {
+                    "FieldError" => format!("import type {{ FieldError }} from '{module}';"),
+                    alias => format!("import type {{ FieldError as {alias} }} from '{module}';"),
+                });
+                imports.insert(format!("import {{ toFieldErrors }} from '{module}';"));
+            }
         }
 
         for transformer in &self.config.transformers {
@@ -452,9 +1003,12 @@ impl TypeScriptGenerator {
         }
 
         if !imports.is_empty() {
-            self.output
-                .push_str(&imports.into_iter().collect::<Vec<_>>().join("\n"));
-            self.output.push_str("\n\n");
+            let mut items = imports
+                .iter()
+                .map(|import| Item::import(import))
+                .collect::<Vec<_>>();
+            items.push(Item::Blank);
+            self.emit(&items);
         }
     }
 
@@ -467,6 +1021,28 @@ impl TypeScriptGenerator {
     }
 }
 
+/// `null` を許容する型 (すでに含まれている場合はそのまま)
+fn with_null(ts_type: String) -> String {
+    if ts_type.split('|').any(|member| member.trim() == "null") {
+        ts_type
+    } else {
+        format!("{ts_type} | null")
+    }
+}
+
+/// 宣言の型引数 (`<T, U>`。なければ空)
+fn generic_params(generics: &[gear_mesh_core::GenericParam]) -> String {
+    if generics.is_empty() {
+        return String::new();
+    }
+    let names = generics.iter().map(|g| g.name.as_str()).collect::<Vec<_>>();
+    format!("<{}>", names.join(", "))
+}
+
+fn is_branded_newtype(ty: &GearMeshType) -> bool {
+    ty.attributes.branded && matches!(ty.kind, TypeKind::Newtype(_))
+}
+
 fn wrap_array_element_type(inner: String) -> String {
     if inner.contains('|') || inner.contains('&') {
         format!("({inner})")
@@ -475,6 +1051,15 @@ fn wrap_array_element_type(inner: String) -> String {
     }
 }
 
+/// 生成対象に `gear_mesh::FieldError` 自体が含まれる場合は別名で参照する
+fn field_error_name(types: &[GearMeshType]) -> &'static str {
+    if types.iter().any(|ty| ty.name == "FieldError") {
+        "GearMeshFieldError"
+    } else {
+        "FieldError"
+    }
+}
+
 fn contains_transformer_type(
     ty: &GearMeshType,
     transformer: &dyn gear_mesh_core::TypeTransformer,
@@ -509,75 +1094,74 @@ fn has_transformer_type(
             .any(|inner| has_transformer_type(inner, transformer))
 }
 
-fn render_type_jsdoc(
-    docs: &gear_mesh_core::DocComment,
-    name: &str,
-    include_schema_ref: bool,
-) -> String {
-    let mut lines = vec!["/**".to_string()];
-
-    if !docs.summary.is_empty() {
-        lines.push(format!(" * {}", docs.summary));
+/// JSDocの末尾にタグを追加する (空なら単一行のJSDocを作る)
+fn append_jsdoc_tag(jsdoc: &str, indent: &str, tag: &str) -> String {
+    let trimmed = jsdoc.trim_start();
+    if trimmed.is_empty() {
+        return format!("{indent}/** {tag} */");
     }
-
-    if let Some(description) = &docs.description {
-        lines.push(" *".to_string());
-        for line in description.lines() {
-            lines.push(format!(" * {}", line));
-        }
+    match jsdoc.rsplit_once('\n') {
+        // 複数行: 閉じる行の前に追加
+        Some((body, close)) => format!("{body}\n{indent} * {tag}\n{close}"),
+        None => match jsdoc.strip_suffix(" */") {
+            Some(body) => format!("{body} {tag} */"),
+            None => format!("{jsdoc}\n{indent}/** {tag} */"),
+        },
     }
+}
 
-    lines.push(" *".to_string());
-    lines.push(" * @remarks".to_string());
-    lines.push(" * This type is automatically generated from Rust.".to_string());
-    lines.push(" * Do not modify manually.".to_string());
+/// エクスポートされる型の名前 (Rustの名前 → 出力される名前)
+fn doc_link_targets(types: &[GearMeshType]) -> impl Iterator<Item = (String, String)> + '_ {
+    types.iter().filter(|ty| ty.attributes.export).map(|ty| {
+        let name = ty.attributes.rename.clone();
+        (ty.name.clone(), name.unwrap_or_else(|| ty.name.clone()))
+    })
+}
+
+fn render_type_jsdoc(docs: &DocComment, name: &str, include_schema_ref: bool) -> String {
+    let mut lines = docs.jsdoc_body();
+    lines.push(String::new());
+    lines.extend(docs.jsdoc_tags());
+    lines.push("@remarks".to_string());
+    lines.push("This type is automatically generated from Rust.".to_string());
+    lines.push("Do not modify manually.".to_string());
 
     if include_schema_ref {
         lines.push(format!(
-            " * @see {{@link {}Schema}} for runtime validation",
+            "@see {{@link {}Schema}} for runtime validation",
             name
         ));
     }
 
-    lines.push(" */".to_string());
-    lines.join("\n")
+    jsdoc_block("", &lines)
 }
 
 fn render_field_jsdoc(
-    indent: &str,
-    docs: &gear_mesh_core::DocComment,
+    docs: &DocComment,
     ts_type: &str,
     optional: bool,
     validations: &[ValidationRule],
+    field_type: &TypeRef,
 ) -> String {
-    let mut lines = vec![format!("{indent}/**")];
-
-    if !docs.summary.is_empty() {
-        lines.push(format!("{indent} * {}", docs.summary));
-    }
-
-    if let Some(description) = &docs.description {
-        lines.push(format!("{indent} *"));
-        for line in description.lines() {
-            lines.push(format!("{indent} * {}", line));
-        }
-    }
-
-    lines.push(format!("{indent} * @type {{{}}}", ts_type));
+    let mut lines = docs.jsdoc_body();
+    lines.push(format!("@type {{{}}}", ts_type));
     if optional {
-        lines.push(format!("{indent} * @optional"));
+        lines.push("@optional".to_string());
     }
+    lines.extend(validation_tags(validations, Some(field_type)));
+    lines.extend(docs.jsdoc_tags());
 
-    for tag in validation_tags(validations) {
-        lines.push(format!("{indent} * {}", tag));
-    }
-
-    lines.push(format!("{indent} */"));
-    lines.join("\n")
+    jsdoc_block("", &lines)
 }
 
-fn validation_tags(validations: &[ValidationRule]) -> Vec<String> {
+fn validation_tags(validations: &[ValidationRule], ty: Option<&TypeRef>) -> Vec<String> {
     let mut tags = Vec::new();
+    let kind = ty.map(ValueKind::of).unwrap_or_default();
+    let (min_length, max_length) = match kind {
+        ValueKind::Scalar | ValueKind::IntegerString => ("@minLength", "@maxLength"),
+        ValueKind::Array | ValueKind::Set => ("@minItems", "@maxItems"),
+        ValueKind::Map => ("@minProperties", "@maxProperties"),
+    };
 
     for rule in validations {
         match rule {
@@ -589,19 +1173,53 @@ fn validation_tags(validations: &[ValidationRule]) -> Vec<String> {
                     tags.push(format!("@maximum {}", max));
                 }
             }
+            ValidationRule::ExclusiveRange { min, max } => {
+                if let Some(min) = min {
+                    tags.push(format!("@exclusiveMinimum {}", min));
+                }
+                if let Some(max) = max {
+                    tags.push(format!("@exclusiveMaximum {}", max));
+                }
+            }
             ValidationRule::Length { min, max } => {
                 if let Some(min) = min {
-                    tags.push(format!("@minLength {}", min));
+                    tags.push(format!("{min_length} {}", min));
                 }
                 if let Some(max) = max {
-                    tags.push(format!("@maxLength {}", max));
+                    tags.push(format!("{max_length} {}", max));
+                }
+            }
+            ValidationRule::NonEmpty => tags.push(format!("{min_length} 1")),
+            ValidationRule::Unique => tags.push("@uniqueItems".to_string()),
+            ValidationRule::Each(rules) => {
+                let element = ty.and_then(element_type_ref);
+                for tag in validation_tags(rules, element) {
+                    tags.push(format!("@each {}", tag.trim_start_matches('@')));
                 }
             }
             ValidationRule::Email => tags.push("@format email".to_string()),
             ValidationRule::Url => tags.push("@format uri".to_string()),
+            ValidationRule::Uuid => tags.push("@format uuid".to_string()),
+            ValidationRule::Ip(version) => tags.push(match version {
+                Some(IpVersion::V4) => "@format ipv4".to_string(),
+                Some(IpVersion::V6) => "@format ipv6".to_string(),
+                None => "@format ip".to_string(),
+            }),
             ValidationRule::Pattern(pattern) => {
                 tags.push(format!("@pattern {}", sanitize_jsdoc_tag_value(pattern)))
             }
+            ValidationRule::Contains(needle) => {
+                tags.push(format!("@contains {}", sanitize_jsdoc_tag_value(needle)))
+            }
+            ValidationRule::StartsWith(prefix) => {
+                tags.push(format!("@startsWith {}", sanitize_jsdoc_tag_value(prefix)))
+            }
+            ValidationRule::EndsWith(suffix) => {
+                tags.push(format!("@endsWith {}", sanitize_jsdoc_tag_value(suffix)))
+            }
+            ValidationRule::Ascii => tags.push("@ascii".to_string()),
+            ValidationRule::Alphanumeric => tags.push("@alphanumeric".to_string()),
+            ValidationRule::MultipleOf(step) => tags.push(format!("@multipleOf {}", step)),
             ValidationRule::Required => tags.push("@required".to_string()),
             ValidationRule::Custom { name, message } => {
                 tags.push(format!("@validation {}", sanitize_jsdoc_tag_value(name)));
@@ -609,6 +1227,9 @@ fn validation_tags(validations: &[ValidationRule]) -> Vec<String> {
                     tags.push(format!("@message {}", sanitize_jsdoc_tag_value(message)));
                 }
             }
+            ValidationRule::WithMessage { rule, .. } => {
+                tags.extend(validation_tags(std::slice::from_ref(rule), ty));
+            }
             // Cross-field and conditional rules are emitted as object-level Zod refinements,
             // so there is no stable field-level JSDoc tag representation for them here.
             ValidationRule::CrossField { .. } | ValidationRule::Conditional { .. } => {}
@@ -618,6 +1239,15 @@ fn validation_tags(validations: &[ValidationRule]) -> Vec<String> {
     tags
 }
 
+/// コレクションの要素 (マップの場合は値) の型を取得
+fn element_type_ref(ty: &TypeRef) -> Option<&TypeRef> {
+    match ty.name.as_str() {
+        "Option" | "Box" | "Arc" | "Rc" | "Cow" => ty.generics.last().and_then(element_type_ref),
+        "HashMap" | "BTreeMap" => ty.generics.get(1),
+        _ => ty.generics.first(),
+    }
+}
+
 fn sanitize_jsdoc_tag_value(value: &str) -> String {
     value.replace("*/", "*\\/").replace('\n', "\\n")
 }
@@ -659,6 +1289,7 @@ mod tests {
                         validations: vec![],
                         optional: false,
                         serde_attrs: Default::default(),
+                        attributes: Default::default(),
                     },
                     FieldInfo {
                         name: "name".to_string(),
@@ -667,6 +1298,7 @@ mod tests {
                         validations: vec![],
                         optional: false,
                         serde_attrs: Default::default(),
+                        attributes: Default::default(),
                     },
                 ],
             }),
@@ -689,6 +1321,8 @@ mod tests {
             name: "UserId".to_string(),
             kind: TypeKind::Newtype(NewtypeType {
                 inner: TypeRef::new("i32"),
+                validations: vec![],
+                markers: vec![],
             }),
             docs: None,
             generics: vec![],
@@ -701,7 +1335,7 @@ mod tests {
         let mut generator = TypeScriptGenerator::new(GeneratorConfig::new().with_jsdoc(false));
         let output = generator.generate(&[ty]);
 
-        assert!(output.contains("type Brand<T, B>"));
+        assert!(output.contains("import type { Brand } from './gear-mesh.brand';"));
         assert!(output.contains("export type UserId = Brand<number, \"UserId\">;"));
         assert!(
             output.contains("export const UserId = (value: number): UserId => value as UserId;")
@@ -728,6 +1362,7 @@ mod tests {
                     validations: vec![],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 }],
             }),
             docs: None,
@@ -754,16 +1389,17 @@ mod tests {
                 fields: vec![FieldInfo {
                     name: "name".to_string(),
                     ty: TypeRef::new("String"),
-                    docs: Some(gear_mesh_core::DocComment::summary("Display name")),
+                    docs: Some(DocComment::summary("Display name")),
                     validations: vec![ValidationRule::Length {
                         min: Some(1),
                         max: Some(20),
                     }],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 }],
             }),
-            docs: Some(gear_mesh_core::DocComment::summary("User information")),
+            docs: Some(DocComment::summary("User information")),
             generics: vec![],
             attributes: TypeAttributes::default(),
         };
@@ -790,7 +1426,7 @@ mod tests {
                 fields: vec![FieldInfo {
                     name: "value".to_string(),
                     ty: TypeRef::new("String"),
-                    docs: Some(gear_mesh_core::DocComment::summary("Docs")),
+                    docs: Some(DocComment::summary("Docs")),
                     validations: vec![
                         ValidationRule::Pattern("foo*/bar".to_string()),
                         ValidationRule::Custom {
@@ -800,6 +1436,7 @@ mod tests {
                     ],
                     optional: false,
                     serde_attrs: Default::default(),
+                    attributes: Default::default(),
                 }],
             }),
             docs: None,
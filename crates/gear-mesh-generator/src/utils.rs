@@ -2,41 +2,143 @@ pub use gear_mesh_core::{
     is_bigint_type, is_builtin_type, is_internal_type, to_typescript_primitive,
 };
 
-use gear_mesh_core::{FieldInfo, RenameRule};
+pub use crate::escape::{format_property_name, is_plain_javascript_identifier};
 
-pub fn format_property_name(name: &str) -> String {
-    if is_plain_javascript_identifier(name) {
-        name.to_string()
-    } else {
-        format!("{name:?}")
-    }
-}
+use std::collections::HashMap;
+
+use gear_mesh_core::{
+    DocComment, FieldInfo, GearMeshType, RenameRule, TypeAttributes, TypeKind, TypeRef,
+    VariantContent,
+};
+
+use crate::escape::declaration_name;
 
 pub fn resolve_field_name(field: &FieldInfo, rename_all: Option<RenameRule>) -> String {
-    if let Some(rename) = &field.serde_attrs.rename {
+    if let Some(rename) = field
+        .attributes
+        .rename
+        .as_ref()
+        .or(field.serde_attrs.rename.as_ref())
+    {
         rename.clone()
     } else {
         apply_rename_all(&field.name, rename_all)
     }
 }
 
+/// 宣言の前に付ける `export ` (`#[gear_mesh(export = false)]` では空)
+pub fn export_keyword(attrs: &TypeAttributes) -> &'static str {
+    if attrs.export { "export " } else { "" }
+}
+
 pub fn apply_rename_all(name: &str, rename_all: Option<RenameRule>) -> String {
     rename_all
         .map(|rule| rule.apply(name))
         .unwrap_or_else(|| name.to_string())
 }
 
-pub fn is_plain_javascript_identifier(name: &str) -> bool {
-    let mut chars = name.chars();
-    let Some(first) = chars.next() else {
-        return false;
-    };
+/// `#[gear_mesh(rename = "...")]` の型名を、型自身とその参照に適用
+///
+/// 生成物のファイル名やimportも新しい名前になるよう、モジュールの分割より前に適用します。
+/// TypeScriptの予約語や生成コードが参照するグローバル (`default`, `Record`, `Date` など) と
+/// 同じ名前は、末尾に `_` を付けた名前にします。
+pub fn apply_type_renames(types: &[GearMeshType]) -> Vec<GearMeshType> {
+    let renames = types
+        .iter()
+        .filter_map(|ty| {
+            let name = declaration_name(ty.attributes.rename.as_deref().unwrap_or(&ty.name));
+            (name != ty.name).then(|| (ty.name.clone(), name))
+        })
+        .collect::<HashMap<_, _>>();
+    let mut types = types.to_vec();
+    if renames.is_empty() {
+        return types;
+    }
 
-    if !(first.is_ascii_alphabetic() || first == '_' || first == '$') {
-        return false;
+    for ty in &mut types {
+        if let Some(rename) = renames.get(&ty.name) {
+            ty.name = rename.clone();
+        }
+        match &mut ty.kind {
+            TypeKind::Struct(s) => {
+                for field in &mut s.fields {
+                    rename_type_ref(&mut field.ty, &renames);
+                }
+            }
+            TypeKind::Enum(e) => {
+                for variant in &mut e.variants {
+                    match &mut variant.content {
+                        VariantContent::Unit => {}
+                        VariantContent::Tuple(types) => {
+                            for ty in types {
+                                rename_type_ref(ty, &renames);
+                            }
+                        }
+                        VariantContent::Struct(fields) => {
+                            for field in fields {
+                                rename_type_ref(&mut field.ty, &renames);
+                            }
+                        }
+                    }
+                }
+            }
+            TypeKind::Newtype(n) => rename_type_ref(&mut n.inner, &renames),
+            TypeKind::Tuple(types) => {
+                for ty in types {
+                    rename_type_ref(ty, &renames);
+                }
+            }
+            TypeKind::Array(inner) | TypeKind::Option(inner) => rename_type_ref(inner, &renames),
+            TypeKind::Result { ok, err } => {
+                rename_type_ref(ok, &renames);
+                rename_type_ref(err, &renames);
+            }
+            TypeKind::Map { key, value } => {
+                rename_type_ref(key, &renames);
+                rename_type_ref(value, &renames);
+            }
+            TypeKind::Reference(reference) => rename_type_ref(reference, &renames),
+            TypeKind::Primitive(_) => {}
+        }
     }
+    types
+}
 
-    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+/// `#[deprecated]` の型・フィールド・バリアントを取り除く
+pub fn strip_deprecated(types: &[GearMeshType]) -> Vec<GearMeshType> {
+    let deprecated =
+        |docs: &Option<DocComment>| docs.as_ref().is_some_and(DocComment::is_deprecated);
+    let keep_fields = |fields: &mut Vec<FieldInfo>| fields.retain(|field| !deprecated(&field.docs));
+
+    let mut types = types
+        .iter()
+        .filter(|ty| !deprecated(&ty.docs))
+        .cloned()
+        .collect::<Vec<_>>();
+    for ty in &mut types {
+        match &mut ty.kind {
+            TypeKind::Struct(s) => keep_fields(&mut s.fields),
+            TypeKind::Enum(e) => {
+                e.variants.retain(|variant| !deprecated(&variant.docs));
+                for variant in &mut e.variants {
+                    if let VariantContent::Struct(fields) = &mut variant.content {
+                        keep_fields(fields);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    types
+}
+
+fn rename_type_ref(ty: &mut TypeRef, renames: &HashMap<String, String>) {
+    if let Some(rename) = renames.get(&ty.name) {
+        ty.name = rename.clone();
+    }
+    for inner in &mut ty.generics {
+        rename_type_ref(inner, renames);
+    }
 }
 
 #[cfg(test)]
@@ -62,6 +164,7 @@ mod tests {
                 rename: Some("display-name".to_string()),
                 ..Default::default()
             },
+            attributes: Default::default(),
         };
 
         assert_eq!(
@@ -69,4 +172,23 @@ mod tests {
             "display-name"
         );
     }
+
+    #[test]
+    fn gear_mesh_rename_overrides_serde_rename() {
+        let mut field = FieldInfo {
+            name: "display_name".to_string(),
+            ty: TypeRef::new("String"),
+            docs: None,
+            validations: vec![],
+            optional: false,
+            serde_attrs: SerdeFieldAttrs {
+                rename: Some("display-name".to_string()),
+                ..Default::default()
+            },
+            attributes: Default::default(),
+        };
+        field.attributes.rename = Some("label".to_string());
+
+        assert_eq!(resolve_field_name(&field, None), "label");
+    }
 }
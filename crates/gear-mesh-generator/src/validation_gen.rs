@@ -1,49 +1,542 @@
-use crate::utils::{format_property_name, resolve_field_name};
-use crate::{GeneratorConfig, OptionStyle, ResultStyle};
+use std::collections::HashMap;
+
+use crate::branded::{marker_literal, marker_positions};
+use crate::escape::{js_string, member_access, property_key};
+use crate::printer::Printer;
+use crate::ts_ast::{
+    Body, Const, Expr, Function, Interface, Item, Member, MethodCall, Param, Property, Stmt,
+};
+use crate::utils::{apply_rename_all, resolve_field_name};
+use crate::{EnumStyle, FloatStyle, GeneratorConfig, OptionStyle, QuoteStyle, ResultStyle};
 use gear_mesh_core::{
-    CrossFieldRule, FieldInfo, GearMeshType, TypeKind, ValidationRule, is_bigint_type,
-    is_builtin_type, is_internal_type,
+    BigIntPolicy, CrossFieldRule, DocComment, EnumRepresentation, EnumType, EnumVariant, FieldInfo,
+    GearMeshType, INTEGER_STRING_PATTERN, RenameRule, TypeKind, ValidationMessage, ValidationRule,
+    ValueKind, VariantContent, ZodVersion, interpolate_message, is_bigint_type, is_builtin_type,
+    is_integer_type, is_internal_type, js_regex_literal, message_key_call,
+    template_literal_pattern,
 };
 
+/// Runtime helpers shared by the `validateX` functions (relative to the output directory)
+pub const VALIDATION_MODULE: &str = "gear-mesh.validation.ts";
+
 /// Generator for Zod validation schemas
 pub struct ValidationGenerator {
     config: GeneratorConfig,
+    /// Marker positions of generic brands such as `Id<T>`
+    brand_markers: HashMap<String, Vec<bool>>,
+    /// Doc link targets (Rust name → generated name) for field JSDoc in schemas
+    doc_links: HashMap<String, String>,
+    /// Type parameters of the schema being generated, passed in as schemas
+    type_params: Vec<String>,
 }
 
 impl ValidationGenerator {
     pub fn new(config: GeneratorConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            brand_markers: HashMap::new(),
+            doc_links: HashMap::new(),
+            type_params: Vec::new(),
+        }
+    }
+
+    /// Registers referenced types, so `Id<User>` becomes `IdSchema("User")`
+    pub fn with_type_context(mut self, types: &[GearMeshType]) -> Self {
+        self.brand_markers.extend(marker_positions(types));
+        self
+    }
+
+    pub(crate) fn with_brand_markers(mut self, markers: HashMap<String, Vec<bool>>) -> Self {
+        self.brand_markers.extend(markers);
+        self
+    }
+
+    pub(crate) fn with_doc_links(mut self, links: HashMap<String, String>) -> Self {
+        self.doc_links.extend(links);
+        self
     }
 
     /// Generates a Zod schema
     pub fn generate_zod_schema(&self, ty: &GearMeshType) -> Option<String> {
+        let schema = self.schema_item(ty)?;
+        Some(Printer::new(&self.config).print(&[schema]))
+    }
+
+    /// `const XSchema = ...;` の宣言
+    pub(crate) fn schema_item(&self, ty: &GearMeshType) -> Option<Item> {
+        // 型に指定された `Option<T>` の表現方法で生成し直す
+        if let Some(style) = ty.attributes.option_style
+            && style != self.config.option_style
+        {
+            let mut config = self.config.clone();
+            config.option_style = style;
+            return ValidationGenerator::new(config)
+                .with_brand_markers(self.brand_markers.clone())
+                .with_doc_links(self.doc_links.clone())
+                .schema_item(ty);
+        }
+        // 型パラメータはファクトリの引数のスキーマとして参照する
+        let params = schema_type_params(ty);
+        if params != self.type_params {
+            return ValidationGenerator {
+                config: self.config.clone(),
+                brand_markers: self.brand_markers.clone(),
+                doc_links: self.doc_links.clone(),
+                type_params: params,
+            }
+            .schema_item(ty);
+        }
+
         match &ty.kind {
             TypeKind::Struct(s) => {
-                let mut schema = format!("export const {}Schema = z.object({{\n", ty.name);
-
-                for field in &s.fields {
-                    let field_schema = self.field_to_zod(field);
-                    let field_name = format_property_name(&resolve_field_name(
-                        field,
-                        ty.attributes.serde.rename_all,
-                    ));
-                    schema.push_str(&format!("    {}: {},\n", field_name, field_schema));
+                let type_policy = ty.attributes.bigint_policy().or(self.config.bigint_policy);
+                let mut properties = Vec::new();
+                for field in s.fields.iter().filter(|field| !field.attributes.skip) {
+                    let field_schema =
+                        self.field_to_zod(field, field.attributes.bigint.or(type_policy));
+                    let field_name = property_key(
+                        &resolve_field_name(field, ty.attributes.serde.rename_all),
+                        self.quote(),
+                    );
+                    // 型を推論する場合、フィールドのドキュメントはスキーマに付ける
+                    let jsdoc = field
+                        .docs
+                        .as_ref()
+                        .filter(|_| self.config.infers_types() && self.config.generate_jsdoc)
+                        .map(|docs| {
+                            docs.with_links(|name| self.doc_links.get(name).cloned())
+                                .to_inline_jsdoc()
+                        })
+                        .filter(|jsdoc| !jsdoc.is_empty());
+                    properties.push(Property {
+                        jsdoc,
+                        key: field_name,
+                        value: field_schema,
+                    });
                 }
 
-                schema.push_str("})");
-                for field in &s.fields {
-                    for refinement in self.object_refinements(field) {
-                        schema.push_str(&refinement);
+                let refinements = s
+                    .fields
+                    .iter()
+                    .filter(|field| !field.attributes.skip)
+                    .flat_map(|field| {
+                        self.object_refinements(field, &s.fields, ty.attributes.serde.rename_all)
+                    })
+                    .chain(self.describe(ty.docs.as_ref()));
+                let schema =
+                    Expr::call("z.object", vec![Expr::Object(properties)]).chain(refinements);
+                Some(self.declare_schema(ty, schema))
+            }
+            TypeKind::Newtype(newtype) => {
+                let policy = ty.attributes.bigint_policy().or(self.config.bigint_policy);
+                let mut schema =
+                    self.schema_with_rules(&newtype.inner, &newtype.validations, policy);
+                if newtype.is_generic_brand() {
+                    // マーカーごとにブランドを変えるスキーマのファクトリ
+                    if ty.attributes.branded && self.config.generate_branded {
+                        let keys = newtype
+                            .markers
+                            .iter()
+                            .map(|marker| format!("${{{marker}}}"))
+                            .collect::<Vec<_>>();
+                        schema = schema
+                            .method(format!("brand<`{}<{}>`>", ty.name, keys.join(", ")), vec![]);
                     }
+                    schema = schema.chain(self.describe(ty.docs.as_ref()));
+                    let params = newtype
+                        .markers
+                        .iter()
+                        .map(|marker| format!("{marker} extends string"))
+                        .collect::<Vec<_>>();
+                    let args = newtype
+                        .markers
+                        .iter()
+                        .map(|marker| format!("_{}: {marker}", marker.to_lowercase()))
+                        .collect::<Vec<_>>();
+                    return Some(Item::Const(Const {
+                        export: ty.attributes.export,
+                        name: format!("{}Schema", ty.name),
+                        ty: None,
+                        value: Expr::arrow(
+                            format!("<{}>({})", params.join(", "), args.join(", ")),
+                            Body::Expr(schema),
+                        ),
+                    }));
+                }
+                if ty.attributes.branded && self.config.generate_branded {
+                    let brand = format!("brand<{}>", js_string(&ty.name, self.quote()));
+                    schema = schema.method(brand, vec![]);
                 }
-                schema.push_str(";\n");
-                Some(schema)
+                schema = schema.chain(self.describe(ty.docs.as_ref()));
+                Some(self.declare_schema(ty, schema))
             }
+            TypeKind::Enum(enum_type) => Some(
+                self.declare_schema(
+                    ty,
+                    self.enum_schema(ty, enum_type)
+                        .chain(self.describe(ty.docs.as_ref())),
+                ),
+            ),
             _ => None,
         }
     }
 
-    fn field_to_zod(&self, field: &FieldInfo) -> String {
+    /// `const XSchema = ...;` (型パラメータを持つ型は要素のスキーマを受け取るファクトリ)
+    fn declare_schema(&self, ty: &GearMeshType, schema: Expr) -> Item {
+        let value = if self.type_params.is_empty() {
+            schema
+        } else {
+            let params = self
+                .type_params
+                .iter()
+                .map(|param| format!("{param} extends z.ZodType"))
+                .collect::<Vec<_>>();
+            let args = self
+                .type_params
+                .iter()
+                .map(|param| format!("{}: {param}", schema_param_name(param)))
+                .collect::<Vec<_>>();
+            Expr::arrow(
+                format!("<{}>({})", params.join(", "), args.join(", ")),
+                Body::Expr(schema),
+            )
+        };
+        Item::Const(Const {
+            export: ty.attributes.export,
+            name: format!("{}Schema", ty.name),
+            ty: None,
+            value,
+        })
+    }
+
+    /// 列挙型のスキーマ (serdeの表現ごとのバリアントのユニオン)
+    fn enum_schema(&self, ty: &GearMeshType, enum_type: &EnumType) -> Expr {
+        let policy = ty.attributes.bigint_policy().or(self.config.bigint_policy);
+        let tags = enum_type
+            .variants
+            .iter()
+            .map(|variant| apply_rename_all(&variant.name, ty.attributes.serde.rename_all))
+            .collect::<Vec<_>>();
+
+        // TS enumとして宣言した型は、スキーマからも同じenumを推論させる
+        if self.config.enum_style == EnumStyle::Enum
+            && !self.config.infers_types()
+            && matches!(enum_type.representation, EnumRepresentation::External)
+            && ty.generics.is_empty()
+            && enum_type
+                .variants
+                .iter()
+                .all(|variant| matches!(variant.content, VariantContent::Unit))
+        {
+            let callee = match self.config.zod_version {
+                ZodVersion::V3 => "z.nativeEnum",
+                ZodVersion::V4 => "z.enum",
+            };
+            return Expr::call(callee, vec![ty.name.as_str().into()]);
+        }
+
+        // ドキュメントのないユニットバリアントだけなら `z.enum`
+        if matches!(enum_type.representation, EnumRepresentation::External)
+            && !enum_type.variants.is_empty()
+            && enum_type.variants.iter().all(|variant| {
+                matches!(variant.content, VariantContent::Unit)
+                    && self.describe(variant.docs.as_ref()).is_none()
+            })
+        {
+            let tags = tags.into_iter().map(Expr::Str).collect();
+            return Expr::call("z.enum", vec![Expr::InlineArray(tags)]);
+        }
+
+        let members = enum_type
+            .variants
+            .iter()
+            .zip(&tags)
+            .map(|(variant, tag)| {
+                self.variant_schema(variant, tag, &enum_type.representation, policy)
+                    .chain(self.describe(variant.docs.as_ref()))
+            })
+            .collect::<Vec<_>>();
+        match members.len() {
+            0 => "z.never()".into(),
+            1 => members
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| "z.never()".into()),
+            _ => Expr::call("z.union", vec![Expr::Array(members)]),
+        }
+    }
+
+    /// バリアント1つ分のスキーマ (`tag` はJSONでのバリアント名)
+    fn variant_schema(
+        &self,
+        variant: &EnumVariant,
+        tag: &str,
+        repr: &EnumRepresentation,
+        policy: Option<BigIntPolicy>,
+    ) -> Expr {
+        let key = |name: &str| property_key(name, self.quote());
+        let fields = |fields: &[FieldInfo]| {
+            fields
+                .iter()
+                .filter(|field| !field.attributes.skip)
+                .map(|field| {
+                    Property::new(
+                        key(&resolve_field_name(field, None)),
+                        self.field_to_zod(field, field.attributes.bigint.or(policy)),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let object =
+            |entries: Vec<Property>| Expr::call("z.object", vec![Expr::InlineObject(entries)]);
+        let literal = || Expr::call("z.literal", vec![Expr::Str(tag.to_string())]);
+        let payload = match &variant.content {
+            VariantContent::Unit => None,
+            VariantContent::Tuple(types) if types.len() == 1 => {
+                Some(self.type_to_zod(&types[0], policy))
+            }
+            VariantContent::Tuple(types) => {
+                let items = types
+                    .iter()
+                    .map(|item| self.type_to_zod(item, policy))
+                    .collect();
+                Some(Expr::call("z.tuple", vec![Expr::InlineArray(items)]))
+            }
+            VariantContent::Struct(struct_fields) => Some(object(fields(struct_fields))),
+        };
+
+        match (repr, payload) {
+            (EnumRepresentation::External, None) => literal(),
+            // 外部タグのキーは常に引用符で囲む
+            (EnumRepresentation::External, Some(payload)) => {
+                object(vec![Property::new(js_string(tag, self.quote()), payload)])
+            }
+            (EnumRepresentation::Internal { tag: name }, None)
+            | (EnumRepresentation::Adjacent { tag: name, .. }, None) => {
+                object(vec![Property::new(key(name), literal())])
+            }
+            (EnumRepresentation::Internal { tag: name }, Some(payload)) => {
+                let tag_entry = Property::new(key(name), literal());
+                match &variant.content {
+                    VariantContent::Struct(struct_fields) => {
+                        object([vec![tag_entry], fields(struct_fields)].concat())
+                    }
+                    // serdeの内部タグはnewtypeバリアントの中身にタグを追加する
+                    _ => object(vec![tag_entry]).method("and", vec![payload]),
+                }
+            }
+            (EnumRepresentation::Adjacent { tag: name, content }, Some(payload)) => object(vec![
+                Property::new(key(name), literal()),
+                Property::new(key(content), payload),
+            ]),
+            (EnumRepresentation::Untagged, None) => "z.null()".into(),
+            (EnumRepresentation::Untagged, Some(payload)) => payload,
+        }
+    }
+
+    /// Generates `validateX` and `parseX` for a type marked with `#[gear_mesh(validate)]`
+    ///
+    /// Both wrap the type's Zod schema, so types without a schema get nothing.
+    pub fn generate_validator_functions(&self, ty: &GearMeshType) -> Option<String> {
+        let items = self.validator_function_items(ty, "FieldError")?;
+        Some(Printer::new(&self.config).print(&items))
+    }
+
+    /// `field_error` は `FieldError` を参照する名前 (生成対象に同名の型がある場合は別名)
+    pub(crate) fn validator_function_items(
+        &self,
+        ty: &GearMeshType,
+        field_error: &str,
+    ) -> Option<Vec<Item>> {
+        if !uses_type_validation(std::slice::from_ref(ty)) {
+            return None;
+        }
+
+        let name = &ty.name;
+        let export = ty.attributes.export;
+        let input = || vec![Param::new("input", "unknown")];
+        Some(vec![
+            Item::Doc(format!(
+                "/** Validates `input` against `{name}Schema` and reports every failed rule. */"
+            )),
+            Item::Function(Function {
+                export,
+                name: format!("validate{name}"),
+                type_params: String::new(),
+                params: input(),
+                multiline: false,
+                return_type: format!(
+                    "{{ ok: true; value: {name} }} | {{ ok: false; errors: {field_error}[] }}"
+                ),
+                body: vec![
+                    Stmt::expr(format!("const result = {name}Schema.safeParse(input)")),
+                    Stmt::block(
+                        "if (result.success)",
+                        vec![Stmt::expr(format!(
+                            "return {{ ok: true, value: result.data as {name} }}"
+                        ))],
+                    ),
+                    Stmt::expr("return { ok: false, errors: toFieldErrors(result.error) }"),
+                ],
+            }),
+            Item::Blank,
+            Item::Doc(format!(
+                "/** Parses `input` with `{name}Schema`, throwing a `ZodError` if it is invalid. */"
+            )),
+            Item::Function(Function {
+                export,
+                name: format!("parse{name}"),
+                type_params: String::new(),
+                params: input(),
+                multiline: false,
+                return_type: name.clone(),
+                body: vec![Stmt::expr(format!(
+                    "return {name}Schema.parse(input) as {name}"
+                ))],
+            }),
+        ])
+    }
+
+    /// Generates `gear-mesh.validation.ts`
+    ///
+    /// `FieldError` has the same shape as the server's `gear_mesh::FieldError`, so client
+    /// and server errors can be handled by the same code.
+    pub fn generate_validation_module(&self) -> String {
+        let field = |jsdoc: Option<&str>, name: &str, optional: bool| Member {
+            jsdoc: jsdoc.map(str::to_string),
+            readonly: false,
+            name: name.to_string(),
+            optional,
+            ty: "string".into(),
+        };
+        let segment = format!(
+            "typeof segment === {} ? `${{path}}[${{segment}}]` : path ? `${{path}}.${{String(segment)}}` : String(segment)",
+            js_string("number", self.quote())
+        );
+        let path = Expr::Call {
+            callee: "issue.path.reduce<string>".to_string(),
+            args: vec![
+                Expr::arrow(
+                    "(path, segment)",
+                    Body::Expr(Expr::Indented(Box::new(segment.into()))),
+                ),
+                Expr::Str(String::new()),
+            ],
+            multiline: true,
+        };
+        let issue = Expr::Object(vec![
+            Property::new("path", path),
+            Property::new("code", "issue.code"),
+            Property::new("message", "issue.message"),
+        ]);
+        let items = [
+            Item::Comment("Auto-generated validation helpers".to_string()),
+            Item::Blank,
+            Item::import("import type { ZodError } from 'zod';"),
+            Item::Blank,
+            Item::Doc("/** A single failed validation rule. */".to_string()),
+            Item::Interface(Interface {
+                export: true,
+                name: "FieldError".to_string(),
+                params: String::new(),
+                members: vec![
+                    field(
+                        Some(
+                            "/** Path to the offending value, e.g. `address.city` or `items[0]` */",
+                        ),
+                        "path",
+                        false,
+                    ),
+                    field(Some("/** Rule identifier */"), "code", false),
+                    field(None, "message", false),
+                    field(None, "message_key", true),
+                ],
+            }),
+            Item::Blank,
+            Item::Doc("/** Converts Zod issues into `FieldError`s. */".to_string()),
+            Item::Function(Function {
+                export: true,
+                name: "toFieldErrors".to_string(),
+                type_params: String::new(),
+                params: vec![Param::new("error", "ZodError")],
+                multiline: false,
+                return_type: "FieldError[]".to_string(),
+                body: vec![Stmt::Expr(Expr::Wrap(
+                    "return ".to_string(),
+                    Box::new(Expr::call(
+                        "error.issues.map",
+                        vec![Expr::arrow(
+                            "(issue)",
+                            Body::Expr(Expr::Wrap(
+                                "(".to_string(),
+                                Box::new(issue),
+                                ")".to_string(),
+                            )),
+                        )],
+                    )),
+                    String::new(),
+                ))],
+            }),
+        ];
+        Printer::new(&self.config).print(&items)
+    }
+
+    fn field_to_zod(&self, field: &FieldInfo, policy: Option<BigIntPolicy>) -> Expr {
+        let mut result = match (&field.attributes.zod, &field.attributes.ts_type) {
+            // 上書きされたスキーマにはフィールドのルールを適用しない
+            (Some(zod), _) => zod.clone().into(),
+            (None, Some(ts_type)) => Expr::call(format!("z.custom<{ts_type}>"), vec![]),
+            (None, None) => self.derived_field_schema(field, policy),
+        };
+
+        if field.attributes.nullable
+            && !result.ends_with_call("nullable")
+            && !result.ends_with_call("nullish")
+        {
+            result = result.method("nullable", vec![]);
+        }
+        if field.attributes.optional
+            && !result.ends_with_call("optional")
+            && !result.ends_with_call("nullish")
+        {
+            result = result.method("optional", vec![]);
+        }
+        result.chain(self.describe(field.docs.as_ref()))
+    }
+
+    /// ドキュメントから `.describe("...")`、または `.meta({ ... })` を作る
+    ///
+    /// 説明の出力が無効か、出力する内容がなければ `None` です。
+    fn describe(&self, docs: Option<&DocComment>) -> Option<MethodCall> {
+        let docs = docs.filter(|_| self.config.generate_schema_descriptions)?;
+        // リンクは表示名だけを残す
+        let summary = docs.with_links(|_| None).summary;
+        if !self.config.generate_schema_meta {
+            return (!summary.is_empty())
+                .then(|| MethodCall::new("describe", vec![Expr::Str(summary)]));
+        }
+
+        let mut entries = Vec::new();
+        if !summary.is_empty() {
+            entries.push(Property::new("description", Expr::Str(summary)));
+        }
+        // JSONのサンプルだけが値として使える
+        let examples = docs
+            .examples
+            .iter()
+            .filter(|example| example.language == "json")
+            .map(|example| example.code.trim().into())
+            .collect::<Vec<_>>();
+        if !examples.is_empty() {
+            entries.push(Property::new("examples", Expr::InlineArray(examples)));
+        }
+        if docs.is_deprecated() {
+            entries.push(Property::new("deprecated", "true"));
+        }
+        (!entries.is_empty()).then(|| MethodCall::new("meta", vec![Expr::InlineObject(entries)]))
+    }
+
+    fn derived_field_schema(&self, field: &FieldInfo, policy: Option<BigIntPolicy>) -> Expr {
         let is_option = field.ty.name == "Option" && field.optional;
 
         // Extract the target type for validation and schema generation.
@@ -57,25 +550,30 @@ impl ValidationGenerator {
             &field.ty
         };
 
-        // ベースとなるスキーマを生成
-        let base_schema = self.type_to_zod(target_type);
-
-        // バリデーションルールの適用のための型判定（BigIntかどうか）
-        // NOTE: ここでの判定は最上位の型に対してのみ有効
-        let is_bigint = self.config.use_bigint && is_bigint_type(&target_type.name);
-
-        let mut result = base_schema;
+        // テンプレートリテラル型はパターンのルールとして他のルールと並べる
+        let template = field
+            .attributes
+            .template
+            .as_ref()
+            .and_then(|template| template_literal_pattern(template).ok())
+            .map(ValidationRule::Pattern);
 
         // IMPORTANT: Add validation rules BEFORE nullable
-        for rule in &field.validations {
-            if matches!(
-                rule,
-                ValidationRule::CrossField { .. } | ValidationRule::Conditional { .. }
-            ) {
-                continue;
+        let mut result = match &field.attributes.literal {
+            Some(literal) => Expr::call(
+                "z.literal",
+                vec![literal.to_typescript(self.quote()).into()],
+            ),
+            None => {
+                let rules = field
+                    .validations
+                    .iter()
+                    .cloned()
+                    .chain(template)
+                    .collect::<Vec<_>>();
+                self.schema_with_rules(target_type, &rules, policy)
             }
-            result.push_str(&rule.to_zod_schema(is_bigint));
-        }
+        };
 
         // Add the configured Option wrapper AFTER validations
         if is_option {
@@ -85,183 +583,409 @@ impl ValidationGenerator {
         result
     }
 
+    /// Generates a schema for `type_ref` with field-local rules applied
+    ///
+    /// `each(...)` rules are pushed down into the element (or map value) schema.
+    fn schema_with_rules(
+        &self,
+        type_ref: &gear_mesh_core::TypeRef,
+        rules: &[ValidationRule],
+        policy: Option<BigIntPolicy>,
+    ) -> Expr {
+        // ルールはラッパーではなく中身の型に適用する
+        if !rules.is_empty()
+            && let Some(inner) = type_ref.generics.last()
+        {
+            match type_ref.name.as_str() {
+                "Option" => {
+                    return self
+                        .wrap_nested_option_schema(self.schema_with_rules(inner, rules, policy));
+                }
+                "Box" | "Arc" | "Rc" | "Cow" => {
+                    return self.schema_with_rules(inner, rules, policy);
+                }
+                _ => {}
+            }
+        }
+
+        let element_rules = rules
+            .iter()
+            .filter_map(|rule| match rule {
+                ValidationRule::Each(inner) => Some(inner.iter().cloned()),
+                _ => None,
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+
+        // NOTE: BigIntかどうかの判定は最上位の型に対してのみ有効
+        let (is_bigint, kind) = match policy.filter(|policy| policy.applies_to(&type_ref.name)) {
+            Some(BigIntPolicy::BigInt) => (true, ValueKind::Scalar),
+            Some(BigIntPolicy::Number) => (false, ValueKind::Scalar),
+            Some(BigIntPolicy::String) => (false, ValueKind::IntegerString),
+            None => (
+                self.config.use_bigint && is_bigint_type(&type_ref.name),
+                ValueKind::of(type_ref),
+            ),
+        };
+        let version = self.config.zod_version;
+        let quote = self.quote();
+        // v4では文字列のフォーマットを `z.string()` の代わりのスキーマで表す
+        let format = match version {
+            ZodVersion::V4 if kind == ValueKind::Scalar && element_rules.is_empty() => rules
+                .iter()
+                .enumerate()
+                .find_map(|(index, rule)| Some((index, rule.zod_format_schema(quote)?)))
+                .filter(|_| {
+                    matches!(self.type_to_zod(type_ref, policy), Expr::Raw(schema) if schema == "z.string()")
+                }),
+            _ => None,
+        };
+        // ルールごとのチェック (`.min(1).max(10)` など) はコアが描画する
+        let mut checks = rules
+            .iter()
+            .enumerate()
+            .filter(|(index, rule)| {
+                !matches!(
+                    rule.without_message(),
+                    ValidationRule::CrossField { .. } | ValidationRule::Conditional { .. }
+                ) && format.as_ref().is_none_or(|(format, _)| format != index)
+            })
+            .map(|(_, rule)| rule)
+            .collect::<Vec<_>>();
+        // v3の `.refine` の後には `.min()` などを続けられない
+        checks.sort_by_key(|rule| rule.is_zod_refinement(kind, version));
+        let checks = checks
+            .into_iter()
+            .map(|rule| rule.to_zod_schema_with(kind, is_bigint, version, quote))
+            .collect::<String>();
+        if let Some((_, schema)) = format {
+            return Expr::Raw(schema).suffixed(checks);
+        }
+
+        if !element_rules.is_empty() {
+            return self
+                .collection_with_element_rules(type_ref, &element_rules, policy)
+                .suffixed(checks);
+        }
+        if self.transformer_schema(type_ref).is_none()
+            && let Some(schema) = self.number_schema(&type_ref.name, policy, rules, &checks)
+        {
+            return schema;
+        }
+        self.type_to_zod(type_ref, policy).suffixed(checks)
+    }
+
+    /// `z.number()` で表す数値型のスキーマ (`checks` はルールから生成したチェック)
+    ///
+    /// 整数にはRustの型の範囲を加えますが、明示的な範囲ルールの方が狭い場合は省略します。
+    fn number_schema(
+        &self,
+        type_name: &str,
+        policy: Option<BigIntPolicy>,
+        rules: &[ValidationRule],
+        checks: &str,
+    ) -> Option<Expr> {
+        let number = || Expr::call("z.number", vec![]);
+        match type_name {
+            "f32" | "f64" => {
+                return Some(match self.config.float_style {
+                    FloatStyle::Number => number().suffixed(checks),
+                    FloatStyle::Finite if self.config.zod_version == ZodVersion::V3 => {
+                        number().method("finite", vec![]).suffixed(checks)
+                    }
+                    // v4の `z.number()` は無限大を受け付けない
+                    FloatStyle::Finite => number().suffixed(checks),
+                    FloatStyle::AllowNaN => number()
+                        .suffixed(checks)
+                        .method("or", vec![Expr::call("z.nan", vec![])]),
+                });
+            }
+            _ if !is_integer_type(type_name) => return None,
+            _ => match policy.filter(|policy| policy.applies_to(type_name)) {
+                Some(BigIntPolicy::BigInt | BigIntPolicy::String) => return None,
+                Some(BigIntPolicy::Number) => {}
+                None if self.config.use_bigint && is_bigint_type(type_name) => return None,
+                None => {}
+            },
+        }
+
+        let (explicit_min, explicit_max) = explicit_bounds(rules);
+        let v4 = self.config.zod_version == ZodVersion::V4;
+        let mut calls = Vec::new();
+        match integer_bounds(type_name) {
+            Some((min, max)) => {
+                if explicit_min.is_none_or(|explicit| explicit < min) {
+                    calls.push(MethodCall::new("min", vec![min.to_string().into()]));
+                }
+                if explicit_max.is_none_or(|explicit| explicit > max) {
+                    calls.push(MethodCall::new("max", vec![max.to_string().into()]));
+                }
+            }
+            // 64bit以上の整数は安全な整数の範囲に制限する
+            None => {
+                if type_name.starts_with('u') && explicit_min.is_none_or(|explicit| explicit < 0.0)
+                {
+                    calls.push(MethodCall::new("nonnegative", vec![]));
+                }
+                let safe =
+                    |bound: Option<f64>| bound.is_some_and(|bound| bound.abs() <= MAX_SAFE_INTEGER);
+                // v4の `z.int()` は安全な整数しか受け付けない
+                if !(v4 || safe(explicit_min) && safe(explicit_max)) {
+                    calls.push(MethodCall::new("safe", vec![]));
+                }
+            }
+        }
+        let schema = if v4 {
+            Expr::call("z.int", vec![])
+        } else {
+            number().method("int", vec![])
+        };
+        Some(schema.chain(calls).suffixed(checks))
+    }
+
+    fn collection_with_element_rules(
+        &self,
+        type_ref: &gear_mesh_core::TypeRef,
+        element_rules: &[ValidationRule],
+        policy: Option<BigIntPolicy>,
+    ) -> Expr {
+        let element = |index: usize| {
+            type_ref
+                .generics
+                .get(index)
+                .map(|inner| self.schema_with_rules(inner, element_rules, policy))
+                .unwrap_or_else(unknown)
+        };
+
+        match type_ref.name.as_str() {
+            "Vec" | "Array" | "__array__" | "__slice__" => Expr::call("z.array", vec![element(0)]),
+            "HashSet" | "BTreeSet" => Expr::call("z.set", vec![element(0)]),
+            "HashMap" | "BTreeMap" => self.record_schema(element(1)),
+            // 要素を持たない型では `each(...)` は意味を持たない
+            _ => self.type_to_zod(type_ref, policy),
+        }
+    }
+
     /// Recursively generates a Zod schema from a TypeRef
-    fn type_to_zod(&self, type_ref: &gear_mesh_core::TypeRef) -> String {
+    fn type_to_zod(
+        &self,
+        type_ref: &gear_mesh_core::TypeRef,
+        policy: Option<BigIntPolicy>,
+    ) -> Expr {
         if let Some(transformed) = self.transformer_schema(type_ref) {
-            return transformed;
+            return transformed.into();
         }
 
+        let generic = |index: usize| {
+            type_ref
+                .generics
+                .get(index)
+                .map(|inner| self.type_to_zod(inner, policy))
+                .unwrap_or_else(unknown)
+        };
         match type_ref.name.as_str() {
-            "__array__" | "__slice__" => {
-                if !type_ref.generics.is_empty() {
-                    let inner_schema = self.type_to_zod(&type_ref.generics[0]);
-                    format!("z.array({})", inner_schema)
-                } else {
-                    "z.array(z.unknown())".to_string()
-                }
-            }
+            "__array__" | "__slice__" => Expr::call("z.array", vec![generic(0)]),
             "__tuple__" => {
                 let items = type_ref
                     .generics
                     .iter()
-                    .map(|item| self.type_to_zod(item))
-                    .collect::<Vec<_>>();
-                format!("z.tuple([{}])", items.join(", "))
-            }
-            "()" => "z.null()".to_string(),
-            "Box" | "Arc" | "Rc" | "Cow" => {
-                if let Some(inner) = type_ref.generics.last() {
-                    self.type_to_zod(inner)
-                } else {
-                    "z.unknown()".to_string()
-                }
+                    .map(|item| self.type_to_zod(item, policy))
+                    .collect();
+                Expr::call("z.tuple", vec![Expr::InlineArray(items)])
             }
+            "()" => "z.null()".into(),
+            "Box" | "Arc" | "Rc" | "Cow" => match type_ref.generics.last() {
+                Some(inner) => self.type_to_zod(inner, policy),
+                None => unknown(),
+            },
             // プリミティブ型
             name if is_builtin_type(name) => {
                 // コレクション型は個別に処理
                 match name {
-                    "Vec" | "Array" => {
-                        if !type_ref.generics.is_empty() {
-                            let inner_schema = self.type_to_zod(&type_ref.generics[0]);
-                            format!("z.array({})", inner_schema)
-                        } else {
-                            "z.array(z.unknown())".to_string()
-                        }
-                    }
-                    "Option" => {
-                        if !type_ref.generics.is_empty() {
-                            let inner_schema = self.type_to_zod(&type_ref.generics[0]);
-                            self.wrap_nested_option_schema(inner_schema)
-                        } else {
-                            self.wrap_nested_option_schema("z.unknown()".to_string())
-                        }
-                    }
-                    "Result" => self.result_to_zod(type_ref),
-                    "HashMap" | "BTreeMap" => {
-                        let value_schema = if type_ref.generics.len() >= 2 {
-                            self.type_to_zod(&type_ref.generics[1])
-                        } else {
-                            "z.unknown()".to_string()
-                        };
-                        // HashMap<K, V> -> z.record(V) (Key is always string in JS objects usually, but Zod supports record)
-                        format!("z.record({})", value_schema)
-                    }
-                    "HashSet" | "BTreeSet" => {
-                        if !type_ref.generics.is_empty() {
-                            format!("z.set({})", self.type_to_zod(&type_ref.generics[0]))
-                        } else {
-                            "z.set(z.unknown())".to_string()
-                        }
-                    }
-                    _ => self.get_zod_primitive_type(name),
+                    "Vec" | "Array" => Expr::call("z.array", vec![generic(0)]),
+                    "Option" => self.wrap_nested_option_schema(generic(0)),
+                    "Result" => self.result_to_zod(type_ref, policy),
+                    // JSONのオブジェクトのキーは常に文字列
+                    "HashMap" | "BTreeMap" => self.record_schema(generic(1)),
+                    "HashSet" | "BTreeSet" => Expr::call("z.set", vec![generic(0)]),
+                    _ => self.get_zod_primitive_type(name, policy),
                 }
             }
             // カスタム型
-            name if is_internal_type(name) => "z.unknown()".to_string(),
-            name => format!("{}Schema", name),
+            name if self.type_params.iter().any(|param| param == name) => {
+                schema_param_name(name).into()
+            }
+            name if is_internal_type(name) => unknown(),
+            name => match self.brand_markers.get(name) {
+                Some(markers) => {
+                    let args = type_ref
+                        .generics
+                        .iter()
+                        .zip(markers)
+                        .filter(|(_, marker)| **marker)
+                        .map(|(generic, _)| marker_literal(generic, self.quote()).into())
+                        .collect();
+                    Expr::call(format!("{name}Schema"), args)
+                }
+                None if type_ref.generics.is_empty() => format!("{name}Schema").into(),
+                // ジェネリックな型のスキーマはファクトリ
+                None => {
+                    let args = type_ref
+                        .generics
+                        .iter()
+                        .map(|generic| self.type_to_zod(generic, policy))
+                        .collect();
+                    Expr::call(format!("{name}Schema"), args)
+                }
+            },
         }
     }
 
-    fn get_zod_primitive_type(&self, type_name: &str) -> String {
-        match type_name {
-            "i8" | "i16" | "i32" | "u8" | "u16" | "u32" | "f32" | "f64" => "z.number()".to_string(),
-            "i64" | "i128" | "u64" | "u128" | "isize" | "usize" => {
-                if self.config.use_bigint {
-                    "z.bigint()".to_string()
-                } else {
-                    "z.number()".to_string()
-                }
+    /// `HashMap<K, V>` のスキーマ (v4では `z.record` にキーのスキーマが必須)
+    fn record_schema(&self, value_schema: Expr) -> Expr {
+        match self.config.zod_version {
+            ZodVersion::V3 => Expr::call("z.record", vec![value_schema]),
+            ZodVersion::V4 => Expr::call("z.record", vec!["z.string()".into(), value_schema]),
+        }
+    }
+
+    fn get_zod_primitive_type(&self, type_name: &str, policy: Option<BigIntPolicy>) -> Expr {
+        // JSONでは `bigint` を表せないため、数値や文字列から変換する
+        match policy.filter(|policy| policy.applies_to(type_name)) {
+            Some(BigIntPolicy::BigInt) => return "z.coerce.bigint()".into(),
+            Some(BigIntPolicy::String) => {
+                return Expr::from("z.string()").method(
+                    "regex",
+                    vec![js_regex_literal(INTEGER_STRING_PATTERN).into()],
+                );
             }
-            "String" | "str" | "char" => "z.string()".to_string(),
-            "bool" => "z.boolean()".to_string(),
-            _ => "z.unknown()".to_string(),
+            Some(BigIntPolicy::Number) | None => {}
+        }
+        if let Some(schema) = self.number_schema(type_name, policy, &[], "") {
+            return schema;
+        }
+        match type_name {
+            "i64" | "i128" | "u64" | "u128" | "isize" | "usize" => "z.bigint()".into(),
+            "String" | "str" | "char" => "z.string()".into(),
+            "bool" => "z.boolean()".into(),
+            _ => unknown(),
         }
     }
 
-    fn wrap_nested_option_schema(&self, schema: String) -> String {
+    fn wrap_nested_option_schema(&self, schema: Expr) -> Expr {
         match self.config.option_style {
             OptionStyle::Nullable => {
-                if schema.ends_with(".nullable()") || schema.ends_with(".nullish()") {
+                if schema.ends_with_call("nullable") || schema.ends_with_call("nullish") {
                     schema
                 } else {
-                    format!("{}.nullable()", schema)
+                    schema.method("nullable", vec![])
                 }
             }
             OptionStyle::Optional => {
-                if schema.ends_with(".optional()") || schema.ends_with(".nullish()") {
+                if schema.ends_with_call("optional") || schema.ends_with_call("nullish") {
                     schema
                 } else {
-                    format!("{}.optional()", schema)
+                    schema.method("optional", vec![])
                 }
             }
             OptionStyle::Both => {
-                if schema.ends_with(".nullish()") {
+                if schema.ends_with_call("nullish") {
                     schema
                 } else {
-                    format!("{}.nullish()", schema)
+                    schema.method("nullish", vec![])
                 }
             }
         }
     }
 
-    fn wrap_option_field_schema(&self, schema: String) -> String {
+    fn wrap_option_field_schema(&self, schema: Expr) -> Expr {
         match self.config.option_style {
             OptionStyle::Nullable => {
-                if schema.ends_with(".nullable()") {
-                    schema
-                } else if schema.ends_with(".nullish()") {
-                    schema.trim_end_matches(".nullish()").to_string() + ".nullable()"
-                } else {
-                    format!("{}.nullable()", schema)
-                }
-            }
-            OptionStyle::Optional => {
-                if schema.ends_with(".optional()") || schema.ends_with(".nullish()") {
-                    schema
-                } else {
-                    format!("{}.optional()", schema)
-                }
-            }
-            OptionStyle::Both => {
-                if schema.ends_with(".nullish()") {
+                if schema.ends_with_call("nullable") {
                     schema
                 } else {
-                    format!("{}.nullish()", schema)
+                    schema.without_call("nullish").method("nullable", vec![])
                 }
             }
+            OptionStyle::Optional | OptionStyle::Both => self.wrap_nested_option_schema(schema),
         }
     }
 
-    fn result_to_zod(&self, type_ref: &gear_mesh_core::TypeRef) -> String {
+    fn result_to_zod(
+        &self,
+        type_ref: &gear_mesh_core::TypeRef,
+        policy: Option<BigIntPolicy>,
+    ) -> Expr {
         let ok = type_ref
             .generics
             .first()
-            .map(|ty| self.type_to_zod(ty))
-            .unwrap_or_else(|| "z.unknown()".to_string());
+            .map(|ty| self.type_to_zod(ty, policy))
+            .unwrap_or_else(unknown);
         let err = type_ref
             .generics
             .get(1)
-            .map(|ty| self.type_to_zod(ty))
-            .unwrap_or_else(|| "z.unknown()".to_string());
+            .map(|ty| self.type_to_zod(ty, policy))
+            .unwrap_or_else(unknown);
+        let object =
+            |entries: Vec<Property>| Expr::call("z.object", vec![Expr::InlineObject(entries)]);
 
         match self.config.result_style {
             ResultStyle::OkOnly => ok,
-            ResultStyle::TaggedUnion => format!(
-                "z.union([z.object({{ ok: {} }}), z.object({{ err: {} }})])",
-                ok, err
-            ),
-            ResultStyle::SuccessError => format!(
-                "z.union([z.object({{ success: z.literal(true), data: {} }}), z.object({{ success: z.literal(false), error: {} }})])",
-                ok, err
+            ResultStyle::TaggedUnion => Expr::call(
+                "z.union",
+                vec![Expr::InlineArray(vec![
+                    object(vec![Property::new("ok", ok)]),
+                    object(vec![Property::new("err", err)]),
+                ])],
             ),
+            ResultStyle::SuccessError => {
+                let variants = Expr::InlineArray(vec![
+                    object(vec![
+                        Property::new("success", "z.literal(true)"),
+                        Property::new("data", ok),
+                    ]),
+                    object(vec![
+                        Property::new("success", "z.literal(false)"),
+                        Property::new("error", err),
+                    ]),
+                ]);
+                match self.config.zod_version {
+                    ZodVersion::V3 => Expr::call("z.union", vec![variants]),
+                    // v4は真偽値のリテラルでも判別できる
+                    ZodVersion::V4 => Expr::call(
+                        "z.discriminatedUnion",
+                        vec![Expr::Str("success".to_string()), variants],
+                    ),
+                }
+            }
         }
     }
 
-    fn object_refinements(&self, field: &FieldInfo) -> Vec<String> {
+    fn object_refinements(
+        &self,
+        field: &FieldInfo,
+        fields: &[FieldInfo],
+        rename_all: Option<RenameRule>,
+    ) -> Vec<MethodCall> {
+        let json_name = |name: &str| {
+            fields
+                .iter()
+                .find(|candidate| candidate.name == name)
+                .map(|candidate| resolve_field_name(candidate, rename_all))
+                .unwrap_or_else(|| name.to_string())
+        };
         field
             .validations
             .iter()
-            .filter_map(|rule| render_object_refinement(field, rule))
+            .filter_map(|rule| {
+                render_object_refinement(
+                    field,
+                    rule,
+                    &json_name,
+                    self.config.zod_version,
+                    self.quote(),
+                )
+            })
             .collect()
     }
 
@@ -272,6 +996,10 @@ impl ValidationGenerator {
             .find(|transformer| transformer.can_handle(&type_ref.name))
             .and_then(|transformer| transformer.transform_zod(type_ref))
     }
+
+    fn quote(&self) -> QuoteStyle {
+        self.config.format.quote_style
+    }
 }
 
 impl Default for ValidationGenerator {
@@ -280,6 +1008,49 @@ impl Default for ValidationGenerator {
     }
 }
 
+/// `z.unknown()`
+fn unknown() -> Expr {
+    "z.unknown()".into()
+}
+
+/// `Number.MAX_SAFE_INTEGER`
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// 32bit以下の整数型の範囲
+fn integer_bounds(type_name: &str) -> Option<(f64, f64)> {
+    match type_name {
+        "i8" => Some((i8::MIN.into(), i8::MAX.into())),
+        "i16" => Some((i16::MIN.into(), i16::MAX.into())),
+        "i32" => Some((i32::MIN.into(), i32::MAX.into())),
+        "u8" => Some((0.0, u8::MAX.into())),
+        "u16" => Some((0.0, u16::MAX.into())),
+        "u32" => Some((0.0, u32::MAX.into())),
+        _ => None,
+    }
+}
+
+/// 範囲ルールで明示された下限・上限 (整数なので排他的な境界は1つ内側に寄せる)
+fn explicit_bounds(rules: &[ValidationRule]) -> (Option<f64>, Option<f64>) {
+    let mut bounds = (None, None);
+    for rule in rules {
+        let (min, max) = match rule.without_message() {
+            ValidationRule::Range { min, max } => (*min, *max),
+            ValidationRule::ExclusiveRange { min, max } => (
+                min.map(|min| min.floor() + 1.0),
+                max.map(|max| max.ceil() - 1.0),
+            ),
+            _ => continue,
+        };
+        if let Some(min) = min {
+            bounds.0 = Some(bounds.0.map_or(min, |current: f64| current.max(min)));
+        }
+        if let Some(max) = max {
+            bounds.1 = Some(bounds.1.map_or(max, |current: f64| current.min(max)));
+        }
+    }
+    bounds
+}
+
 fn default_cross_field_message(rule: &CrossFieldRule, fields: &[String]) -> String {
     match rule {
         CrossFieldRule::Match => format!("{} must match {}", fields[0], fields[1]),
@@ -288,20 +1059,56 @@ fn default_cross_field_message(rule: &CrossFieldRule, fields: &[String]) -> Stri
     }
 }
 
-fn render_object_refinement(field: &FieldInfo, rule: &ValidationRule) -> Option<String> {
-    match rule {
+fn render_object_refinement(
+    field: &FieldInfo,
+    rule: &ValidationRule,
+    json_name: &dyn Fn(&str) -> String,
+    version: ZodVersion,
+    quote: QuoteStyle,
+) -> Option<MethodCall> {
+    let accessor = |name: &str| member_access("data", &json_name(name), quote);
+    // `.refine((data) => check, { ... })`
+    let refine = |check: String, options: Vec<Property>| {
+        MethodCall::new(
+            "refine",
+            vec![
+                Expr::arrow("(data)", Body::Expr(check.into())),
+                Expr::InlineObject(options),
+            ],
+        )
+    };
+    // v3の `.superRefine` (メッセージキーを検証時に解決するため)
+    let super_refine = |failed: String, message: Expr, path: Expr| {
+        let issue = Expr::InlineObject(vec![
+            Property::new("code", Expr::Str("custom".to_string())),
+            Property::new("message", message),
+            Property::new("path", path),
+        ]);
+        MethodCall::new(
+            "superRefine",
+            vec![Expr::arrow(
+                "(data, ctx)",
+                Body::Expr(Expr::Wrap(
+                    format!("{{ if ({failed}) {{ ctx.addIssue("),
+                    Box::new(issue),
+                    "); } }".to_string(),
+                )),
+            )],
+        )
+    };
+    match rule.without_message() {
         ValidationRule::CrossField {
             fields,
-            rule,
+            rule: cross_rule,
             message,
             path,
         } => {
-            let expression = match rule {
+            let expression = match cross_rule {
                 CrossFieldRule::Match => {
                     let mut iter = fields.iter();
-                    let first = iter.next()?;
+                    let first = accessor(iter.next()?);
                     let comparisons = iter
-                        .map(|other| format!("data.{first} === data.{other}"))
+                        .map(|other| format!("{first} === {}", accessor(other)))
                         .collect::<Vec<_>>();
                     if comparisons.is_empty() {
                         return None;
@@ -311,9 +1118,9 @@ fn render_object_refinement(field: &FieldInfo, rule: &ValidationRule) -> Option<
                 CrossFieldRule::AtLeastOne => fields
                     .iter()
                     .map(|field| {
-                        format!(
-                            "data.{field} !== undefined && data.{field} !== null && data.{field} !== ''"
-                        )
+                        let field = accessor(field);
+                        let empty = js_string("", quote);
+                        format!("{field} !== undefined && {field} !== null && {field} !== {empty}")
                     })
                     .collect::<Vec<_>>()
                     .join(" || "),
@@ -321,93 +1128,129 @@ fn render_object_refinement(field: &FieldInfo, rule: &ValidationRule) -> Option<
                     "[{}].filter(Boolean).length <= 1",
                     fields
                         .iter()
-                        .map(|field| format!("data.{field}"))
+                        .map(|field| accessor(field))
                         .collect::<Vec<_>>()
                         .join(", ")
                 ),
             };
 
+            let path = || {
+                let path = path.clone().unwrap_or_else(|| json_name(&field.name));
+                Expr::InlineArray(vec![Expr::Str(path)])
+            };
+            // メッセージキーは検証時のロケールで解決する
+            if let Some(ValidationMessage::Key { key, .. }) = rule.message() {
+                let message = message_key_call(key, &[], quote);
+                if version == ZodVersion::V4 {
+                    return Some(refine(
+                        expression,
+                        vec![
+                            Property::new("error", format!("() => {message}")),
+                            Property::new("path", path()),
+                        ],
+                    ));
+                }
+                return Some(super_refine(
+                    format!("!({expression})"),
+                    message.into(),
+                    path(),
+                ));
+            }
             let message = message
                 .clone()
-                .unwrap_or_else(|| default_cross_field_message(rule, fields));
-            let path = path.clone().unwrap_or_else(|| field.name.clone());
-            Some(format!(
-                ".refine((data) => {}, {{ message: \"{}\", path: [\"{}\"] }})",
-                expression, message, path
+                .unwrap_or_else(|| default_cross_field_message(cross_rule, fields));
+            Some(refine(
+                expression,
+                vec![
+                    Property::new(version.message_option(), Expr::Str(message)),
+                    Property::new("path", path()),
+                ],
             ))
         }
         ValidationRule::Conditional { condition, rule } => {
-            let refinement = refinement_spec(field, rule)?;
-            Some(format!(
-                ".refine((data) => !({}) || ({}), {{ message: \"{}\", path: [\"{}\"] }})",
-                condition, refinement.expression, refinement.message, refinement.path
+            let path = json_name(&field.name);
+            let target = accessor(&field.name);
+            let inner = rule.without_message();
+            let check = match inner {
+                ValidationRule::Required => {
+                    inner.check_expression_for(&target, ValueKind::Scalar, quote)
+                }
+                ValidationRule::Custom { name, .. } => format!("validate{name}({target})"),
+                // 値がない場合は他のルールと同様に検査しない
+                _ => format!(
+                    "{target} === undefined || {target} === null || ({})",
+                    inner.check_expression_for(&target, ValueKind::of(&field.ty), quote)
+                ),
+            };
+            let message = match (rule.message(), inner) {
+                (Some(ValidationMessage::Text(text)), _) => {
+                    Expr::Str(interpolate_message(text, &inner.message_params()))
+                }
+                (Some(ValidationMessage::Key { key, .. }), _) => {
+                    message_key_call(key, &inner.message_params(), quote).into()
+                }
+                (
+                    None,
+                    ValidationRule::Custom {
+                        message: Some(message),
+                        ..
+                    },
+                ) => Expr::Str(message.clone()),
+                (None, ValidationRule::Required) => Expr::Str(format!("{path} is required")),
+                (None, _) => Expr::Str(format!("{path} failed validation")),
+            };
+            let condition = condition.to_typescript(accessor, quote);
+            let path = Expr::InlineArray(vec![Expr::Str(path)]);
+            if version == ZodVersion::V4 {
+                // メッセージキーは検証時に解決するため、エラーを関数で渡す
+                let error = match rule.message() {
+                    Some(ValidationMessage::Key { .. }) => {
+                        Expr::Wrap("() => ".to_string(), Box::new(message), String::new())
+                    }
+                    _ => message,
+                };
+                return Some(refine(
+                    format!("!({condition}) || ({check})"),
+                    vec![Property::new("error", error), Property::new("path", path)],
+                ));
+            }
+            Some(super_refine(
+                format!("({condition}) && !({check})"),
+                message,
+                path,
             ))
         }
         _ => None,
     }
 }
 
-struct RefinementSpec {
-    expression: String,
-    message: String,
-    path: String,
+/// スキーマのファクトリが引数で受け取る型パラメータ (ジェネリックなブランドのマーカーは除く)
+pub(crate) fn schema_type_params(ty: &GearMeshType) -> Vec<String> {
+    let markers = match &ty.kind {
+        TypeKind::Newtype(newtype) => newtype.markers.as_slice(),
+        _ => &[],
+    };
+    ty.generics
+        .iter()
+        .map(|param| param.name.clone())
+        .filter(|name| !markers.contains(name))
+        .collect()
 }
 
-fn refinement_spec(field: &FieldInfo, rule: &ValidationRule) -> Option<RefinementSpec> {
-    match rule {
-        ValidationRule::CrossField {
-            fields,
-            rule,
-            message,
-            path,
-        } => {
-            let expression = match rule {
-                CrossFieldRule::Match => {
-                    let mut iter = fields.iter();
-                    let first = iter.next()?;
-                    let comparisons = iter
-                        .map(|other| format!("data.{first} === data.{other}"))
-                        .collect::<Vec<_>>();
-                    if comparisons.is_empty() {
-                        return None;
-                    }
-                    comparisons.join(" && ")
-                }
-                CrossFieldRule::AtLeastOne => fields
-                    .iter()
-                    .map(|field| {
-                        format!(
-                            "data.{field} !== undefined && data.{field} !== null && data.{field} !== ''"
-                        )
-                    })
-                    .collect::<Vec<_>>()
-                    .join(" || "),
-                CrossFieldRule::MutuallyExclusive => format!(
-                    "[{}].filter(Boolean).length <= 1",
-                    fields
-                        .iter()
-                        .map(|field| format!("data.{field}"))
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                ),
-            };
+/// 型パラメータのスキーマを受け取る引数名 (`T` → `t`)
+fn schema_param_name(param: &str) -> String {
+    param.to_lowercase()
+}
 
-            Some(RefinementSpec {
-                expression,
-                message: message
-                    .clone()
-                    .unwrap_or_else(|| default_cross_field_message(rule, fields)),
-                path: path.clone().unwrap_or_else(|| field.name.clone()),
-            })
-        }
-        ValidationRule::Custom { name, message } => Some(RefinementSpec {
-            expression: format!("validate{name}(data.{})", field.name),
-            message: message
-                .clone()
-                .unwrap_or_else(|| format!("{} failed validation", field.name)),
-            path: field.name.clone(),
-        }),
-        ValidationRule::Conditional { condition: _, rule } => refinement_spec(field, rule),
-        _ => None,
-    }
+/// 型のいずれかが `#[gear_mesh(validate)]` のバリデーション関数を持つか
+pub(crate) fn uses_type_validation(types: &[GearMeshType]) -> bool {
+    types.iter().any(|ty| {
+        ty.attributes.validate
+            && match &ty.kind {
+                TypeKind::Struct(_) => true,
+                // ジェネリックなブランドのスキーマはファクトリなので対象外
+                TypeKind::Newtype(newtype) => !newtype.is_generic_brand(),
+                _ => false,
+            }
+    })
 }
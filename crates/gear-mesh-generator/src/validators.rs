@@ -0,0 +1,190 @@
+//! カスタムバリデータの参照
+//!
+//! `custom = "..."` ルールが参照する `validateXxx` 関数を集め、モジュールごとの
+//! import 文と、型付きのスタブを持つ `validators.ts` を生成するための情報を提供します。
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use gear_mesh_core::{FieldInfo, GearMeshType, TypeKind, TypeRef, ValidationRule, VariantContent};
+
+use crate::GeneratorConfig;
+use crate::module_organizer::relative_import;
+
+/// スタブを生成するモジュール (出力ディレクトリからの相対パス)
+pub const VALIDATORS_MODULE: &str = "validators.ts";
+
+/// カスタムバリデータが検証する値
+pub(crate) struct ValidatorUse<'a> {
+    /// `User.username` のような参照元
+    pub owner: String,
+    /// 検証する値の型
+    pub value: &'a TypeRef,
+    /// 条件付きルールでは値が `null` / `undefined` のまま渡される
+    pub nullable: bool,
+}
+
+/// 型が参照するカスタムバリデータを名前ごとに収集
+pub(crate) fn collect_custom_validators(
+    types: &[GearMeshType],
+) -> BTreeMap<String, Vec<ValidatorUse<'_>>> {
+    let mut validators = BTreeMap::new();
+    for ty in types {
+        match &ty.kind {
+            TypeKind::Struct(s) => {
+                for field in &s.fields {
+                    collect_field(&ty.name, field, &mut validators);
+                }
+            }
+            TypeKind::Enum(e) => {
+                for variant in &e.variants {
+                    if let VariantContent::Struct(fields) = &variant.content {
+                        let owner = format!("{}.{}", ty.name, variant.name);
+                        for field in fields {
+                            collect_field(&owner, field, &mut validators);
+                        }
+                    }
+                }
+            }
+            TypeKind::Newtype(newtype) => collect_rules(
+                &ty.name,
+                &newtype.validations,
+                unwrap_value(&newtype.inner),
+                false,
+                &mut validators,
+            ),
+            _ => {}
+        }
+    }
+    validators
+}
+
+fn collect_field<'a>(
+    owner: &str,
+    field: &'a FieldInfo,
+    validators: &mut BTreeMap<String, Vec<ValidatorUse<'a>>>,
+) {
+    if field.attributes.skip {
+        return;
+    }
+    let owner = format!("{owner}.{}", field.name);
+    collect_rules(
+        &owner,
+        &field.validations,
+        unwrap_value(&field.ty),
+        false,
+        validators,
+    );
+}
+
+fn collect_rules<'a>(
+    owner: &str,
+    rules: &'a [ValidationRule],
+    value: &'a TypeRef,
+    nullable: bool,
+    validators: &mut BTreeMap<String, Vec<ValidatorUse<'a>>>,
+) {
+    for rule in rules {
+        match rule {
+            ValidationRule::Custom { name, .. } => {
+                validators
+                    .entry(name.clone())
+                    .or_default()
+                    .push(ValidatorUse {
+                        owner: owner.to_string(),
+                        value,
+                        nullable,
+                    });
+            }
+            ValidationRule::WithMessage { rule, .. } => {
+                collect_rules(
+                    owner,
+                    std::slice::from_ref(rule),
+                    value,
+                    nullable,
+                    validators,
+                );
+            }
+            ValidationRule::Conditional { rule, .. } => {
+                collect_rules(owner, std::slice::from_ref(rule), value, true, validators);
+            }
+            ValidationRule::Each(rules) => {
+                let element = match value.name.as_str() {
+                    "HashMap" | "BTreeMap" => value.generics.get(1),
+                    _ => value.generics.first(),
+                };
+                if let Some(element) = element {
+                    collect_rules(owner, rules, unwrap_value(element), false, validators);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// ルールは `Option` やスマートポインタの中身に適用される
+fn unwrap_value(ty: &TypeRef) -> &TypeRef {
+    match ty.name.as_str() {
+        "Option" | "Box" | "Arc" | "Rc" | "Cow" => {
+            ty.generics.last().map(unwrap_value).unwrap_or(ty)
+        }
+        _ => ty,
+    }
+}
+
+/// TypeScriptで呼び出す関数名 (`Username` → `validateUsername`)
+pub(crate) fn validator_function(name: &str) -> String {
+    format!("validate{name}")
+}
+
+/// `module` が使うカスタムバリデータのimport文
+///
+/// 設定でimport先が指定されていないバリデータは `validators.ts` から読み込みます。
+pub(crate) fn validator_imports(
+    names: impl IntoIterator<Item = impl AsRef<str>>,
+    module: &str,
+    config: &GeneratorConfig,
+) -> Vec<String> {
+    let mut imports = BTreeMap::<String, BTreeSet<String>>::new();
+    for name in names {
+        let name = name.as_ref();
+        let specifier = match config.custom_validator_imports.get(name) {
+            Some(specifier) => resolve_specifier(module, specifier),
+            None => relative_import(module, VALIDATORS_MODULE),
+        };
+        imports
+            .entry(specifier)
+            .or_default()
+            .insert(validator_function(name));
+    }
+    imports
+        .into_iter()
+        .map(|(specifier, functions)| {
+            format!(
+                "import {{ {} }} from '{specifier}';",
+                functions.into_iter().collect::<Vec<_>>().join(", ")
+            )
+        })
+        .collect()
+}
+
+/// 相対パスのimport先は出力ディレクトリ基準として、モジュールの位置に合わせる
+fn resolve_specifier(module: &str, specifier: &str) -> String {
+    if let Some(path) = specifier.strip_prefix("./") {
+        relative_import(module, path)
+    } else if specifier.starts_with("../") {
+        let depth = module.split('/').count().saturating_sub(1);
+        format!("{}{specifier}", "../".repeat(depth))
+    } else {
+        specifier.to_string()
+    }
+}
+
+/// `source` で `function_name` が宣言・参照されているか
+pub(crate) fn mentions_identifier(source: &str, function_name: &str) -> bool {
+    let is_ident = |ch: char| ch.is_alphanumeric() || ch == '_' || ch == '$';
+    source.match_indices(function_name).any(|(start, _)| {
+        let before = source[..start].chars().next_back();
+        let after = source[start + function_name.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
@@ -43,6 +43,7 @@ fn arb_gear_mesh_type() -> impl Strategy<Value = GearMeshType> {
                         docs: None,
                         validations: vec![],
                         serde_attrs: Default::default(),
+                        attributes: Default::default(),
                     })
                     .collect(),
             }),
@@ -0,0 +1,100 @@
+//! Axum integration.
+//!
+//! [`ValidJson`] is a drop-in replacement for `axum::Json` that runs the
+//! GearMesh validation rules of the target type before handing it to the
+//! handler.
+//!
+//! ```ignore
+//! use gear_mesh::axum::ValidJson;
+//!
+//! async fn create_user(ValidJson(request): ValidJson<CreateUserRequest>) -> String {
+//!     format!("created {}", request.name)
+//! }
+//! ```
+
+use ::axum::Json;
+use ::axum::extract::rejection::JsonRejection;
+use ::axum::extract::{FromRequest, Request};
+use ::axum::http::StatusCode;
+use ::axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{FieldError, GearMeshExport, validate_value};
+
+/// JSON extractor that validates the body against the type's GearMesh rules.
+///
+/// The body is deserialized, checked with [`validate_value`], and only then
+/// converted into `T`. Failed rules are rejected with
+/// `422 Unprocessable Entity` and a [`ValidationErrorResponse`] body.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidJson<T>
+where
+    T: DeserializeOwned + GearMeshExport,
+    S: Send + Sync,
+{
+    type Rejection = ValidJsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<serde_json::Value>::from_request(req, state)
+            .await
+            .map_err(ValidJsonRejection::Json)?;
+
+        validate_value(&T::gear_mesh_type(), &value).map_err(|errors| {
+            ValidJsonRejection::Validation(ValidationErrorResponse::new(errors))
+        })?;
+
+        serde_json::from_value(value).map(ValidJson).map_err(|err| {
+            ValidJsonRejection::Validation(ValidationErrorResponse::new(vec![FieldError {
+                path: String::new(),
+                code: "invalid_type".to_string(),
+                message: err.to_string(),
+                message_key: None,
+            }]))
+        })
+    }
+}
+
+/// Body of a `422 Unprocessable Entity` response produced by [`ValidJson`].
+#[derive(Debug, Clone, PartialEq, Serialize, crate::GearMesh)]
+pub struct ValidationErrorResponse {
+    /// Summary of the failure
+    pub message: String,
+    /// Every rule that failed
+    pub errors: Vec<FieldError>,
+}
+
+impl ValidationErrorResponse {
+    pub fn new(errors: Vec<FieldError>) -> Self {
+        Self {
+            message: "Validation failed".to_string(),
+            errors,
+        }
+    }
+}
+
+impl IntoResponse for ValidationErrorResponse {
+    fn into_response(self) -> Response {
+        (StatusCode::UNPROCESSABLE_ENTITY, Json(self)).into_response()
+    }
+}
+
+/// Rejection returned by [`ValidJson`].
+#[derive(Debug)]
+pub enum ValidJsonRejection {
+    /// The body was not valid JSON, or the content type was wrong
+    Json(JsonRejection),
+    /// The body was well-formed but violated the type's validation rules
+    Validation(ValidationErrorResponse),
+}
+
+impl IntoResponse for ValidJsonRejection {
+    fn into_response(self) -> Response {
+        match self {
+            ValidJsonRejection::Json(rejection) => rejection.into_response(),
+            ValidJsonRejection::Validation(response) => response.into_response(),
+        }
+    }
+}
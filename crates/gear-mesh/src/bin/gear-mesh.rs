@@ -75,6 +75,9 @@ fn diff_command(old: &PathBuf, new: &PathBuf, markdown: bool) -> Result<()> {
     for change in report.additions {
         println!("ADDED: {}", change);
     }
+    for change in report.deprecations {
+        println!("DEPRECATED: {}", change);
+    }
 
     Ok(())
 }
@@ -2,6 +2,9 @@
 ///
 /// # Example
 ///
+/// The helper modules the output imports, such as `gear-mesh.brand.ts` for Branded
+/// Types, are written to the same directory.
+///
 /// ```ignore
 /// use gear_mesh::export_types;
 ///
@@ -26,7 +29,7 @@ macro_rules! export_types {
 
             // Generate TypeScript
             let config = GeneratorConfig::new();
-            let mut generator = TypeScriptGenerator::new(config);
+            let mut generator = TypeScriptGenerator::new(config.clone());
             let output_content = generator.generate(&types);
 
             // Create output directory
@@ -39,6 +42,10 @@ macro_rules! export_types {
             fs::write(output_path, output_content)
                 .expect(&format!("Failed to write TypeScript definitions to {}", $output));
 
+            // Write the helper modules the output imports (e.g. `gear-mesh.brand.ts`)
+            $crate::write_export_modules(output_path, &types, &config)
+                .expect(&format!("Failed to write helper modules next to {}", $output));
+
             println!("✅ Generated TypeScript types: {}", $output);
         }
     };
@@ -78,6 +78,18 @@ pub fn generate_with_config(
         crate::cache::OutputCache::default()
     };
     write_output(output_path, &output, config.enable_cache, &mut cache)?;
+    let output_dir = output_path.parent().unwrap_or(std::path::Path::new("."));
+    write_message_catalog(output_dir, &types, &config, &mut cache)?;
+    write_validator_stubs(output_dir, &types, &config)?;
+    write_validation_module(output_dir, &types, &config, &mut cache)?;
+    write_brand_module(output_dir, &types, &config, &mut cache)?;
+    let file_name = output_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    write_source_map(output_dir, &types, &config, &mut cache, |_| {
+        file_name.clone()
+    })?;
     if config.enable_cache {
         cache.persist(&cache_path)?;
     }
@@ -104,6 +116,12 @@ pub fn generate_types_to_dir_with_config(
 
     fs::create_dir_all(output_dir)?;
 
+    // 非推奨の型だけのモジュールを作らないよう、振り分ける前に取り除く
+    let types = if config.omit_deprecated {
+        crate::utils::strip_deprecated(&types)
+    } else {
+        types
+    };
     let organizer = crate::ModuleOrganizer::new(&types);
     let modules = organizer.organize(&types, &config.module_strategy);
     let type_index = organizer.build_type_index(&modules);
@@ -126,7 +144,9 @@ pub fn generate_types_to_dir_with_config(
             &type_index,
             config.generate_zod,
         );
-        let mut generator = crate::TypeScriptGenerator::new(config.clone());
+        let mut generator = crate::TypeScriptGenerator::new(config.clone())
+            .with_module(relative_path.as_str())
+            .with_type_context(&types);
         let content = generator.generate_with_imports(module_types, &imports);
         write_output(&file_path, &content, config.enable_cache, &mut cache)?;
         println!("  ✓ {}", relative_path);
@@ -151,6 +171,14 @@ pub fn generate_types_to_dir_with_config(
         println!("   📄 index.ts created");
     }
 
+    write_message_catalog(output_dir, &types, &config, &mut cache)?;
+    write_validator_stubs(output_dir, &types, &config)?;
+    write_validation_module(output_dir, &types, &config, &mut cache)?;
+    write_brand_module(output_dir, &types, &config, &mut cache)?;
+    write_source_map(output_dir, &types, &config, &mut cache, |ty| {
+        type_index.get(&ty.name).cloned().unwrap_or_default()
+    })?;
+
     if config.enable_cache {
         cache.persist(&cache_path)?;
     }
@@ -160,10 +188,183 @@ pub fn generate_types_to_dir_with_config(
     Ok(())
 }
 
+/// Writes `gear-mesh.messages.ts` and adds new message keys to `locales/<locale>.json`.
+///
+/// Existing translations are kept; keys missing from a locale get the default text.
+fn write_message_catalog(
+    output_dir: &std::path::Path,
+    types: &[crate::GearMeshType],
+    config: &crate::GeneratorConfig,
+    cache: &mut crate::cache::OutputCache,
+) -> std::io::Result<()> {
+    use std::collections::BTreeMap;
+    use std::fs;
+
+    let catalog = crate::MessageCatalog::collect(types);
+    if catalog.is_empty() {
+        return Ok(());
+    }
+
+    let mut locales = Vec::new();
+    for locale in &config.message_locales {
+        let path = output_dir
+            .join(crate::LOCALES_DIR)
+            .join(format!("{locale}.json"));
+        let existing = path.exists();
+        let mut translations = if existing {
+            serde_json::from_str::<BTreeMap<String, String>>(&fs::read_to_string(&path)?).map_err(
+                |err| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("{}: {err}", path.display()),
+                    )
+                },
+            )?
+        } else {
+            BTreeMap::new()
+        };
+        if catalog.merge_into(&mut translations) || !existing {
+            let json =
+                serde_json::to_string_pretty(&translations).map_err(std::io::Error::other)?;
+            write_output(&path, &format!("{json}\n"), false, cache)?;
+        }
+        locales.push((locale.clone(), translations));
+    }
+
+    let module = catalog.render_module(&locales, config);
+    write_output(
+        &output_dir.join(crate::MESSAGES_MODULE),
+        &module,
+        config.enable_cache,
+        cache,
+    )?;
+    println!(
+        "   🌐 {} message keys in {} locale(s)",
+        catalog.entries().len(),
+        locales.len()
+    );
+    Ok(())
+}
+
+/// Writes `gear-mesh.validation.ts` when a type uses `#[gear_mesh(validate)]`.
+fn write_validation_module(
+    output_dir: &std::path::Path,
+    types: &[crate::GearMeshType],
+    config: &crate::GeneratorConfig,
+    cache: &mut crate::cache::OutputCache,
+) -> std::io::Result<()> {
+    let generator = crate::ValidationGenerator::new(config.clone());
+    if !config.generate_zod
+        || !types
+            .iter()
+            .any(|ty| generator.generate_validator_functions(ty).is_some())
+    {
+        return Ok(());
+    }
+
+    write_output(
+        &output_dir.join(crate::VALIDATION_MODULE),
+        &generator.generate_validation_module(),
+        config.enable_cache,
+        cache,
+    )
+}
+
+/// Writes the helper modules the generated file imports, next to `output_path`.
+///
+/// Used by `export_types!`, which writes the type definitions itself.
+#[doc(hidden)]
+pub fn write_export_modules(
+    output_path: &std::path::Path,
+    types: &[crate::GearMeshType],
+    config: &crate::GeneratorConfig,
+) -> std::io::Result<()> {
+    let output_dir = output_path.parent().unwrap_or(std::path::Path::new("."));
+    let mut cache = crate::cache::OutputCache::default();
+    write_validation_module(output_dir, types, config, &mut cache)?;
+    write_brand_module(output_dir, types, config, &mut cache)
+}
+
+/// Writes `gear-mesh.brand.ts` when a Branded Type is generated.
+fn write_brand_module(
+    output_dir: &std::path::Path,
+    types: &[crate::GearMeshType],
+    config: &crate::GeneratorConfig,
+    cache: &mut crate::cache::OutputCache,
+) -> std::io::Result<()> {
+    if !config.generate_branded
+        || !types
+            .iter()
+            .any(|ty| ty.attributes.branded && matches!(ty.kind, crate::TypeKind::Newtype(_)))
+    {
+        return Ok(());
+    }
+
+    write_output(
+        &output_dir.join(crate::BRAND_MODULE),
+        &crate::BrandedTypeGenerator::new(config.clone()).generate_helpers(),
+        config.enable_cache,
+        cache,
+    )
+}
+
+/// Writes `gear-mesh.sourcemap.json`, mapping each declaration to its Rust definition.
+fn write_source_map(
+    output_dir: &std::path::Path,
+    types: &[crate::GearMeshType],
+    config: &crate::GeneratorConfig,
+    cache: &mut crate::cache::OutputCache,
+    output: impl Fn(&crate::GearMeshType) -> String,
+) -> std::io::Result<()> {
+    if !config.generate_source_map {
+        return Ok(());
+    }
+
+    let source_map = crate::SourceMap::collect(types, output);
+    write_output(
+        &output_dir.join(crate::SOURCE_MAP_FILE),
+        &source_map.render_json(),
+        config.enable_cache,
+        cache,
+    )?;
+    println!("   🗺️  {} declarations mapped", source_map.entries().len());
+    Ok(())
+}
+
+/// Writes stubs for custom validators to `validators.ts` when enabled.
+///
+/// The file is user-owned: stubs are only appended for validators it does not declare yet.
+fn write_validator_stubs(
+    output_dir: &std::path::Path,
+    types: &[crate::GearMeshType],
+    config: &crate::GeneratorConfig,
+) -> std::io::Result<()> {
+    use std::fs;
+
+    if !config.generate_validator_stubs {
+        return Ok(());
+    }
+
+    let path = output_dir.join(crate::VALIDATORS_MODULE);
+    let existing = if path.exists() {
+        Some(fs::read_to_string(&path)?)
+    } else {
+        None
+    };
+    let generator = crate::TypeScriptGenerator::new(config.clone());
+    if let Some(stubs) = generator.generate_validator_stubs(types, existing.as_deref()) {
+        fs::write(&path, stubs)?;
+        println!("   🧩 {} updated", crate::VALIDATORS_MODULE);
+    }
+    Ok(())
+}
+
+/// Collects every registered type, with `#[gear_mesh(rename = "...")]` applied.
 fn collect_registered_types() -> Vec<crate::GearMeshType> {
-    inventory::iter::<TypeInfo>()
+    let types = inventory::iter::<TypeInfo>()
         .map(|info| (info.get_type)())
-        .collect()
+        .collect::<Vec<_>>();
+    crate::utils::apply_type_renames(&types)
 }
 
 fn write_output(
@@ -9,6 +9,7 @@
 //! - **Doc Comments**: Rust doc comments → JSDoc
 //! - **BigInt Support**: Automatic i64/u64 → bigint conversion
 //! - **Validation**: Runtime validation function generation
+//! - **Axum Integration**: `gear_mesh::axum::ValidJson<T>` extractor (`axum` feature)
 //! - **Serde Integration**: Full serde attribute support
 //!
 //! ## Quick Start
@@ -31,10 +32,13 @@
 //! Generated TypeScript:
 //!
 //! ```typescript
-//! type Brand<T, B> = T & { readonly __brand: B };
+//! import type { Brand } from './gear-mesh.brand';
 //!
 //! export type UserId = Brand<number, "UserId">;
 //! export const UserId = (value: number): UserId => value as UserId;
+//! export function isUserId(value: unknown): value is UserId {
+//!     return typeof value === "number";
+//! }
 //!
 //! export interface User {
 //!     id: UserId;
@@ -77,21 +81,35 @@
 pub use gear_mesh_generator::*;
 
 // Explicitly re-export commonly used items for discoverability
-pub use gear_mesh_core::{DocComment, GearMeshType, TypeKind, ValidationRule};
+pub use gear_mesh_core::{DocComment, GearMeshType, SourceLocation, TypeKind, ValidationRule};
 
 pub use gear_mesh_derive::GearMesh;
 
 // Re-export inventory for use in proc-macro
 pub use inventory;
 
+// Lets `#[derive(GearMesh)]` expand to `::gear_mesh::...` paths inside this crate
+extern crate self as gear_mesh;
+
 // Automatic type collection
 mod cache;
 mod inventory_collect;
 mod migration;
+#[doc(hidden)]
+pub use inventory_collect::write_export_modules;
 pub use inventory_collect::{TypeInfo, generate_types, generate_types_to_dir};
 pub use inventory_collect::{generate_types_to_dir_with_config, generate_with_config};
 pub use migration::{MigrationReport, diff_typescript, parse_typescript_snapshot};
 
+// Runtime validation of JSON payloads against the IR rules
+#[cfg(feature = "validation")]
+mod validator;
+#[cfg(feature = "validation")]
+pub use validator::{CustomValidator, FieldError, validate_value};
+
+#[cfg(feature = "axum")]
+pub mod axum;
+
 // Output path registry for automatic generation
 mod output_registry;
 mod type_deps;
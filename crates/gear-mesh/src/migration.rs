@@ -4,6 +4,8 @@ use std::collections::BTreeMap;
 pub struct TypeScriptSnapshot {
     pub interfaces: BTreeMap<String, InterfaceDef>,
     pub aliases: BTreeMap<String, String>,
+    /// Members marked `@deprecated`, keyed by how the report names them, with the note
+    pub deprecated: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -21,11 +23,15 @@ pub struct FieldDef {
 pub struct MigrationReport {
     pub breaking_changes: Vec<String>,
     pub additions: Vec<String>,
+    /// Members that became `@deprecated` (still present, so not breaking yet)
+    pub deprecations: Vec<String>,
 }
 
 impl MigrationReport {
     pub fn is_empty(&self) -> bool {
-        self.breaking_changes.is_empty() && self.additions.is_empty()
+        self.breaking_changes.is_empty()
+            && self.additions.is_empty()
+            && self.deprecations.is_empty()
     }
 
     pub fn to_markdown(&self, from_label: &str, to_label: &str) -> String {
@@ -53,6 +59,15 @@ impl MigrationReport {
             }
         }
 
+        out.push_str("\n## Deprecations\n");
+        if self.deprecations.is_empty() {
+            out.push_str("- None detected\n");
+        } else {
+            for change in &self.deprecations {
+                out.push_str(&format!("- {}\n", change));
+            }
+        }
+
         out
     }
 }
@@ -129,15 +144,31 @@ pub fn diff_typescript(old_source: &str, new_source: &str) -> MigrationReport {
         }
     }
 
+    for (subject, note) in &new_snapshot.deprecated {
+        if !old_snapshot.deprecated.contains_key(subject) {
+            report.deprecations.push(if note.is_empty() {
+                format!("{} was deprecated", subject)
+            } else {
+                format!("{} was deprecated: {}", subject, note)
+            });
+        }
+    }
+
     report
 }
 
 pub fn parse_typescript_snapshot(source: &str) -> TypeScriptSnapshot {
     let mut snapshot = TypeScriptSnapshot::default();
     let mut lines = source.lines().peekable();
+    let mut docs = DocTracker::default();
 
     while let Some(line) = lines.next() {
         let trimmed = line.trim();
+        if docs.consume(trimmed) {
+            continue;
+        }
+        let deprecation = docs.take();
+
         if let Some(body) = trimmed
             .strip_prefix("export interface ")
             .and_then(|rest| rest.strip_suffix(" {"))
@@ -146,17 +177,26 @@ pub fn parse_typescript_snapshot(source: &str) -> TypeScriptSnapshot {
             if name.is_empty() {
                 continue;
             }
+            if let Some(note) = deprecation {
+                snapshot.deprecated.insert(format!("`{}`", name), note);
+            }
             let mut interface = InterfaceDef::default();
             for field_line in lines.by_ref() {
                 let trimmed = field_line.trim();
                 if trimmed == "}" {
                     break;
                 }
-                if trimmed.starts_with("/**") || trimmed.starts_with('*') || trimmed.is_empty() {
+                if docs.consume(trimmed) || trimmed.is_empty() {
                     continue;
                 }
+                let deprecation = docs.take();
 
                 if let Some((field, ty)) = parse_field(trimmed) {
+                    if let Some(note) = deprecation {
+                        snapshot
+                            .deprecated
+                            .insert(format!("`{}.{}`", name, field), note);
+                    }
                     interface.fields.insert(field, ty);
                 }
             }
@@ -164,19 +204,105 @@ pub fn parse_typescript_snapshot(source: &str) -> TypeScriptSnapshot {
             continue;
         }
 
-        if let Some(rest) = trimmed.strip_prefix("export type ")
-            && let Some((name, body)) = rest.split_once(" = ")
-        {
-            snapshot.aliases.insert(
-                name.trim().to_string(),
-                body.trim_end_matches(';').trim().to_string(),
-            );
+        let Some(rest) = trimmed.strip_prefix("export type ") else {
+            continue;
+        };
+        let (name, body) = match rest.split_once(" = ") {
+            Some((name, body)) => (name.trim(), body.to_string()),
+            // 複数行のユニオン (`export type X =` の後に `| member` が続く)
+            None => match rest.strip_suffix(" =") {
+                Some(name) => (
+                    name.trim(),
+                    parse_union_members(name.trim(), &mut lines, &mut snapshot),
+                ),
+                None => continue,
+            },
+        };
+        if let Some(note) = deprecation {
+            snapshot.deprecated.insert(format!("`{}`", name), note);
         }
+        snapshot.aliases.insert(
+            name.to_string(),
+            body.trim_end_matches(';').trim().to_string(),
+        );
     }
 
     snapshot
 }
 
+/// Reads the `| member` lines of a multi-line union and returns them joined on one line
+fn parse_union_members<'a>(
+    name: &str,
+    lines: &mut impl Iterator<Item = &'a str>,
+    snapshot: &mut TypeScriptSnapshot,
+) -> String {
+    let mut members: Vec<String> = Vec::new();
+    let mut docs = DocTracker::default();
+    let mut depth = 0usize;
+    for line in lines {
+        let trimmed = line.trim();
+        if depth == 0 {
+            if docs.consume(trimmed) {
+                continue;
+            }
+            if let Some(member) = trimmed.strip_prefix('|') {
+                let member = member.trim();
+                if let Some(note) = docs.take() {
+                    let variant = member.split('"').nth(1).unwrap_or(member);
+                    snapshot
+                        .deprecated
+                        .insert(format!("Variant `{}` of `{}`", variant, name), note);
+                }
+                members.push(member.to_string());
+            } else if let Some(last) = members.last_mut() {
+                last.push(' ');
+                last.push_str(trimmed);
+            }
+        } else if !is_comment(trimmed)
+            && let Some(last) = members.last_mut()
+        {
+            // 構造体バリアントのフィールド
+            last.push(' ');
+            last.push_str(trimmed);
+        }
+        depth = (depth + trimmed.matches('{').count()).saturating_sub(trimmed.matches('}').count());
+        if depth == 0 && trimmed.ends_with(';') {
+            break;
+        }
+    }
+    members.join(" | ")
+}
+
+fn is_comment(line: &str) -> bool {
+    line.starts_with("/**") || line.starts_with('*')
+}
+
+/// Follows JSDoc blocks and remembers whether the last one was `@deprecated`
+#[derive(Default)]
+struct DocTracker {
+    in_comment: bool,
+    deprecation: Option<String>,
+}
+
+impl DocTracker {
+    /// Returns `true` if `line` belongs to a doc comment
+    fn consume(&mut self, line: &str) -> bool {
+        if !self.in_comment && !line.starts_with("/**") {
+            return false;
+        }
+        self.in_comment = !line.ends_with("*/");
+        if let Some((_, note)) = line.split_once("@deprecated") {
+            let note = note.trim_end_matches("*/").trim();
+            self.deprecation = Some(note.to_string());
+        }
+        true
+    }
+
+    fn take(&mut self) -> Option<String> {
+        self.deprecation.take()
+    }
+}
+
 fn parse_field(line: &str) -> Option<(String, FieldDef)> {
     let normalized = line.trim_end_matches(';');
     let (field, ty) = normalized.split_once(':')?;
@@ -243,6 +369,71 @@ export interface User {
         );
     }
 
+    #[test]
+    fn diff_reports_new_deprecations_separately() {
+        let old_ts = r#"
+export interface User {
+    login: string;
+}
+
+export type Status =
+    | "active"
+    | "legacy";
+"#;
+        let new_ts = r#"
+/** @deprecated Use `Account` */
+export interface User {
+    /**
+     * Login name
+     * @deprecated Since 1.2.0. Use `email` instead.
+     */
+    login: string;
+}
+
+export type Status =
+    | "active"
+    /** @deprecated */
+    | "legacy";
+"#;
+
+        let report = diff_typescript(old_ts, new_ts);
+        assert!(report.breaking_changes.is_empty());
+        assert_eq!(
+            report.deprecations,
+            [
+                "Variant `legacy` of `Status` was deprecated",
+                "`User.login` was deprecated: Since 1.2.0. Use `email` instead.",
+                "`User` was deprecated: Use `Account`",
+            ]
+        );
+        assert!(
+            report
+                .to_markdown("v1", "v2")
+                .contains("## Deprecations\n- Variant")
+        );
+        // すでに非推奨だったものは再度報告しない
+        assert!(diff_typescript(new_ts, new_ts).deprecations.is_empty());
+    }
+
+    #[test]
+    fn parser_joins_multi_line_unions() {
+        let source = r#"
+export type Event =
+    /** Sent on login */
+    | { "Login": {
+        /** Account ID */
+        id: number;
+    } }
+    | "Logout";
+"#;
+
+        let snapshot = parse_typescript_snapshot(source);
+        assert_eq!(
+            snapshot.aliases["Event"],
+            "{ \"Login\": { id: number; } } | \"Logout\""
+        );
+    }
+
     #[test]
     fn parser_handles_generic_interfaces() {
         let source = r#"
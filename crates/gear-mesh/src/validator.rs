@@ -0,0 +1,1202 @@
+//! Runtime validation driven by the GearMesh IR.
+//!
+//! The rules collected by `#[derive(GearMesh)]` (`FieldInfo.validations`) are
+//! evaluated against a `serde_json::Value`, so the server enforces exactly what
+//! the generated Zod schemas check on the client.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+
+use gear_mesh_core::{
+    Condition, ConditionValue, CrossFieldRule, EnumRepresentation, EnumVariant, FieldInfo,
+    GearMeshType, INTEGER_STRING_PATTERN, IpVersion, QuoteStyle, TypeKind, TypeRef, UUID_PATTERN,
+    ValidationMessage, ValidationRule, VariantContent, interpolate_message, rust_regex_pattern,
+    template_literal_pattern,
+};
+use gear_mesh_generator::utils::{apply_rename_all, resolve_field_name};
+
+const EMAIL_PATTERN: &str = r"^[^\s@]+@[^\s@]+\.[^\s@]+$";
+const URL_PATTERN: &str = r"^https?://[^\s]+$";
+
+static REGEX_CACHE: Lazy<Mutex<HashMap<String, Regex>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A single failed validation rule.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "axum", derive(crate::GearMesh))]
+pub struct FieldError {
+    /// Dotted path to the offending value, e.g. `address.city` or `items[0]`
+    pub path: String,
+    /// Machine-readable rule identifier, e.g. `length` or `cross_field`
+    pub code: String,
+    /// Human-readable message
+    pub message: String,
+    /// Message catalog key declared with `message_key = "..."`, for client-side localization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_key: Option<String>,
+}
+
+impl FieldError {
+    fn new(path: &str, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            path: path.to_string(),
+            code: code.to_string(),
+            message: message.into(),
+            message_key: None,
+        }
+    }
+
+    /// Replaces the built-in message with the one declared on the rule.
+    fn apply_message(&mut self, message: &ValidationMessage, params: &[(&str, String)]) {
+        match message {
+            ValidationMessage::Text(text) => self.message = interpolate_message(text, params),
+            ValidationMessage::Key { key, default } => {
+                if let Some(default) = default {
+                    self.message = interpolate_message(default, params);
+                }
+                self.message_key = Some(key.clone());
+            }
+        }
+    }
+}
+
+/// A server-side implementation of a `custom = "..."` validation rule.
+///
+/// Custom rules are looked up by name when validating. A rule without a registered
+/// validator fails with the code `custom_unregistered`. Register one with:
+///
+/// ```ignore
+/// gear_mesh::inventory::submit! {
+///     gear_mesh::CustomValidator { name: "Username", check: |value| value.as_str().is_some() }
+/// }
+/// ```
+pub struct CustomValidator {
+    pub name: &'static str,
+    pub check: fn(&Value) -> bool,
+}
+
+inventory::collect!(CustomValidator);
+
+/// Validates a JSON value against the rules recorded for a registered type.
+///
+/// Nested fields whose type is registered through `#[derive(GearMesh)]` are
+/// validated recursively. Conditional rules written with `when(...)` are
+/// evaluated here as well; those whose condition is an opaque JavaScript
+/// expression (`conditional(condition = "...")`) are only enforced by the
+/// generated Zod schema.
+pub fn validate_value(ty: &GearMeshType, value: &Value) -> Result<(), Vec<FieldError>> {
+    let mut errors = Vec::new();
+    validate_type(ty, value, "", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn validate_type(ty: &GearMeshType, value: &Value, path: &str, errors: &mut Vec<FieldError>) {
+    match &ty.kind {
+        TypeKind::Struct(s) => validate_fields(
+            &s.fields,
+            ty.attributes.serde.rename_all,
+            value,
+            path,
+            errors,
+        ),
+        TypeKind::Newtype(newtype) => {
+            // Rules on the newtype itself, as in its Zod brand schema
+            for rule in &newtype.validations {
+                check_rule(rule, value, path, errors);
+            }
+            validate_nested(&newtype.inner, value, path, errors);
+        }
+        TypeKind::Enum(e) => {
+            let tag_of = |variant: &EnumVariant| {
+                apply_rename_all(&variant.name, ty.attributes.serde.rename_all)
+            };
+            let Value::Object(map) = value else {
+                // Unit variants of tagged enums are plain strings without rules
+                if let EnumRepresentation::Untagged = e.representation {
+                    validate_untagged(&e.variants, value, path, errors);
+                }
+                return;
+            };
+            match &e.representation {
+                EnumRepresentation::External => {
+                    for variant in &e.variants {
+                        let tag = tag_of(variant);
+                        if let Some(inner) = map.get(&tag) {
+                            validate_variant(variant, inner, &join_path(path, &tag), errors);
+                        }
+                    }
+                }
+                EnumRepresentation::Internal { tag } => {
+                    // The tag sits next to the variant's fields, or inside a newtype's object
+                    if let Some(variant) = e.variants.iter().find(|variant| {
+                        map.get(tag).and_then(Value::as_str) == Some(&tag_of(variant))
+                    }) {
+                        validate_variant(variant, value, path, errors);
+                    }
+                }
+                EnumRepresentation::Adjacent { tag, content } => {
+                    if let Some(variant) = e.variants.iter().find(|variant| {
+                        map.get(tag).and_then(Value::as_str) == Some(&tag_of(variant))
+                    }) && let Some(inner) = map.get(content)
+                    {
+                        validate_variant(variant, inner, &join_path(path, content), errors);
+                    }
+                }
+                EnumRepresentation::Untagged => {
+                    validate_untagged(&e.variants, value, path, errors);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Validates the content of one variant (`value` is the variant's payload).
+fn validate_variant(
+    variant: &EnumVariant,
+    value: &Value,
+    path: &str,
+    errors: &mut Vec<FieldError>,
+) {
+    match &variant.content {
+        VariantContent::Unit => {}
+        VariantContent::Struct(fields) => validate_fields(fields, None, value, path, errors),
+        VariantContent::Tuple(types) if types.len() == 1 => {
+            validate_nested(&types[0], value, path, errors);
+        }
+        VariantContent::Tuple(types) => {
+            if let Value::Array(items) = value {
+                for (index, (ty, item)) in types.iter().zip(items).enumerate() {
+                    validate_nested(ty, item, &format!("{path}[{index}]"), errors);
+                }
+            }
+        }
+    }
+}
+
+/// Like `z.union`, an untagged value is valid if any variant accepts it.
+///
+/// When none does, the errors of the variant with the fewest failures are reported.
+fn validate_untagged(
+    variants: &[EnumVariant],
+    value: &Value,
+    path: &str,
+    errors: &mut Vec<FieldError>,
+) {
+    let mut best: Option<Vec<FieldError>> = None;
+    for variant in variants {
+        // Skip variants whose JSON shape cannot match, as the Zod union would
+        let shape_matches = match &variant.content {
+            VariantContent::Unit => value.is_null(),
+            VariantContent::Struct(_) => value.is_object(),
+            VariantContent::Tuple(types) if types.len() == 1 => true,
+            VariantContent::Tuple(_) => value.is_array(),
+        };
+        if !shape_matches {
+            continue;
+        }
+        let mut variant_errors = Vec::new();
+        validate_variant(variant, value, path, &mut variant_errors);
+        if variant_errors.is_empty() {
+            return;
+        }
+        if best
+            .as_ref()
+            .is_none_or(|best| variant_errors.len() < best.len())
+        {
+            best = Some(variant_errors);
+        }
+    }
+    errors.extend(best.unwrap_or_default());
+}
+
+fn validate_fields(
+    fields: &[FieldInfo],
+    rename_all: Option<gear_mesh_core::RenameRule>,
+    value: &Value,
+    path: &str,
+    errors: &mut Vec<FieldError>,
+) {
+    let Value::Object(map) = value else {
+        return;
+    };
+    let json_names = fields
+        .iter()
+        .map(|field| (field.name.as_str(), resolve_field_name(field, rename_all)))
+        .collect::<HashMap<_, _>>();
+
+    for field in fields.iter().filter(|field| !field.attributes.skip) {
+        let key = &json_names[field.name.as_str()];
+        let field_path = join_path(path, key);
+        let field_value = map.get(key).unwrap_or(&Value::Null);
+
+        for field_rule in &field.validations {
+            match field_rule.without_message() {
+                ValidationRule::CrossField {
+                    fields: members,
+                    rule,
+                    message,
+                    path: error_path,
+                } => {
+                    let lookup = |name: &String| {
+                        json_names
+                            .get(name.as_str())
+                            .and_then(|key| map.get(key))
+                            .unwrap_or(&Value::Null)
+                    };
+                    let values = members.iter().map(lookup).collect::<Vec<_>>();
+                    if !check_cross_field(rule, &values) {
+                        let target = error_path
+                            .as_ref()
+                            .and_then(|name| json_names.get(name.as_str()))
+                            .unwrap_or(key);
+                        let mut error = FieldError::new(
+                            &join_path(path, target),
+                            "cross_field",
+                            message
+                                .clone()
+                                .unwrap_or_else(|| default_cross_field_message(rule, members)),
+                        );
+                        if let Some(message) = field_rule.message() {
+                            error.apply_message(message, &[]);
+                        }
+                        errors.push(error);
+                    }
+                }
+                ValidationRule::Conditional { condition, rule } => {
+                    let lookup = |name: &str| {
+                        json_names
+                            .get(name)
+                            .and_then(|key| map.get(key))
+                            .unwrap_or(&Value::Null)
+                    };
+                    if evaluate_condition(condition, lookup) {
+                        check_rule(rule, field_value, &field_path, errors);
+                    }
+                }
+                _ => check_rule(field_rule, field_value, &field_path, errors),
+            }
+        }
+
+        let absent = field_value.is_null()
+            && (field.optional || field.attributes.optional || field.attributes.nullable);
+        if !absent {
+            check_fixed_value(field, field_value, &field_path, errors);
+        }
+
+        if !field_value.is_null() {
+            validate_nested(&field.ty, field_value, &field_path, errors);
+        }
+    }
+}
+
+/// Enforces `literal = ...` and `template = "..."` like the generated TypeScript types.
+fn check_fixed_value(field: &FieldInfo, value: &Value, path: &str, errors: &mut Vec<FieldError>) {
+    if let Some(literal) = &field.attributes.literal
+        && !condition_matches(value, literal)
+    {
+        errors.push(FieldError::new(
+            path,
+            "literal",
+            format!("must be {}", literal.to_typescript(QuoteStyle::Mixed)),
+        ));
+    }
+    if let Some(template) = &field.attributes.template {
+        let matches = match (value.as_str(), template_literal_pattern(template)) {
+            (Some(text), Ok(pattern)) => matches_pattern(&pattern, text),
+            _ => false,
+        };
+        if !matches {
+            errors.push(FieldError::new(
+                path,
+                "template",
+                format!("must match `{template}`"),
+            ));
+        }
+    }
+}
+
+fn check_rule(rule: &ValidationRule, value: &Value, path: &str, errors: &mut Vec<FieldError>) {
+    if let ValidationRule::WithMessage { rule, message } = rule {
+        let start = errors.len();
+        check_rule(rule, value, path, errors);
+        let params = rule.message_params();
+        for error in &mut errors[start..] {
+            error.apply_message(message, &params);
+        }
+        return;
+    }
+    if value.is_null() {
+        if matches!(rule, ValidationRule::Required) {
+            errors.push(FieldError::new(path, "required", "is required"));
+        }
+        return;
+    }
+
+    match rule {
+        ValidationRule::Range { min, max } => {
+            let Some(number) = numeric_value(value) else {
+                return;
+            };
+            if let Some(min) = min
+                && number < *min
+            {
+                errors.push(FieldError::new(
+                    path,
+                    "range",
+                    format!("must be greater than or equal to {min}"),
+                ));
+            }
+            if let Some(max) = max
+                && number > *max
+            {
+                errors.push(FieldError::new(
+                    path,
+                    "range",
+                    format!("must be less than or equal to {max}"),
+                ));
+            }
+        }
+        ValidationRule::ExclusiveRange { min, max } => {
+            let Some(number) = numeric_value(value) else {
+                return;
+            };
+            if let Some(min) = min
+                && number <= *min
+            {
+                errors.push(FieldError::new(
+                    path,
+                    "range",
+                    format!("must be greater than {min}"),
+                ));
+            }
+            if let Some(max) = max
+                && number >= *max
+            {
+                errors.push(FieldError::new(
+                    path,
+                    "range",
+                    format!("must be less than {max}"),
+                ));
+            }
+        }
+        ValidationRule::Length { min, max } => {
+            let Some(length) = value_length(value) else {
+                return;
+            };
+            if let Some(min) = min
+                && length < *min
+            {
+                errors.push(FieldError::new(
+                    path,
+                    "length",
+                    format!("must have a length of at least {min}"),
+                ));
+            }
+            if let Some(max) = max
+                && length > *max
+            {
+                errors.push(FieldError::new(
+                    path,
+                    "length",
+                    format!("must have a length of at most {max}"),
+                ));
+            }
+        }
+        ValidationRule::NonEmpty => {
+            if value_length(value) == Some(0) {
+                errors.push(FieldError::new(path, "non_empty", "must not be empty"));
+            }
+        }
+        ValidationRule::Unique => {
+            if let Value::Array(items) = value
+                && items
+                    .iter()
+                    .enumerate()
+                    .any(|(index, item)| items[..index].contains(item))
+            {
+                errors.push(FieldError::new(path, "unique", "items must be unique"));
+            }
+        }
+        ValidationRule::Each(rules) => match value {
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    for rule in rules {
+                        check_rule(rule, item, &format!("{path}[{index}]"), errors);
+                    }
+                }
+            }
+            Value::Object(entries) => {
+                for (key, item) in entries {
+                    for rule in rules {
+                        check_rule(rule, item, &join_path(path, key), errors);
+                    }
+                }
+            }
+            _ => {}
+        },
+        ValidationRule::Contains(needle) => {
+            let contains = match value {
+                Value::String(text) => text.contains(needle.as_str()),
+                Value::Array(items) => items.iter().any(|item| item.as_str() == Some(needle)),
+                Value::Object(entries) => entries.contains_key(needle),
+                _ => true,
+            };
+            if !contains {
+                errors.push(FieldError::new(
+                    path,
+                    "contains",
+                    format!("must contain `{needle}`"),
+                ));
+            }
+        }
+        ValidationRule::StartsWith(prefix) => {
+            if let Some(text) = value.as_str()
+                && !text.starts_with(prefix.as_str())
+            {
+                errors.push(FieldError::new(
+                    path,
+                    "starts_with",
+                    format!("must start with `{prefix}`"),
+                ));
+            }
+        }
+        ValidationRule::EndsWith(suffix) => {
+            if let Some(text) = value.as_str()
+                && !text.ends_with(suffix.as_str())
+            {
+                errors.push(FieldError::new(
+                    path,
+                    "ends_with",
+                    format!("must end with `{suffix}`"),
+                ));
+            }
+        }
+        ValidationRule::Uuid => {
+            if let Some(text) = value.as_str()
+                && !matches_pattern(UUID_PATTERN, text)
+            {
+                errors.push(FieldError::new(path, "uuid", "must be a valid UUID"));
+            }
+        }
+        ValidationRule::Ip(version) => {
+            if let Some(text) = value.as_str() {
+                let valid = match (text.parse::<IpAddr>(), version) {
+                    (Ok(IpAddr::V4(_)), Some(IpVersion::V6))
+                    | (Ok(IpAddr::V6(_)), Some(IpVersion::V4))
+                    | (Err(_), _) => false,
+                    (Ok(_), _) => true,
+                };
+                if !valid {
+                    errors.push(FieldError::new(path, "ip", "must be a valid IP address"));
+                }
+            }
+        }
+        ValidationRule::Ascii => {
+            if let Some(text) = value.as_str()
+                && !text.is_ascii()
+            {
+                errors.push(FieldError::new(
+                    path,
+                    "ascii",
+                    "must contain only ASCII characters",
+                ));
+            }
+        }
+        ValidationRule::Alphanumeric => {
+            if let Some(text) = value.as_str()
+                && !text.chars().all(char::is_alphanumeric)
+            {
+                errors.push(FieldError::new(
+                    path,
+                    "alphanumeric",
+                    "must contain only letters and digits",
+                ));
+            }
+        }
+        ValidationRule::MultipleOf(step) => {
+            if let Some(number) = numeric_value(value) {
+                let quotient = number / step;
+                if (quotient - quotient.round()).abs() > 1e-9 {
+                    errors.push(FieldError::new(
+                        path,
+                        "multiple_of",
+                        format!("must be a multiple of {step}"),
+                    ));
+                }
+            }
+        }
+        ValidationRule::Pattern(pattern) => {
+            if let Some(text) = value.as_str()
+                && !matches_pattern(pattern, text)
+            {
+                errors.push(FieldError::new(
+                    path,
+                    "pattern",
+                    format!("must match the pattern `{pattern}`"),
+                ));
+            }
+        }
+        ValidationRule::Email => {
+            if let Some(text) = value.as_str()
+                && !matches_pattern(EMAIL_PATTERN, text)
+            {
+                errors.push(FieldError::new(
+                    path,
+                    "email",
+                    "must be a valid email address",
+                ));
+            }
+        }
+        ValidationRule::Url => {
+            if let Some(text) = value.as_str()
+                && !matches_pattern(URL_PATTERN, text)
+            {
+                errors.push(FieldError::new(path, "url", "must be a valid URL"));
+            }
+        }
+        ValidationRule::Custom { name, message } => {
+            // The Zod schema always runs the client-side validator, so an unregistered one must not pass
+            match inventory::iter::<CustomValidator>().find(|v| v.name == name) {
+                Some(validator) if (validator.check)(value) => {}
+                Some(_) => errors.push(FieldError::new(
+                    path,
+                    "custom",
+                    message
+                        .clone()
+                        .unwrap_or_else(|| format!("failed {name} validation")),
+                )),
+                None => errors.push(FieldError::new(
+                    path,
+                    "custom_unregistered",
+                    format!("no server-side validator is registered for `{name}`"),
+                )),
+            }
+        }
+        ValidationRule::Required
+        | ValidationRule::CrossField { .. }
+        | ValidationRule::Conditional { .. }
+        | ValidationRule::WithMessage { .. } => {}
+    }
+}
+
+/// Recurses into container elements and registered types.
+fn validate_nested(type_ref: &TypeRef, value: &Value, path: &str, errors: &mut Vec<FieldError>) {
+    match type_ref.name.as_str() {
+        "Option" | "Box" | "Arc" | "Rc" | "Cow" => {
+            if let Some(inner) = type_ref.generics.last()
+                && !value.is_null()
+            {
+                validate_nested(inner, value, path, errors);
+            }
+        }
+        "Vec" | "__array__" | "__slice__" | "HashSet" | "BTreeSet" => {
+            if let (Some(inner), Value::Array(items)) = (type_ref.generics.first(), value) {
+                for (index, item) in items.iter().enumerate() {
+                    validate_nested(inner, item, &format!("{path}[{index}]"), errors);
+                }
+            }
+        }
+        "HashMap" | "BTreeMap" => {
+            if let (Some(inner), Value::Object(entries)) = (type_ref.generics.get(1), value) {
+                for (key, item) in entries {
+                    validate_nested(inner, item, &join_path(path, key), errors);
+                }
+            }
+        }
+        name => {
+            if let Some(ty) = registered_type(name) {
+                validate_type(&ty, value, path, errors);
+            }
+        }
+    }
+}
+
+fn registered_type(name: &str) -> Option<GearMeshType> {
+    inventory::iter::<crate::TypeInfo>()
+        .find(|info| info.type_name == name)
+        .map(|info| (info.get_type)())
+}
+
+fn check_cross_field(rule: &CrossFieldRule, values: &[&Value]) -> bool {
+    match rule {
+        CrossFieldRule::Match => values.windows(2).all(|pair| pair[0] == pair[1]),
+        CrossFieldRule::AtLeastOne => values.iter().any(|value| is_present(value)),
+        CrossFieldRule::MutuallyExclusive => {
+            values.iter().filter(|value| is_truthy(value)).count() <= 1
+        }
+    }
+}
+
+/// Evaluates a structured condition; JavaScript expressions are never satisfied here.
+fn evaluate_condition<'a>(condition: &Condition, lookup: impl Fn(&str) -> &'a Value) -> bool {
+    match condition {
+        Condition::Expression(_) => false,
+        Condition::Equals { field, value } => condition_matches(lookup(field), value),
+        Condition::NotEquals { field, value } => !condition_matches(lookup(field), value),
+        Condition::Present(field) => is_present(lookup(field)),
+        Condition::Absent(field) => !is_present(lookup(field)),
+    }
+}
+
+fn condition_matches(actual: &Value, expected: &ConditionValue) -> bool {
+    match expected {
+        ConditionValue::String(expected) => actual.as_str() == Some(expected),
+        ConditionValue::Number(expected) => actual.as_f64() == Some(*expected),
+        ConditionValue::Bool(expected) => actual.as_bool() == Some(*expected),
+    }
+}
+
+fn default_cross_field_message(rule: &CrossFieldRule, fields: &[String]) -> String {
+    match rule {
+        CrossFieldRule::Match => format!("{} must match {}", fields[0], fields[1]),
+        CrossFieldRule::AtLeastOne => "At least one field must be provided".to_string(),
+        CrossFieldRule::MutuallyExclusive => "Fields are mutually exclusive".to_string(),
+    }
+}
+
+/// Mirrors the generated `data.x !== undefined && data.x !== null && data.x !== ""` check.
+fn is_present(value: &Value) -> bool {
+    !matches!(value, Value::Null) && value.as_str() != Some("")
+}
+
+/// Mirrors JavaScript truthiness as used by `[...].filter(Boolean)`.
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(_) | Value::Object(_) => true,
+    }
+}
+
+/// Numbers, and integers sent as decimal strings (`bigint = "string"`).
+fn numeric_value(value: &Value) -> Option<f64> {
+    value.as_f64().or_else(|| {
+        value
+            .as_str()
+            .filter(|text| matches_pattern(INTEGER_STRING_PATTERN, text))
+            .and_then(|text| text.parse::<i128>().ok())
+            .map(|number| number as f64)
+    })
+}
+
+/// Mirrors JavaScript `.length`, which counts strings in UTF-16 code units.
+fn value_length(value: &Value) -> Option<usize> {
+    match value {
+        Value::String(s) => Some(s.encode_utf16().count()),
+        Value::Array(items) => Some(items.len()),
+        Value::Object(entries) => Some(entries.len()),
+        _ => None,
+    }
+}
+
+fn matches_pattern(pattern: &str, text: &str) -> bool {
+    let mut cache = REGEX_CACHE.lock().unwrap();
+    if !cache.contains_key(pattern) {
+        match Regex::new(&rust_regex_pattern(pattern)) {
+            Ok(regex) => {
+                cache.insert(pattern.to_string(), regex);
+            }
+            // Invalid patterns are rejected at derive time; treat anything else as a mismatch.
+            Err(_) => return false,
+        }
+    }
+    cache[pattern].is_match(text)
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gear_mesh_core::{NewtypeType, StructType, TypeAttributes};
+    use serde_json::json;
+
+    fn field(name: &str, ty: TypeRef, validations: Vec<ValidationRule>) -> FieldInfo {
+        FieldInfo {
+            name: name.to_string(),
+            optional: ty.name == "Option",
+            ty,
+            docs: None,
+            validations,
+            serde_attrs: Default::default(),
+            attributes: Default::default(),
+        }
+    }
+
+    fn signup() -> GearMeshType {
+        GearMeshType {
+            name: "Signup".to_string(),
+            kind: TypeKind::Struct(StructType {
+                fields: vec![
+                    field(
+                        "name",
+                        TypeRef::new("String"),
+                        vec![ValidationRule::Length {
+                            min: Some(1),
+                            max: Some(5),
+                        }],
+                    ),
+                    field(
+                        "age",
+                        TypeRef::with_generics("Option", vec![TypeRef::new("i32")]),
+                        vec![ValidationRule::Range {
+                            min: Some(18.0),
+                            max: None,
+                        }],
+                    ),
+                    field("password", TypeRef::new("String"), vec![]),
+                    field(
+                        "password_confirmation",
+                        TypeRef::new("String"),
+                        vec![ValidationRule::CrossField {
+                            fields: vec![
+                                "password_confirmation".to_string(),
+                                "password".to_string(),
+                            ],
+                            rule: CrossFieldRule::Match,
+                            message: None,
+                            path: Some("password_confirmation".to_string()),
+                        }],
+                    ),
+                ],
+            }),
+            docs: None,
+            generics: vec![],
+            attributes: TypeAttributes::default(),
+        }
+    }
+
+    #[test]
+    fn valid_payload_passes() {
+        let value = json!({
+            "name": "Alice",
+            "age": null,
+            "password": "secret",
+            "password_confirmation": "secret",
+        });
+
+        assert_eq!(validate_value(&signup(), &value), Ok(()));
+    }
+
+    #[test]
+    fn collects_field_and_cross_field_errors() {
+        let value = json!({
+            "name": "Alexander",
+            "age": 12,
+            "password": "secret",
+            "password_confirmation": "other",
+        });
+
+        let errors = validate_value(&signup(), &value).unwrap_err();
+        let codes = errors
+            .iter()
+            .map(|error| (error.path.as_str(), error.code.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec![
+                ("name", "length"),
+                ("age", "range"),
+                ("password_confirmation", "cross_field"),
+            ]
+        );
+        assert_eq!(
+            errors[2].message,
+            "password_confirmation must match password"
+        );
+    }
+
+    #[test]
+    fn newtype_rules_are_checked() {
+        let ty = GearMeshType {
+            name: "Username".to_string(),
+            kind: TypeKind::Newtype(NewtypeType {
+                inner: TypeRef::new("String"),
+                validations: vec![ValidationRule::Length {
+                    min: Some(3),
+                    max: None,
+                }],
+                markers: vec![],
+            }),
+            docs: None,
+            generics: vec![],
+            attributes: TypeAttributes {
+                branded: true,
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(validate_value(&ty, &json!("alice")), Ok(()));
+        let errors = validate_value(&ty, &json!("a")).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "length");
+    }
+
+    #[test]
+    fn element_rules_report_indexed_paths() {
+        let ty = GearMeshType {
+            name: "Tags".to_string(),
+            kind: TypeKind::Struct(StructType {
+                fields: vec![field(
+                    "tags",
+                    TypeRef::with_generics("Vec", vec![TypeRef::new("String")]),
+                    vec![
+                        ValidationRule::Unique,
+                        ValidationRule::Each(vec![ValidationRule::StartsWith("#".to_string())]),
+                    ],
+                )],
+            }),
+            docs: None,
+            generics: vec![],
+            attributes: TypeAttributes::default(),
+        };
+
+        let errors = validate_value(&ty, &json!({ "tags": ["#a", "b", "#a"] })).unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| (error.path.as_str(), error.code.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("tags", "unique"), ("tags[1]", "starts_with")]
+        );
+    }
+
+    #[test]
+    fn structured_conditions_gate_nested_rules() {
+        let ty = GearMeshType {
+            name: "Billing".to_string(),
+            kind: TypeKind::Struct(StructType {
+                fields: vec![
+                    field("kind", TypeRef::new("String"), vec![]),
+                    field(
+                        "vat_id",
+                        TypeRef::with_generics("Option", vec![TypeRef::new("String")]),
+                        vec![
+                            ValidationRule::Conditional {
+                                condition: Condition::Equals {
+                                    field: "kind".to_string(),
+                                    value: ConditionValue::String("company".to_string()),
+                                },
+                                rule: Box::new(ValidationRule::Required),
+                            },
+                            ValidationRule::Conditional {
+                                condition: Condition::Present("vat_id".to_string()),
+                                rule: Box::new(ValidationRule::Length {
+                                    min: Some(8),
+                                    max: None,
+                                }),
+                            },
+                        ],
+                    ),
+                ],
+            }),
+            docs: None,
+            generics: vec![],
+            attributes: TypeAttributes::default(),
+        };
+
+        assert_eq!(
+            validate_value(&ty, &json!({ "kind": "person", "vat_id": null })),
+            Ok(())
+        );
+        let errors =
+            validate_value(&ty, &json!({ "kind": "company", "vat_id": null })).unwrap_err();
+        assert_eq!(errors[0].code, "required");
+        let errors =
+            validate_value(&ty, &json!({ "kind": "person", "vat_id": "DE1" })).unwrap_err();
+        assert_eq!(errors[0].code, "length");
+    }
+
+    #[test]
+    fn rule_messages_replace_defaults_and_carry_keys() {
+        let ty = GearMeshType {
+            name: "Profile".to_string(),
+            kind: TypeKind::Struct(StructType {
+                fields: vec![
+                    field(
+                        "name",
+                        TypeRef::new("String"),
+                        vec![ValidationRule::WithMessage {
+                            rule: Box::new(ValidationRule::Length {
+                                min: Some(3),
+                                max: None,
+                            }),
+                            message: ValidationMessage::Text(
+                                "At least {min} characters".to_string(),
+                            ),
+                        }],
+                    ),
+                    field(
+                        "email",
+                        TypeRef::new("String"),
+                        vec![ValidationRule::WithMessage {
+                            rule: Box::new(ValidationRule::Email),
+                            message: ValidationMessage::Key {
+                                key: "profile.email.invalid".to_string(),
+                                default: None,
+                            },
+                        }],
+                    ),
+                ],
+            }),
+            docs: None,
+            generics: vec![],
+            attributes: TypeAttributes::default(),
+        };
+
+        let errors = validate_value(&ty, &json!({ "name": "Al", "email": "nope" })).unwrap_err();
+        assert_eq!(errors[0].message, "At least 3 characters");
+        assert_eq!(errors[0].message_key, None);
+        assert_eq!(errors[1].code, "email");
+        assert_eq!(errors[1].message, "must be a valid email address");
+        assert_eq!(
+            errors[1].message_key.as_deref(),
+            Some("profile.email.invalid")
+        );
+        assert_eq!(
+            serde_json::to_value(&errors[1]).unwrap()["message_key"],
+            "profile.email.invalid"
+        );
+    }
+
+    #[test]
+    fn numeric_rules_accept_integer_strings() {
+        let ty = GearMeshType {
+            name: "Order".to_string(),
+            kind: TypeKind::Struct(StructType {
+                fields: vec![field(
+                    "total",
+                    TypeRef::new("u64"),
+                    vec![ValidationRule::Range {
+                        min: Some(1.0),
+                        max: Some(1000.0),
+                    }],
+                )],
+            }),
+            docs: None,
+            generics: vec![],
+            attributes: TypeAttributes::default(),
+        };
+
+        assert!(validate_value(&ty, &json!({ "total": "250" })).is_ok());
+        let errors = validate_value(&ty, &json!({ "total": "5000" })).unwrap_err();
+        assert_eq!(errors[0].code, "range");
+    }
+
+    #[test]
+    fn field_overrides_rename_and_skip_fields() {
+        let mut label = field(
+            "display_name",
+            TypeRef::new("String"),
+            vec![ValidationRule::Length {
+                min: Some(1),
+                max: None,
+            }],
+        );
+        label.attributes.rename = Some("label".to_string());
+        let mut internal = field(
+            "internal",
+            TypeRef::new("String"),
+            vec![ValidationRule::Email],
+        );
+        internal.attributes.skip = true;
+        let ty = GearMeshType {
+            name: "Profile".to_string(),
+            kind: TypeKind::Struct(StructType {
+                fields: vec![label, internal],
+            }),
+            docs: None,
+            generics: vec![],
+            attributes: TypeAttributes::default(),
+        };
+
+        let errors = validate_value(&ty, &json!({ "label": "" })).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "label");
+    }
+
+    #[test]
+    fn literal_and_template_fields_are_enforced() {
+        let mut version = field("version", TypeRef::new("String"), vec![]);
+        version.attributes.literal = Some(ConditionValue::String("v2".to_string()));
+        let mut id = field("id", TypeRef::new("String"), vec![]);
+        id.attributes.template = Some("usr_${string}".to_string());
+        let ty = GearMeshType {
+            name: "Envelope".to_string(),
+            kind: TypeKind::Struct(StructType {
+                fields: vec![version, id],
+            }),
+            docs: None,
+            generics: vec![],
+            attributes: TypeAttributes::default(),
+        };
+
+        assert!(validate_value(&ty, &json!({ "version": "v2", "id": "usr_42" })).is_ok());
+        let errors = validate_value(&ty, &json!({ "version": "v1", "id": "ord_42" })).unwrap_err();
+        let codes = errors.iter().map(|e| e.code.as_str()).collect::<Vec<_>>();
+        assert_eq!(codes, vec!["literal", "template"]);
+        assert_eq!(errors[0].message, "must be \"v2\"");
+    }
+
+    inventory::submit! {
+        CustomValidator { name: "Lowercase", check: |value| value.as_str().is_some_and(|text| text == text.to_lowercase()) }
+    }
+
+    #[test]
+    fn custom_rules_fail_without_a_registered_validator() {
+        let custom = |name: &str| ValidationRule::Custom {
+            name: name.to_string(),
+            message: None,
+        };
+        let ty = GearMeshType {
+            name: "Profile".to_string(),
+            kind: TypeKind::Struct(StructType {
+                fields: vec![
+                    field("slug", TypeRef::new("String"), vec![custom("Lowercase")]),
+                    field("handle", TypeRef::new("String"), vec![custom("Username")]),
+                ],
+            }),
+            docs: None,
+            generics: vec![],
+            attributes: TypeAttributes::default(),
+        };
+
+        let errors =
+            validate_value(&ty, &json!({ "slug": "Alice", "handle": "alice" })).unwrap_err();
+        let codes = errors
+            .iter()
+            .map(|e| (e.path.as_str(), e.code.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec![("slug", "custom"), ("handle", "custom_unregistered")]
+        );
+        assert_eq!(
+            errors[1].message,
+            "no server-side validator is registered for `Username`"
+        );
+    }
+
+    #[test]
+    fn enum_rules_follow_the_serde_representation() {
+        let name = || {
+            field(
+                "name",
+                TypeRef::new("String"),
+                vec![ValidationRule::Length {
+                    min: Some(1),
+                    max: None,
+                }],
+            )
+        };
+        let event = |representation: EnumRepresentation| GearMeshType {
+            name: "Event".to_string(),
+            kind: TypeKind::Enum(gear_mesh_core::EnumType {
+                variants: vec![
+                    EnumVariant {
+                        name: "Created".to_string(),
+                        content: VariantContent::Struct(vec![name()]),
+                        docs: None,
+                    },
+                    EnumVariant {
+                        name: "Ping".to_string(),
+                        content: VariantContent::Unit,
+                        docs: None,
+                    },
+                ],
+                representation,
+            }),
+            docs: None,
+            generics: vec![],
+            attributes: TypeAttributes::default(),
+        };
+        let paths = |ty: &GearMeshType, value: Value| {
+            validate_value(ty, &value)
+                .err()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|error| error.path)
+                .collect::<Vec<_>>()
+        };
+
+        let external = event(EnumRepresentation::External);
+        assert_eq!(
+            paths(&external, json!({ "Created": { "name": "" } })),
+            ["Created.name"]
+        );
+        assert!(paths(&external, json!("Ping")).is_empty());
+
+        let internal = event(EnumRepresentation::Internal {
+            tag: "type".to_string(),
+        });
+        assert_eq!(
+            paths(&internal, json!({ "type": "Created", "name": "" })),
+            ["name"]
+        );
+        assert!(paths(&internal, json!({ "type": "Created", "name": "a" })).is_empty());
+
+        let adjacent = event(EnumRepresentation::Adjacent {
+            tag: "t".to_string(),
+            content: "c".to_string(),
+        });
+        assert_eq!(
+            paths(&adjacent, json!({ "t": "Created", "c": { "name": "" } })),
+            ["c.name"]
+        );
+
+        let untagged = event(EnumRepresentation::Untagged);
+        assert_eq!(paths(&untagged, json!({ "name": "" })), ["name"]);
+        assert!(paths(&untagged, json!(null)).is_empty());
+    }
+
+    #[test]
+    fn string_length_counts_utf16_code_units() {
+        // "👍" is one char but two UTF-16 code units, like `"👍".length === 2` in JavaScript
+        let value = json!({
+            "name": "👍👍👍",
+            "age": null,
+            "password": "secret",
+            "password_confirmation": "secret",
+        });
+
+        let errors = validate_value(&signup(), &value).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "length");
+        assert_eq!(value_length(&json!("é👍")), Some(3));
+    }
+
+    #[test]
+    fn perl_classes_match_ascii_like_javascript() {
+        // JavaScript's `/^\d+$/` rejects "٣" (ARABIC-INDIC DIGIT THREE); Rust's `\d` would accept it
+        let pattern = r"^\d+$";
+        assert_eq!(
+            ValidationRule::Pattern(pattern.to_string()).to_zod_schema(false),
+            r".regex(/^\d+$/)"
+        );
+        assert!(matches_pattern(pattern, "123"));
+        assert!(!matches_pattern(pattern, "٣"));
+
+        assert!(!matches_pattern(r"^\w+$", "é"));
+        assert!(!matches_pattern(r"^\s$", "\u{85}"));
+        assert!(matches_pattern(r"^\s$", "\u{feff}"));
+        assert!(matches_pattern(r"a\b", "aé"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Test the `ValidJson` axum extractor
+
+#![cfg(feature = "axum")]
+
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode, header};
+use axum::routing::post;
+use gear_mesh::GearMesh;
+use gear_mesh::axum::ValidJson;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, GearMesh)]
+struct Address {
+    #[validate(length(min = 1))]
+    city: String,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, GearMesh)]
+#[serde(rename_all = "camelCase")]
+struct CreateAccount {
+    #[validate(length(min = 1, max = 20))]
+    display_name: String,
+    #[validate(email)]
+    email: String,
+    #[validate(range(min = 18, max = 130))]
+    age: Option<i32>,
+    password: String,
+    #[validate(cross_field(match = "password", message = "Passwords must match"))]
+    password_confirmation: String,
+    address: Address,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, GearMesh)]
+#[gear_mesh(branded)]
+struct Username(#[validate(length(min = 3))] String);
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, GearMesh)]
+struct Invite {
+    username: Username,
+}
+
+async fn create_account(ValidJson(request): ValidJson<CreateAccount>) -> String {
+    request.display_name
+}
+
+async fn create_invite(ValidJson(request): ValidJson<Invite>) -> String {
+    request.username.0
+}
+
+async fn post_json(body: Value) -> (StatusCode, Vec<u8>) {
+    let app = Router::new().route("/accounts", post(create_account));
+    send_json(app, "/accounts", body).await
+}
+
+async fn send_json(app: Router, uri: &str, body: Value) -> (StatusCode, Vec<u8>) {
+    let request = Request::post(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, bytes.to_vec())
+}
+
+#[tokio::test]
+async fn valid_body_reaches_handler() {
+    let (status, body) = post_json(json!({
+        "displayName": "Alice",
+        "email": "alice@example.com",
+        "age": null,
+        "password": "secret",
+        "passwordConfirmation": "secret",
+        "address": { "city": "Tokyo" },
+    }))
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, b"Alice");
+}
+
+#[tokio::test]
+async fn invalid_body_is_rejected_with_field_errors() {
+    let (status, body) = post_json(json!({
+        "displayName": "",
+        "email": "not-an-email",
+        "age": 12,
+        "password": "secret",
+        "passwordConfirmation": "different",
+        "address": { "city": "" },
+    }))
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["message"], "Validation failed");
+    let errors = body["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| {
+            (
+                error["path"].as_str().unwrap().to_string(),
+                error["code"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        vec![
+            ("displayName".to_string(), "length".to_string()),
+            ("email".to_string(), "email".to_string()),
+            ("age".to_string(), "range".to_string()),
+            (
+                "passwordConfirmation".to_string(),
+                "cross_field".to_string()
+            ),
+            ("address.city".to_string(), "length".to_string()),
+        ]
+    );
+    assert_eq!(body["errors"][3]["message"], "Passwords must match");
+}
+
+#[tokio::test]
+async fn type_mismatch_is_reported_as_validation_error() {
+    let (status, body) = post_json(json!({
+        "displayName": 42,
+        "email": "alice@example.com",
+        "password": "secret",
+        "passwordConfirmation": "secret",
+        "address": { "city": "Tokyo" },
+    }))
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["errors"][0]["code"], "invalid_type");
+}
+
+#[tokio::test]
+async fn newtype_rules_are_enforced() {
+    let app = || Router::new().route("/invites", post(create_invite));
+
+    let (status, body) = send_json(app(), "/invites", json!({ "username": "alice" })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, b"alice");
+
+    let (status, body) = send_json(app(), "/invites", json!({ "username": "a" })).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["errors"][0]["path"], "username");
+    assert_eq!(body["errors"][0]["code"], "length");
+}
+
+#[test]
+fn validation_error_response_is_exported_to_typescript() {
+    use gear_mesh::{GearMeshExport, GeneratorConfig, TypeScriptGenerator};
+
+    let mut generator = TypeScriptGenerator::new(GeneratorConfig::new().with_jsdoc(false));
+    let output = generator.generate(&[
+        gear_mesh::FieldError::gear_mesh_type(),
+        gear_mesh::axum::ValidationErrorResponse::gear_mesh_type(),
+    ]);
+
+    assert!(output.contains("export interface FieldError {"));
+    assert!(output.contains("export interface ValidationErrorResponse {"));
+    assert!(output.contains("errors: FieldError[];"));
+}
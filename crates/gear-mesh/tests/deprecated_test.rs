@@ -0,0 +1,59 @@
+//! Test `#[deprecated]` on types, fields and variants
+
+use gear_mesh::{GearMesh, GearMeshExport, GeneratorConfig, TypeScriptGenerator};
+
+#[allow(dead_code)]
+#[derive(GearMesh)]
+struct User {
+    email: String,
+    /// Login name
+    #[deprecated(since = "1.2.0", note = "Use `email` instead")]
+    login: String,
+}
+
+#[allow(dead_code)]
+#[derive(GearMesh)]
+enum Status {
+    Active,
+    #[deprecated]
+    Legacy,
+}
+
+#[allow(dead_code)]
+#[derive(GearMesh)]
+#[deprecated = "Use `User`"]
+struct Account {
+    id: i32,
+}
+
+// 非推奨の型を参照するため
+#[allow(deprecated)]
+fn types() -> Vec<gear_mesh::GearMeshType> {
+    vec![
+        User::gear_mesh_type(),
+        Status::gear_mesh_type(),
+        Account::gear_mesh_type(),
+    ]
+}
+
+#[test]
+fn test_deprecated_members_are_tagged() {
+    let output = TypeScriptGenerator::new(GeneratorConfig::new()).generate(&types());
+
+    assert!(output.contains(
+        "    /** Login name @deprecated Since 1.2.0. Use `email` instead */\n    login: string;"
+    ));
+    assert!(output.contains("    /** @deprecated */\n    | \"Legacy\""));
+    assert!(output.contains("/**\n * @deprecated Use `User`\n */\nexport interface Account {"));
+}
+
+#[test]
+fn test_omit_deprecated_leaves_members_out() {
+    let config = GeneratorConfig::new().with_omit_deprecated(true);
+    let output = TypeScriptGenerator::new(config).generate(&types());
+
+    assert!(output.contains("email: string;"));
+    assert!(!output.contains("login"));
+    assert!(output.contains("export type Status = \"Active\";"));
+    assert!(!output.contains("Account"));
+}
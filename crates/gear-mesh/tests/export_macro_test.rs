@@ -0,0 +1,32 @@
+//! Test that `export_types!` writes the helper modules its output imports
+
+use gear_mesh::{GearMesh, export_types};
+
+#[allow(dead_code)]
+#[derive(GearMesh)]
+#[gear_mesh(branded)]
+struct UserId(i32);
+
+#[allow(dead_code)]
+#[derive(GearMesh)]
+struct User {
+    id: UserId,
+}
+
+#[test]
+fn test_export_types_writes_brand_module() {
+    let dir = std::env::temp_dir().join(format!("gear-mesh-export-{}", std::process::id()));
+    let output = dir.join("types.ts").to_string_lossy().into_owned();
+
+    export_types! {
+        output = &output,
+        types = [UserId, User]
+    }
+
+    let types = std::fs::read_to_string(&output).unwrap();
+    assert!(types.contains("import type { Brand } from './gear-mesh.brand';"));
+    let brand = std::fs::read_to_string(dir.join(gear_mesh::BRAND_MODULE)).unwrap();
+    assert!(brand.contains("export type Brand<T, B>"));
+
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,49 @@
+//! Test generic branded IDs keyed on a PhantomData marker
+
+use std::marker::PhantomData;
+
+use gear_mesh::{GearMesh, GearMeshExport, GeneratorConfig, TypeScriptGenerator};
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(GearMesh, Serialize)]
+#[gear_mesh(branded)]
+#[serde(transparent)]
+struct Id<T>(i64, PhantomData<T>);
+
+#[allow(dead_code)]
+#[derive(GearMesh)]
+struct User {
+    id: Id<User>,
+}
+
+#[allow(dead_code)]
+#[derive(GearMesh)]
+struct Order {
+    id: Id<Order>,
+    user_id: Id<User>,
+}
+
+#[test]
+fn test_generic_brand_is_keyed_on_marker() {
+    let types = vec![
+        Id::<User>::gear_mesh_type(),
+        User::gear_mesh_type(),
+        Order::gear_mesh_type(),
+    ];
+
+    let config = GeneratorConfig::new().with_bigint(false).with_zod(true);
+    let output = TypeScriptGenerator::new(config).generate(&types);
+
+    assert!(output.contains("export type Id<T extends string> = Brand<number, `Id<${T}>`>;"));
+    assert!(output.contains("id: Id<\"Order\">;"));
+    assert!(output.contains("user_id: Id<\"User\">;"));
+    assert!(output.contains("user_id: IdSchema(\"User\"),"));
+}
+
+#[test]
+fn test_generic_brand_serializes_as_inner_value() {
+    let id = Id::<User>(1, PhantomData);
+
+    assert_eq!(serde_json::to_string(&id).unwrap(), "1");
+}
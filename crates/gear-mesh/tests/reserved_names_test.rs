@@ -0,0 +1,48 @@
+//! Test raw identifiers and names that collide with TypeScript keywords or globals
+
+use gear_mesh::{GearMesh, GearMeshExport, GeneratorConfig, TypeScriptGenerator};
+
+#[allow(dead_code, non_camel_case_types)]
+#[derive(GearMesh)]
+struct r#default {
+    r#type: String,
+    delete: bool,
+}
+
+#[allow(dead_code)]
+#[derive(GearMesh)]
+enum Error {
+    NotFound,
+    r#Internal,
+}
+
+#[allow(dead_code)]
+#[derive(GearMesh)]
+struct Response {
+    body: r#default,
+    error: Option<Error>,
+}
+
+#[test]
+fn test_raw_identifiers_use_the_serde_name() {
+    assert_eq!(r#default::type_name(), "default");
+    assert_eq!(r#default::gear_mesh_type().name, "default");
+}
+
+#[test]
+fn test_reserved_type_names_are_mangled() {
+    let types = [
+        r#default::gear_mesh_type(),
+        Error::gear_mesh_type(),
+        Response::gear_mesh_type(),
+    ];
+    let output = TypeScriptGenerator::new(GeneratorConfig::new().with_zod(true)).generate(&types);
+
+    assert!(
+        output.contains("export interface default_ {\n    type: string;\n    delete: boolean;")
+    );
+    assert!(output.contains("export type Error_ =\n    | \"NotFound\"\n    | \"Internal\";"));
+    assert!(output.contains("    body: default_;\n    error: Error_ | null;"));
+    assert!(output.contains("export const Error_Schema = z.enum([\"NotFound\", \"Internal\"]);"));
+    assert!(output.contains("    body: default_Schema,"));
+}
@@ -0,0 +1,61 @@
+//! Test source locations recorded by the derive macro
+
+use gear_mesh::{GearMesh, GearMeshExport, GeneratorConfig, SourceMap, TypeScriptGenerator};
+
+const USER_LINE: u32 = line!() + 4;
+
+#[allow(dead_code)]
+#[derive(GearMesh)]
+struct User {
+    /// Display name
+    name: String,
+    #[serde(rename = "mail")]
+    email: String,
+}
+
+#[test]
+fn test_source_location_is_recorded() {
+    let ty = User::gear_mesh_type();
+    let source = ty.attributes.source.as_ref().unwrap();
+
+    assert_eq!(source.file, file!());
+    assert_eq!(source.line, USER_LINE);
+    assert_eq!(source.module, module_path!());
+    assert_eq!(source.path(), format!("{}::User", module_path!()));
+
+    let gear_mesh::TypeKind::Struct(s) = &ty.kind else {
+        panic!("expected a struct");
+    };
+    assert_eq!(s.fields[0].attributes.line, Some(USER_LINE + 2));
+    assert_eq!(s.fields[1].attributes.line, Some(USER_LINE + 4));
+}
+
+#[test]
+fn test_source_links_and_source_map() {
+    let types = vec![User::gear_mesh_type()];
+    let config = GeneratorConfig::new().with_source_links(true);
+    let output = TypeScriptGenerator::new(config).generate(&types);
+
+    let file = file!();
+    assert!(output.contains(&format!(
+        "/**\n * @see {file}:{USER_LINE}\n */\nexport interface User {{"
+    )));
+    assert!(output.contains(&format!(
+        "    /** Display name @see {file}:{} */\n    name: string;",
+        USER_LINE + 2
+    )));
+    assert!(output.contains(&format!(
+        "    /** @see {file}:{} */\n    mail: string;",
+        USER_LINE + 4
+    )));
+
+    let json = SourceMap::collect(&types, |_| "types.ts".to_string()).render_json();
+    let map: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let user = &map["declarations"]["User"];
+    assert_eq!(map["version"], 1);
+    assert_eq!(user["output"], "types.ts");
+    assert_eq!(user["file"], file);
+    assert_eq!(user["line"], USER_LINE);
+    assert_eq!(user["rust"], format!("{}::User", module_path!()));
+    assert_eq!(user["fields"]["mail"], USER_LINE + 4);
+}